@@ -2,30 +2,44 @@ pub mod config;
 pub mod data;
 pub mod handler;
 pub mod header;
+pub mod help;
+pub mod keymap;
+pub mod session;
 pub mod shell;
 pub mod state;
 pub mod widget;
 
 use std::sync::Arc;
 
+use rusoto_core::Region;
+use rusoto_logs::{DescribeLogStreamsRequest, FilterLogEventsRequest};
 use thiserror::Error;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::Mutex;
+use tui::layout::Rect;
+use tui::style::Style;
 
 use crate::app::config::AppConfig;
 use crate::app::data::AppData;
 use crate::app::header::widget::HeaderWidgetSet;
+use crate::app::session::widget::SessionWidgetSet;
 use crate::app::shell::widget::ShellWidgetSet;
 use crate::app::state::AppSharedState;
 use crate::app::widget::{AppWidgetSet, AppWidgetStates};
 use crate::aws::cwlogs::client::{ClientFactory, ClientFactoryError};
-use crate::aws::cwlogs::group::RusotoCwlGroupCursor;
+use crate::aws::cwlogs::event::{RusotoCwlEventContextLoader, RusotoCwlEventCursor};
+use crate::aws::cwlogs::group::{
+    CwlGroupSource, RusotoCwlGroupCursor, RusotoCwlGroupDetailLoader, RusotoCwlGroupOps,
+};
+use crate::aws::cwlogs::stream::RusotoCwlStreamCursor;
 use crate::aws::profile::ProfileName;
 use crate::event::Action;
-use crate::preset::Preset;
 use crate::session::SessionId;
+use crate::time::TimeRange;
 use crate::ui::theme::Theme;
 use crate::ui::widget::debug::DebugWidget;
+use crate::ui::widget::help::HelpWidget;
+use crate::ui::widget::status::{StatusMessage, StatusWidget};
 
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -37,6 +51,8 @@ pub enum AppError {
 pub enum AppFocus {
     Shell,
     Header,
+    /// The command line, returning to the focus kept in the header state.
+    Command,
     Session(SessionId),
 }
 
@@ -58,11 +74,12 @@ pub struct App {
 }
 
 impl App {
-    pub fn new(config: AppConfig, data: AppData, action_sender: Sender<Action>) -> App {
+    pub fn new(config: AppConfig, mut data: AppData, action_sender: Sender<Action>) -> App {
+        data.time_display = config.time_display;
         App {
+            widget_states: AppWidgetStates::with_config(&config),
             config,
             data,
-            widget_states: AppWidgetStates::default(),
             theme: Theme::default(),
             focus: AppFocus::default(),
             action_sender,
@@ -71,16 +88,98 @@ impl App {
         }
     }
 
+    pub fn region(&self, profile_name: &ProfileName) -> Region {
+        match self.data.region.as_ref() {
+            Some(region) => region.clone(),
+            None => self.client_factory.region(profile_name),
+        }
+    }
+
     pub fn create_groups_cursor(
         &self,
-        profile_name: ProfileName,
-        preset: Preset,
+        source: &CwlGroupSource,
     ) -> Result<RusotoCwlGroupCursor, AppError> {
-        let client = self.client_factory.new_client(profile_name)?;
-        let cursor = RusotoCwlGroupCursor::new(client, preset.into());
+        let client = self
+            .client_factory
+            .new_client(source.profile.clone(), source.region.clone())?;
+        let cursor = RusotoCwlGroupCursor::new(client, source.into());
         Ok(cursor)
     }
 
+    pub fn create_group_detail_loader(
+        &self,
+        source: &CwlGroupSource,
+    ) -> Result<RusotoCwlGroupDetailLoader, AppError> {
+        let client = self
+            .client_factory
+            .new_client(source.profile.clone(), source.region.clone())?;
+        Ok(RusotoCwlGroupDetailLoader::new(client))
+    }
+
+    pub fn create_group_ops(&self, source: &CwlGroupSource) -> Result<RusotoCwlGroupOps, AppError> {
+        let client = self
+            .client_factory
+            .new_client(source.profile.clone(), source.region.clone())?;
+        Ok(RusotoCwlGroupOps::new(client))
+    }
+
+    pub fn create_streams_cursor(
+        &self,
+        source: &CwlGroupSource,
+        group_name: &str,
+    ) -> Result<RusotoCwlStreamCursor, AppError> {
+        let client = self
+            .client_factory
+            .new_client(source.profile.clone(), source.region.clone())?;
+        let request = DescribeLogStreamsRequest {
+            log_group_name: group_name.to_string(),
+            order_by: Some("LastEventTime".to_string()),
+            descending: Some(true),
+            ..Default::default()
+        };
+        Ok(RusotoCwlStreamCursor::new(client, request))
+    }
+
+    /// Lists events of the group in the time range, of the stream or of every stream.
+    pub fn create_events_cursor(
+        &self,
+        source: &CwlGroupSource,
+        group_name: &str,
+        stream_name: Option<&str>,
+        range: &TimeRange,
+        filter_pattern: Option<String>,
+    ) -> Result<RusotoCwlEventCursor, AppError> {
+        let client = self
+            .client_factory
+            .new_client(source.profile.clone(), source.region.clone())?;
+        let request = FilterLogEventsRequest {
+            log_group_name: group_name.to_string(),
+            log_stream_names: stream_name.map(|s| vec![s.to_string()]),
+            start_time: Some(range.start_time()),
+            end_time: Some(range.end_time()),
+            filter_pattern,
+            ..Default::default()
+        };
+        Ok(RusotoCwlEventCursor::new(client, request))
+    }
+
+    /// Reads lines around an event of the stream.
+    pub fn create_event_context_loader(
+        &self,
+        source: &CwlGroupSource,
+        group_name: &str,
+        stream_name: &str,
+    ) -> Result<RusotoCwlEventContextLoader, AppError> {
+        let client = self
+            .client_factory
+            .new_client(source.profile.clone(), source.region.clone())?;
+        Ok(RusotoCwlEventContextLoader::new(
+            client,
+            group_name.to_string(),
+            stream_name.to_string(),
+        ))
+    }
+
     pub fn state(&self) -> &Mutex<AppSharedState> {
         &self.shared_state
     }
@@ -91,7 +190,11 @@ impl App {
 
     pub fn widgets(&mut self) -> AppWidgetSet {
         let header = HeaderWidgetSet::new(self.theme, self.focus);
-        let shell = ShellWidgetSet::new(self.theme, &self.widget_states.shell);
+        let time_display = self.data.time_display;
+        let shell = ShellWidgetSet::new(self.theme, &self.widget_states.shell, time_display);
+        let session = SessionWidgetSet::new(self.theme, time_display);
+        let status = StatusWidget::with_style(Style::default(), self.theme.error);
+        let help = HelpWidget::with_style(self.theme.active_widget);
         let debug = self
             .data
             .debug()
@@ -100,12 +203,25 @@ impl App {
         AppWidgetSet {
             data: &self.data,
             states: &mut self.widget_states,
+            focus: self.focus,
             header,
             shell,
+            session,
+            status,
+            help,
             debug,
         }
     }
 
+    /// Text and URL of a link in the status, with where it is drawn.
+    pub fn status_link(&self) -> Option<(Rect, &str, &str)> {
+        let area = self.widget_states.status.link_area()?;
+        match self.data.status.message() {
+            Some(StatusMessage::Link { text, url }) => Some((area, text, url)),
+            _ => None,
+        }
+    }
+
     pub fn action_sender(&self) -> Sender<Action> {
         self.action_sender.clone()
     }
@@ -1,11 +1,64 @@
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use dirs_next::config_dir;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::aws::cwlogs::event::{LevelDetector, LevelRule};
+use crate::aws::cwlogs::group::StorageCostConfig;
 use crate::event::Key;
+use crate::time::TimeDisplay;
+use crate::ui::widget::groups::{ColumnWidth, GroupColumn, GroupColumns};
+
+#[derive(Debug, Error)]
+pub enum AppConfigError {
+    #[error("could not read {}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("invalid config file {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+}
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub struct AppConfig {
     pub quit_key: Key,
     pub tick_rate: Duration,
+    /// Stops listing log groups of an account after this many groups, and narrows down groups
+    /// by name prefix on the server side instead.
+    pub max_cached_groups: usize,
+    pub group_columns: GroupColumns,
+    pub storage_cost: StorageCostConfig,
+    /// Initial way of showing times, switched by a key at runtime.
+    pub time_display: TimeDisplay,
+    pub level_detector: LevelDetector,
+}
+
+impl AppConfig {
+    /// Loads `config.toml` in the config directory, or the default config if it does not exist.
+    pub fn load() -> Result<AppConfig, AppConfigError> {
+        match default_config_dir().map(|dir| dir.join("config.toml")) {
+            Some(path) if path.exists() => AppConfig::from_file(&path),
+            _ => Ok(AppConfig::default()),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<AppConfig, AppConfigError> {
+        let s = std::fs::read_to_string(path).map_err(|source| AppConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let file: ConfigFile = toml::from_str(&s).map_err(|source| AppConfigError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        Ok(AppConfig::from(file))
+    }
 }
 
 impl Default for AppConfig {
@@ -13,6 +66,187 @@ impl Default for AppConfig {
         AppConfig {
             quit_key: Key::Char('q'),
             tick_rate: Duration::from_millis(100),
+            max_cached_groups: 10_000,
+            group_columns: GroupColumns::default(),
+            storage_cost: StorageCostConfig::default(),
+            time_display: TimeDisplay::default(),
+            level_detector: LevelDetector::default(),
+        }
+    }
+}
+
+pub fn default_config_dir() -> Option<PathBuf> {
+    config_dir().map(|p| p.join("razy-cwlogs"))
+}
+
+/// ```toml
+/// max_cached_groups = 10000
+/// # local, utc, an offset like "+09:00", or relative
+/// time_display = "utc"
+///
+/// [groups]
+/// columns = ["name", "retention", "stored"]
+///
+/// [groups.widths]
+/// name = "50%"
+/// retention = 12
+///
+/// [analytics]
+/// price_per_gib = 0.033
+/// top_groups = 20
+/// prefix_depth = 2
+///
+/// [levels]
+/// # tried in order, until one of them finds the level of a message
+/// rules = ["json:$.level", "json:$.severity", "lambda", "prefix"]
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    max_cached_groups: Option<usize>,
+    time_display: Option<TimeDisplay>,
+    groups: GroupsConfigFile,
+    analytics: AnalyticsConfigFile,
+    levels: LevelsConfigFile,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GroupsConfigFile {
+    columns: Option<Vec<GroupColumn>>,
+    widths: HashMap<GroupColumn, ColumnWidth>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AnalyticsConfigFile {
+    price_per_gib: Option<f64>,
+    top_groups: Option<usize>,
+    prefix_depth: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct LevelsConfigFile {
+    rules: Option<Vec<LevelRule>>,
+}
+
+impl From<ConfigFile> for AppConfig {
+    fn from(file: ConfigFile) -> Self {
+        let default = AppConfig::default();
+        let columns = file
+            .groups
+            .columns
+            .unwrap_or_else(|| default.group_columns.visible().map(|e| e.column).collect());
+        let (analytics, cost) = (file.analytics, default.storage_cost);
+
+        AppConfig {
+            max_cached_groups: file.max_cached_groups.unwrap_or(default.max_cached_groups),
+            time_display: file.time_display.unwrap_or(default.time_display),
+            group_columns: GroupColumns::new(&columns, &file.groups.widths),
+            storage_cost: StorageCostConfig {
+                price_per_gib: analytics.price_per_gib.unwrap_or(cost.price_per_gib),
+                top_groups: analytics.top_groups.unwrap_or(cost.top_groups),
+                prefix_depth: analytics.prefix_depth.unwrap_or(cost.prefix_depth),
+            },
+            level_detector: file
+                .levels
+                .rules
+                .map(LevelDetector::new)
+                .unwrap_or(default.level_detector),
+            ..default
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<AppConfig, toml::de::Error> {
+        toml::from_str::<ConfigFile>(s).map(AppConfig::from)
+    }
+
+    #[test]
+    fn test_empty() {
+        let config = parse("").unwrap();
+        assert_eq!(10_000, config.max_cached_groups);
+        assert_eq!(GroupColumns::default(), config.group_columns);
+        assert_eq!(StorageCostConfig::default(), config.storage_cost);
+        assert_eq!(TimeDisplay::default(), config.time_display);
+        assert_eq!(LevelDetector::default(), config.level_detector);
+    }
+
+    #[test]
+    fn test_levels() {
+        let config = parse(r#"levels = { rules = ["json:$.lvl", "prefix"] }"#).unwrap();
+        assert_eq!(
+            LevelDetector::new(vec!["json:$.lvl".parse().unwrap(), LevelRule::Prefix]),
+            config.level_detector
+        );
+        assert!(parse(r#"levels = { rules = ["regex"] }"#).is_err());
+    }
+
+    #[test]
+    fn test_time_display() {
+        let config = parse(r#"time_display = "relative""#).unwrap();
+        assert_eq!(TimeDisplay::Relative, config.time_display);
+        assert!(parse(r#"time_display = "JST""#).is_err());
+    }
+
+    #[test]
+    fn test_analytics() {
+        let config = parse(
+            r#"
+            [analytics]
+            price_per_gib = 0.05
+            prefix_depth = 3
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            StorageCostConfig {
+                price_per_gib: 0.05,
+                top_groups: 20,
+                prefix_depth: 3,
+            },
+            config.storage_cost
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        use GroupColumn::*;
+
+        let config = parse(
+            r#"
+            max_cached_groups = 500
+
+            [groups]
+            columns = ["name", "retention", "kms_key"]
+
+            [groups.widths]
+            name = "50%"
+            kms_key = 40
+            "#,
+        )
+        .unwrap();
+        assert_eq!(500, config.max_cached_groups);
+
+        let visible = config.group_columns.visible().collect::<Vec<_>>();
+        assert_eq!(
+            vec![Name, Retention, KmsKey],
+            visible.iter().map(|e| e.column).collect::<Vec<_>>()
+        );
+        assert_eq!(ColumnWidth::Percentage(50), visible[0].width);
+        assert_eq!(Retention.default_width(), visible[1].width);
+        assert_eq!(ColumnWidth::Length(40), visible[2].width);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert!(parse(r#"groups = { columns = ["unknown"] }"#).is_err());
+        assert!(parse(r#"groups = { widths = { name = "200%" } }"#).is_err());
+        assert!(parse("unknown = 1").is_err());
+    }
+}
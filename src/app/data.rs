@@ -1,19 +1,45 @@
-use crate::aws::cwlogs::group::{CwlGroupStore, RusotoCwlGroupCursor};
+use std::collections::HashMap;
+
+use futures::future::AbortHandle;
+use rusoto_core::Region;
+
+use crate::aws::cwlogs::group::{
+    CwlGroup, CwlGroupCache, CwlGroupCacheState, CwlGroupSource, CwlGroupStore,
+};
 use crate::aws::profile::ProfileStore;
+use crate::bookmark::BookmarkStore;
 use crate::event::Key;
+use crate::launch::LaunchTarget;
 use crate::preset::{Preset, PresetName, PresetStore};
-use crate::session::{Session, SessionSet};
+use crate::session::{Session, SessionId, SessionSet};
+use crate::time::TimeDisplay;
+use crate::ui::widget::command::CommandData;
 use crate::ui::widget::debug::DebugData;
+use crate::ui::widget::groups::GroupDetailData;
 use crate::ui::widget::search::SearchData;
+use crate::ui::widget::status::StatusData;
 
 pub struct AppData {
     pub presets: PresetStore,
     pub profiles: ProfileStore,
     pub groups: CwlGroupStore,
+    pub group_cache: CwlGroupCache,
     pub search: SearchData,
-    sessions: SessionSet,
+    pub command: CommandData,
+    pub group_detail: Option<GroupDetailData>,
+    pub status: StatusData,
+    pub sessions: SessionSet,
+    pub bookmarks: BookmarkStore,
+    pub time_display: TimeDisplay,
+    /// Region given on startup, used over the one of each profile.
+    pub region: Option<Region>,
+    /// View to open once its group is listed.
+    pub launch: Option<LaunchTarget>,
     debug: Option<DebugData>,
-    groups_cursor: Option<RusotoCwlGroupCursor>,
+    /// Tasks listing the groups of each source, stopped when the source is listed again.
+    groups_tasks: HashMap<CwlGroupSource, AbortHandle>,
+    groups_source: Option<CwlGroupSource>,
+    groups_prefix: Option<String>,
 }
 
 impl AppData {
@@ -29,10 +55,20 @@ impl AppData {
             presets,
             profiles,
             groups,
+            group_cache: CwlGroupCache::default(),
             search,
+            command: CommandData::default(),
+            group_detail: None,
+            status: StatusData::default(),
             sessions,
+            bookmarks: BookmarkStore::default(),
+            time_display: TimeDisplay::default(),
+            region: None,
+            launch: None,
             debug,
-            groups_cursor: None,
+            groups_tasks: HashMap::new(),
+            groups_source: None,
+            groups_prefix: None,
         }
     }
 
@@ -74,12 +110,71 @@ impl AppData {
         }
     }
 
-    pub fn set_groups_cursor(&mut self, cursor: RusotoCwlGroupCursor) {
-        self.groups_cursor = Some(cursor);
+    /// Keeps the task listing the groups of the source, stopping the one listing it before.
+    pub fn set_groups_task(&mut self, source: CwlGroupSource, task: AbortHandle) {
+        if let Some(previous) = self.groups_tasks.insert(source, task) {
+            previous.abort();
+        }
+    }
+
+    pub fn groups_source(&self) -> Option<&CwlGroupSource> {
+        self.groups_source.as_ref()
+    }
+
+    pub fn groups_prefix(&self) -> Option<&str> {
+        self.groups_prefix.as_deref()
+    }
+
+    /// Shows cached groups of the source, narrowed down by the name prefix.
+    pub fn show_groups(&mut self, source: CwlGroupSource, prefix: Option<String>) {
+        self.groups_source = Some(source);
+        self.groups_prefix = prefix;
+        self.reload_groups();
+    }
+
+    pub fn reload_groups(&mut self) {
+        self.groups.clear();
+        if let Some(source) = self.groups_source.as_ref() {
+            let prefix = self.groups_prefix.as_deref().unwrap_or("");
+            self.groups.extend(
+                self.group_cache
+                    .groups(source)
+                    .iter()
+                    .filter(|g| g.group_name.starts_with(prefix))
+                    .cloned(),
+            );
+            self.groups.set_state(self.group_cache.state(source));
+        }
+    }
+
+    /// Appends a page of groups, and returns whether it belongs to the current listing.
+    pub fn append_groups(
+        &mut self,
+        source: &CwlGroupSource,
+        generation: u64,
+        groups: Vec<CwlGroup>,
+        state: CwlGroupCacheState,
+    ) -> bool {
+        if !self
+            .group_cache
+            .append(source, generation, groups.clone(), state)
+        {
+            return false;
+        }
+        if self.groups_source.as_ref() == Some(source) {
+            let prefix = self.groups_prefix.as_deref().unwrap_or("");
+            self.groups.extend(
+                groups
+                    .into_iter()
+                    .filter(|g| g.group_name.starts_with(prefix)),
+            );
+            self.groups.set_state(self.group_cache.state(source));
+        }
+        true
     }
 
-    pub fn create_session(&mut self, session: Session) {
-        self.sessions.insert(session);
+    pub fn create_session(&mut self, session: Session) -> SessionId {
+        self.sessions.insert(session)
     }
 }
 
@@ -1,6 +1,9 @@
 use async_trait::async_trait;
 
+use crate::app::header::command::CommandHandler;
 use crate::app::header::handler::HeaderHandler;
+use crate::app::help::HelpHandler;
+use crate::app::session::handler::SessionHandler;
 use crate::app::shell::handler::ShellHandler;
 use crate::app::{App, AppFocus};
 use crate::event::{Action, Key};
@@ -13,11 +16,16 @@ pub trait HandleKey {
 pub async fn handle_key_input(app: &mut App, key: Key) -> anyhow::Result<()> {
     app.data.debug_key(key);
 
+    if app.widget_states.help.is_some() {
+        return HelpHandler.handle_key(app, key).await;
+    }
+
     let focus = app.focus;
     match focus {
         AppFocus::Shell => ShellHandler.handle_key(app, key).await,
         AppFocus::Header => HeaderHandler.handle_key(app, key).await,
-        AppFocus::Session(_session_id) => Ok(()),
+        AppFocus::Command => CommandHandler.handle_key(app, key).await,
+        AppFocus::Session(id) => SessionHandler::new(id).handle_key(app, key).await,
     }
 }
 
@@ -26,11 +34,10 @@ pub trait HandleAction {
     async fn handle_action(&self, app: &mut App, action: Action) -> anyhow::Result<()>;
 }
 
+/// Routes actions by the session they belong to, regardless of the focus.
 pub async fn handle_action(app: &mut App, action: Action) -> anyhow::Result<()> {
-    let focus = app.focus;
-    match focus {
-        AppFocus::Shell => ShellHandler.handle_action(app, action).await,
-        AppFocus::Header => Ok(()),
-        AppFocus::Session(_session_id) => Ok(()),
+    match action.session_id() {
+        Some(id) => SessionHandler::new(id).handle_action(app, action).await,
+        None => ShellHandler.handle_action(app, action).await,
     }
 }
@@ -1,3 +1,4 @@
+pub mod command;
 pub mod handler;
 pub mod state;
 pub mod widget;
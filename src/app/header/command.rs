@@ -0,0 +1,205 @@
+use async_trait::async_trait;
+use chrono::Utc;
+
+use crate::app::data::AppData;
+use crate::app::handler::HandleKey;
+use crate::app::session::handler::SessionHandler;
+use crate::app::shell::handler::{export_marked_groups, launch, on_quit};
+use crate::app::shell::state::ShellSelection;
+use crate::app::{App, AppFocus};
+use crate::command::{complete, Command, ExportFormat, REGIONS};
+use crate::event::{Action, Key};
+use crate::launch::LaunchTarget;
+use crate::preset::{Preset, PresetName};
+use crate::time::{TimeZoneChoice, TIME_RANGE_PRESETS};
+use crate::ui::theme::{Theme, ThemeName};
+
+pub struct CommandHandler;
+
+#[async_trait]
+impl HandleKey for CommandHandler {
+    async fn handle_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        let command = &mut app.data.command;
+        match key {
+            Key::Char(c) => command.push(c),
+            Key::BackSpace if command.input().is_empty() => close_command_line(app),
+            Key::BackSpace => command.pop(),
+            Key::Tab => {
+                let completion =
+                    complete(app.data.command.input(), |name| arguments(&app.data, name));
+                app.data.command.set_completion(completion);
+            }
+            Key::Up => {
+                if let Some(line) = command.history.older().map(str::to_string) {
+                    command.set_input(&line);
+                }
+            }
+            Key::Down => {
+                let line = command.history.newer().map(str::to_string);
+                command.set_input(line.as_deref().unwrap_or_default());
+            }
+            Key::Esc => close_command_line(app),
+            Key::Enter => on_enter(app).await?,
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Opens the command line over the focused view.
+pub fn open_command_line(app: &mut App) {
+    app.widget_states.header.command_return = app.focus;
+    app.data.command.clear();
+    app.focus = AppFocus::Command;
+}
+
+fn close_command_line(app: &mut App) {
+    app.data.command.clear();
+    app.focus = app.widget_states.header.command_return;
+}
+
+/// What the argument of the command can be, for completion.
+fn arguments(data: &AppData, name: &str) -> Vec<String> {
+    match name {
+        "profile" => data.profiles.all().map(ToString::to_string).collect(),
+        "preset" => data.presets.all().map(|p| p.name.to_string()).collect(),
+        "region" => REGIONS.iter().map(ToString::to_string).collect(),
+        "group" => data
+            .groups
+            .order_by_name_asc()
+            .map(|g| g.group_name.clone())
+            .collect(),
+        "since" => TIME_RANGE_PRESETS.iter().map(ToString::to_string).collect(),
+        "export" => vec!["csv".to_string()],
+        "theme" => ThemeName::ALL
+            .iter()
+            .map(|t| t.as_str().to_string())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Runs the command, keeping the command line open with the error if it is not valid.
+async fn on_enter(app: &mut App) -> anyhow::Result<()> {
+    let line = app.data.command.input().to_string();
+    let zone = match app.widget_states.header.command_return {
+        AppFocus::Session(id) => app.data.sessions.get(id).map(|s| s.time_zone),
+        _ => None,
+    };
+    let zone = zone.unwrap_or_else(TimeZoneChoice::default);
+    let command = match Command::parse(&line, &zone, &Utc::now()) {
+        Ok(command) => command,
+        Err(e) => {
+            app.data.command.set_error(e.to_string());
+            return Ok(());
+        }
+    };
+    app.data.command.history.push(&line);
+
+    if let Err(msg) = validate(app, &command) {
+        app.data.command.set_error(msg);
+        return Ok(());
+    }
+    close_command_line(app);
+    execute(app, command).await
+}
+
+/// Checks the command can run in the current view, before changing anything.
+fn validate(app: &App, command: &Command) -> Result<(), String> {
+    match command {
+        Command::Profile(name) if !app.data.profiles.all().any(|p| p == name) => {
+            Err(format!("unknown profile `{}`", name))
+        }
+        Command::Preset(name) if !app.data.presets.all().any(|p| &p.name == name) => {
+            Err(format!("unknown preset `{}`", name))
+        }
+        Command::Preset(_) if app.data.groups_source().is_none() => {
+            Err(":preset needs a profile, run :profile <profile> first".to_string())
+        }
+        Command::Since(_) => match app.widget_states.header.command_return {
+            AppFocus::Session(id) if app.data.sessions.get(id).is_some() => Ok(()),
+            _ => Err(":since needs a session, open a group first".to_string()),
+        },
+        Command::Export(_) if app.data.groups_source().is_none() => {
+            Err(":export needs listed groups, run :profile <profile> first".to_string())
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn execute(app: &mut App, command: Command) -> anyhow::Result<()> {
+    match command {
+        Command::Profile(profile) => {
+            app.widget_states.shell.selection = Some(ShellSelection::Groups);
+            app.focus = AppFocus::Shell;
+            app.dispatch_action(Action::RequestLogGroups(profile, None))
+                .await;
+        }
+        Command::Preset(name) => {
+            let preset = app.data.presets.all().find(|p| p.name == name).cloned();
+            let profile = app.data.groups_source().map(|s| s.profile.clone());
+            if let Some(profile) = profile {
+                app.widget_states.shell.selection = Some(ShellSelection::Groups);
+                app.focus = AppFocus::Shell;
+                app.dispatch_action(Action::RequestLogGroups(profile, preset))
+                    .await;
+            }
+        }
+        Command::Region(region) => {
+            app.data
+                .status
+                .set_info(format!("region: {}", region.name()));
+            app.data.region = Some(region);
+            // list the groups again in the region.
+            if let Some(source) = app.data.groups_source().cloned() {
+                let preset = app
+                    .data
+                    .groups_prefix()
+                    .map(|prefix| Preset::new(PresetName::from("anonymous"), Some(prefix.into())));
+                app.dispatch_action(Action::RequestLogGroups(source.profile, preset))
+                    .await;
+            }
+        }
+        Command::Group(group_name) => {
+            let group = app
+                .data
+                .groups
+                .order_by_name_asc()
+                .find(|g| g.group_name == group_name)
+                .cloned();
+            match (app.data.groups_source().cloned(), group) {
+                (Some(source), Some(group)) => {
+                    app.dispatch_action(Action::OpenSession(source, Box::new(group)))
+                        .await;
+                }
+                // not listed yet, list groups with the name to open it.
+                (source, _) => {
+                    let target = LaunchTarget {
+                        profile: source.map(|s| s.profile),
+                        region: app.data.region.clone(),
+                        group_name: Some(group_name),
+                        ..Default::default()
+                    };
+                    launch(app, target).await;
+                }
+            }
+        }
+        Command::Since(range) => {
+            if let AppFocus::Session(id) = app.focus {
+                let zone = app.data.sessions.get(id).map(|s| s.time_zone);
+                SessionHandler::new(id).set_time_range(app, range, zone.unwrap_or_default());
+            }
+        }
+        Command::Export(ExportFormat::Csv) => export_marked_groups(app),
+        Command::Theme(name) => {
+            app.theme = Theme::named(name);
+            app.data
+                .status
+                .set_info(format!("theme: {}", name.as_str()));
+        }
+        Command::Quit => on_quit(app).await?,
+    }
+
+    Ok(())
+}
@@ -1,6 +1,9 @@
+use crate::app::AppFocus;
 use crate::ui::widget::search::SearchState;
 
 #[derive(Debug, Clone, Default)]
 pub struct HeaderState {
     pub search: SearchState,
+    /// Focus to return to when the command line is closed.
+    pub command_return: AppFocus,
 }
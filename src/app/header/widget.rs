@@ -2,6 +2,7 @@ use crate::app::data::AppData;
 use crate::app::header::state::HeaderState;
 use crate::app::AppFocus;
 use crate::ui::theme::Theme;
+use crate::ui::widget::command::CommandWidget;
 use crate::ui::widget::search::SearchWidget;
 use crate::ui::widget::CustomWidget;
 use tui::buffer::Buffer;
@@ -9,6 +10,8 @@ use tui::layout::Rect;
 
 pub struct HeaderWidgetSet {
     pub search: SearchWidget,
+    /// The command line shown in place of the search while it is focused.
+    pub command: Option<CommandWidget>,
 }
 
 impl HeaderWidgetSet {
@@ -19,8 +22,15 @@ impl HeaderWidgetSet {
             theme.normal_widget
         };
 
+        let command = if app_focus == AppFocus::Command {
+            Some(CommandWidget::with_style(theme.active_widget, theme.error))
+        } else {
+            None
+        };
+
         HeaderWidgetSet {
-            search: SearchWidget::with_style(style),
+            search: SearchWidget::with_style(style, theme.error),
+            command,
         }
     }
 }
@@ -36,7 +46,11 @@ impl CustomWidget for HeaderWidgetSet {
         data: &Self::Data,
         state: &mut Self::State,
     ) {
-        self.search
-            .render_app_widget(area, buf, &data.search, &mut state.search);
+        match self.command {
+            Some(command) => command.render_app_widget(area, buf, &data.command, &mut ()),
+            None => self
+                .search
+                .render_app_widget(area, buf, &data.search, &mut state.search),
+        }
     }
 }
@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+
+use crate::app::handler::HandleKey;
+use crate::app::keymap::{HelpEntry, HelpSection};
+use crate::app::session::keymap::{
+    EVENTS_KEYS, EVENT_CONTEXT_KEYS, EVENT_DETAIL_KEYS, INVOCATIONS_KEYS, SESSION_KEYS,
+    STREAMS_KEYS, TIME_RANGE_KEYS,
+};
+use crate::app::session::state::{SessionPane, SessionPopup};
+use crate::app::shell::keymap::{
+    BOOKMARKS_KEYS, DELETE_KEYS, GROUPS_KEYS, POPUP_KEYS, SHELL_KEYS, TAG_INPUT_KEYS,
+};
+use crate::app::shell::state::{ShellPopup, ShellSelection};
+use crate::app::{App, AppFocus};
+use crate::command::COMMANDS;
+use crate::event::Key;
+use crate::ui::widget::help::HelpState;
+
+/// Handles keys while the help is shown over the focused view.
+pub struct HelpHandler;
+
+#[async_trait]
+impl HandleKey for HelpHandler {
+    async fn handle_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        let help = match app.widget_states.help.as_mut() {
+            Some(help) => help,
+            None => return Ok(()),
+        };
+
+        if help.is_searching() {
+            match key {
+                Key::Char(c) => help.push(c),
+                Key::BackSpace => help.pop(),
+                Key::Enter => help.stop_search(),
+                Key::Esc => help.clear_search(),
+                _ => (),
+            }
+            return Ok(());
+        }
+
+        match key {
+            Key::Up | Key::Char('k') => help.scroll_up(),
+            Key::Down | Key::Char('j') => help.scroll_down(),
+            Key::Char('g') => help.scroll_to_top(),
+            Key::Char('G') => help.scroll_to_bottom(),
+            Key::Char('/') => help.start_search(),
+            Key::Esc if help.is_filtered() => help.clear_search(),
+            Key::Esc | Key::Char('?') | Key::Char('q') => app.widget_states.help = None,
+            _ => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Shows the keys of the focused view, listed from the keymaps its handler reads.
+/// Keys of the popups the view opens follow, the open popup's keys come first under its name.
+pub fn open_help(app: &mut App) {
+    let (context, mut sections, popups, open) = match app.focus {
+        AppFocus::Session(id) => {
+            let state = app.widget_states.sessions.get(&id);
+            let time_range = HelpSection::of_keymap("Time range", TIME_RANGE_KEYS);
+            let (pane, popups) = match state.map(|state| state.pane).unwrap_or_default() {
+                SessionPane::Streams => (
+                    HelpSection::of_keymap("Streams", STREAMS_KEYS),
+                    vec![time_range, HelpSection::of_keymap("Delete", DELETE_KEYS)],
+                ),
+                SessionPane::Events => (
+                    HelpSection::of_keymap("Events", EVENTS_KEYS),
+                    vec![
+                        time_range,
+                        HelpSection::of_keymap("Event detail", EVENT_DETAIL_KEYS),
+                        HelpSection::of_keymap("Event context", EVENT_CONTEXT_KEYS),
+                    ],
+                ),
+                SessionPane::Invocations => (
+                    HelpSection::of_keymap("Invocations", INVOCATIONS_KEYS),
+                    vec![time_range],
+                ),
+            };
+            let open = match state.and_then(|state| state.popup.as_ref()) {
+                Some(SessionPopup::Delete(_)) => Some(("Delete", "Delete")),
+                Some(SessionPopup::TimeRange(_)) => Some(("Time range", "Time range")),
+                Some(SessionPopup::EventDetail(_)) => Some(("Event detail", "Event detail")),
+                Some(SessionPopup::EventContext(_)) => Some(("Event context", "Event context")),
+                None => None,
+            };
+            let context = pane.title.clone();
+            (
+                context,
+                vec![pane, HelpSection::of_keymap("Session", SESSION_KEYS)],
+                popups,
+                open,
+            )
+        }
+        _ => {
+            let shell = HelpSection::of_keymap("Shell", SHELL_KEYS)
+                .entry(HelpEntry::new(app.config.quit_key.to_string(), "quit"));
+            let mut popups = vec![HelpSection::of_keymap("Bookmarks", BOOKMARKS_KEYS)];
+            let open = app
+                .widget_states
+                .shell
+                .popup
+                .as_ref()
+                .map(|popup| match popup {
+                    ShellPopup::Bookmarks(_) => ("Bookmarks", "Bookmarks"),
+                    ShellPopup::Delete(_) => ("Delete", "Delete"),
+                    ShellPopup::TagInput(_) => ("Tag input", "Tag input"),
+                    ShellPopup::Columns(_) => ("Columns", "Popup"),
+                    ShellPopup::Detail(_) => ("Group detail", "Popup"),
+                    ShellPopup::Retention(_) => ("Retention", "Popup"),
+                    ShellPopup::BulkMenu(_) => ("Bulk actions", "Popup"),
+                    ShellPopup::BulkSummary(_) => ("Bulk summary", "Popup"),
+                    ShellPopup::Analytics(_) => ("Analytics", "Popup"),
+                });
+            let (context, sections) = match app.widget_states.shell.selection {
+                Some(ShellSelection::Groups) => {
+                    popups.extend(vec![
+                        HelpSection::of_keymap("Delete", DELETE_KEYS),
+                        HelpSection::of_keymap("Tag input", TAG_INPUT_KEYS),
+                        HelpSection::of_keymap("Popup", POPUP_KEYS),
+                    ]);
+                    (
+                        "Groups",
+                        vec![HelpSection::of_keymap("Groups", GROUPS_KEYS), shell],
+                    )
+                }
+                Some(ShellSelection::Presets) => ("Presets", vec![shell]),
+                Some(ShellSelection::Profiles) => ("Profiles", vec![shell]),
+                None => ("Shell", vec![shell]),
+            };
+            (context.to_string(), sections, popups, open)
+        }
+    };
+
+    let (open_sections, popups): (Vec<_>, Vec<_>) = popups
+        .into_iter()
+        .partition(|popup| open.map(|(_, title)| title) == Some(popup.title.as_str()));
+    let context = match open.filter(|_| !open_sections.is_empty()) {
+        Some((name, _)) => name.to_string(),
+        None => context,
+    };
+    sections = open_sections
+        .into_iter()
+        .chain(sections)
+        .chain(popups)
+        .collect();
+
+    let commands = COMMANDS.iter().map(|c| {
+        let keys = match c.argument {
+            Some(argument) => format!(":{} {}", c.name, argument),
+            None => format!(":{}", c.name),
+        };
+        HelpEntry::new(keys, c.description)
+    });
+    sections.push(HelpSection::new("Commands").entries(commands));
+
+    app.widget_states.help = Some(HelpState::new(context, sections));
+}
@@ -0,0 +1,175 @@
+use crate::event::Key;
+
+/// Keys of a view bound to what they do, read by its handler and listed in the help.
+#[derive(Debug, Copy, Clone)]
+pub struct KeyBinding<B: 'static> {
+    pub keys: &'static [Key],
+    pub binding: B,
+    pub description: &'static str,
+}
+
+impl<B> KeyBinding<B> {
+    pub const fn new(keys: &'static [Key], binding: B, description: &'static str) -> Self {
+        KeyBinding {
+            keys,
+            binding,
+            description,
+        }
+    }
+}
+
+/// What the key does in the keymap.
+pub fn find_binding<B: Copy>(keymap: &[KeyBinding<B>], key: Key) -> Option<B> {
+    keymap
+        .iter()
+        .find(|b| b.keys.contains(&key))
+        .map(|b| b.binding)
+}
+
+/// Describes keys like `<Up>/k`, with runs of characters like `1-9` shortened.
+pub fn describe_keys(keys: &[Key]) -> String {
+    let mut described = Vec::new();
+    let mut i = 0;
+    while i < keys.len() {
+        let mut end = i;
+        while let (Key::Char(a), Some(Key::Char(b))) = (keys[end], keys.get(end + 1)) {
+            if (a as u32) + 1 != *b as u32 {
+                break;
+            }
+            end += 1;
+        }
+        if end - i >= 2 {
+            described.push(format!("{}-{}", keys[i], keys[end]));
+        } else {
+            described.extend(keys[i..=end].iter().map(ToString::to_string));
+        }
+        i = end + 1;
+    }
+    described.join("/")
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HelpEntry {
+    pub keys: String,
+    pub description: String,
+}
+
+impl HelpEntry {
+    pub fn new(keys: impl Into<String>, description: impl Into<String>) -> Self {
+        HelpEntry {
+            keys: keys.into(),
+            description: description.into(),
+        }
+    }
+
+    fn contains(&self, query: &str) -> bool {
+        self.keys.to_lowercase().contains(query) || self.description.to_lowercase().contains(query)
+    }
+}
+
+/// Entries of the help under a title, like the keys of a pane.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct HelpSection {
+    pub title: String,
+    pub entries: Vec<HelpEntry>,
+}
+
+impl HelpSection {
+    pub fn new(title: impl Into<String>) -> Self {
+        HelpSection {
+            title: title.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// A section listing the keymap.
+    pub fn of_keymap<B>(title: impl Into<String>, keymap: &[KeyBinding<B>]) -> Self {
+        HelpSection::new(title).entries(
+            keymap
+                .iter()
+                .map(|b| HelpEntry::new(describe_keys(b.keys), b.description)),
+        )
+    }
+
+    pub fn entry(mut self, entry: HelpEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    pub fn entries(mut self, entries: impl IntoIterator<Item = HelpEntry>) -> Self {
+        self.entries.extend(entries);
+        self
+    }
+
+    /// The entries whose keys or description contain the query, ignoring case.
+    pub fn filter(&self, query: &str) -> Option<HelpSection> {
+        let query = query.to_lowercase();
+        let entries = self
+            .entries
+            .iter()
+            .filter(|e| e.contains(&query))
+            .cloned()
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            None
+        } else {
+            Some(HelpSection {
+                title: self.title.clone(),
+                entries,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+    enum Binding {
+        Up,
+        Mute,
+    }
+
+    const KEYS: &[KeyBinding<Binding>] = &[
+        KeyBinding::new(
+            &[Key::Up, Key::Char('k')],
+            Binding::Up,
+            "select the previous",
+        ),
+        KeyBinding::new(
+            &[Key::Char('1'), Key::Char('2'), Key::Char('3')],
+            Binding::Mute,
+            "mute the group",
+        ),
+    ];
+
+    #[test]
+    fn test_find_binding() {
+        assert_eq!(Some(Binding::Up), find_binding(KEYS, Key::Char('k')));
+        assert_eq!(Some(Binding::Mute), find_binding(KEYS, Key::Char('2')));
+        assert_eq!(None, find_binding(KEYS, Key::Char('K')));
+    }
+
+    #[test]
+    fn test_describe_keys() {
+        assert_eq!("<Up>/k", describe_keys(KEYS[0].keys));
+        assert_eq!("1-3", describe_keys(KEYS[1].keys));
+        assert_eq!(
+            "a/b/<Space>",
+            describe_keys(&[Key::Char('a'), Key::Char('b'), Key::Char(' ')])
+        );
+    }
+
+    #[test]
+    fn test_filter() {
+        let section = HelpSection::of_keymap("Events", KEYS);
+        assert_eq!(2, section.filter("").unwrap().entries.len());
+        assert_eq!(
+            vec![HelpEntry::new("1-3", "mute the group")],
+            section.filter("MUTE").unwrap().entries
+        );
+        assert_eq!(1, section.filter("<up>").unwrap().entries.len());
+        assert_eq!(None, section.filter("delete"));
+    }
+}
@@ -0,0 +1,951 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use tokio::time::delay_for;
+
+use crate::app::handler::{HandleAction, HandleKey};
+use crate::app::header::command::open_command_line;
+use crate::app::help::open_help;
+use crate::app::keymap::find_binding;
+use crate::app::session::keymap::{
+    EventContextBinding, EventDetailBinding, EventsBinding, InvocationsBinding, SessionBinding,
+    StreamsBinding, TimeRangeBinding, EVENTS_KEYS, EVENT_CONTEXT_KEYS, EVENT_DETAIL_KEYS,
+    INVOCATIONS_KEYS, SESSION_KEYS, STREAMS_KEYS, TIME_RANGE_KEYS,
+};
+use crate::app::session::state::{SessionPane, SessionPopup, SessionState};
+use crate::app::shell::handler::{cycle_time_display, show_console_link, yank};
+use crate::app::shell::keymap::{DeleteBinding, DELETE_KEYS};
+use crate::app::{App, AppError, AppFocus};
+use crate::aws::cwlogs::console::{event_link, events_link, group_link, insights_link};
+use crate::aws::cwlogs::event::{
+    ContextDirection, CwlEvent, CwlEventContextLoader, CwlEventCursor, CwlEventPage,
+    MergedCwlEventCursor,
+};
+use crate::aws::cwlogs::group::CwlGroupOps;
+use crate::aws::cwlogs::stream::{CwlStream, CwlStreamCursor};
+use crate::bookmark::Bookmark;
+use crate::event::{Action, Key};
+use crate::json::is_json_message;
+use crate::launch::LaunchTarget;
+use crate::session::SessionId;
+use crate::time::{TimeRangeSpec, TimeZoneChoice};
+use crate::ui::widget::delete::DeleteTarget;
+use crate::ui::widget::event_context::EventContextState;
+
+const PAGE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Streams are listed from the most recent one, older ones are rarely needed.
+const MAX_STREAMS: usize = 1000;
+
+/// Events kept in memory at most, narrow down the time range to see the rest.
+const MAX_EVENTS: usize = 10_000;
+
+/// Lines read at once on either side of an event.
+const CONTEXT_LINES: i64 = 20;
+
+pub struct SessionHandler {
+    id: SessionId,
+}
+
+impl SessionHandler {
+    pub fn new(id: SessionId) -> Self {
+        SessionHandler { id }
+    }
+
+    fn state_mut<'a>(&self, app: &'a mut App) -> &'a mut SessionState {
+        app.widget_states.sessions.entry(self.id).or_default()
+    }
+}
+
+#[async_trait]
+impl HandleKey for SessionHandler {
+    async fn handle_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        if app.data.sessions.get(self.id).is_none() {
+            app.focus = AppFocus::Shell;
+            return Ok(());
+        }
+
+        if self.state_mut(app).popup.is_some() {
+            self.on_popup_key(app, key).await;
+            return Ok(());
+        }
+
+        let session = app.data.sessions.get(self.id).unwrap();
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        match find_binding(SESSION_KEYS, key) {
+            Some(SessionBinding::SelectPrevious) => state.select_previous(session),
+            Some(SessionBinding::SelectNext) => state.select_next(session),
+            Some(SessionBinding::TimeRange) => state.open_time_range_picker(session),
+            Some(SessionBinding::CycleTimeDisplay) => cycle_time_display(app),
+            Some(SessionBinding::CommandLine) => open_command_line(app),
+            Some(SessionBinding::InsightsLink) => self.show_insights_link(app),
+            Some(SessionBinding::ConsoleLink) => self.show_console_link(app),
+            Some(SessionBinding::YankViewLink) => self.yank_view_link(app),
+            Some(SessionBinding::Help) => open_help(app),
+            None => match state.pane {
+                SessionPane::Streams => self.on_streams_key(app, key)?,
+                SessionPane::Events => self.on_events_key(app, key)?,
+                SessionPane::Invocations => self.on_invocations_key(app, key)?,
+            },
+        }
+
+        Ok(())
+    }
+}
+
+impl SessionHandler {
+    fn on_streams_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        let session = match app.data.sessions.get(self.id) {
+            Some(session) => session,
+            None => return Ok(()),
+        };
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        match find_binding(STREAMS_KEYS, key) {
+            Some(StreamsBinding::Back) => app.focus = AppFocus::Shell,
+            Some(StreamsBinding::Refresh) => fetch_log_streams(app, self.id)?,
+            Some(StreamsBinding::Delete) => state.open_delete_confirm(session),
+            Some(StreamsBinding::OpenEvents) => {
+                let stream = state.streams.selected_stream(&session.streams);
+                if let Some(name) = stream.map(|s| s.stream_name.clone()) {
+                    self.open_events(app, Some(name))?;
+                }
+            }
+            Some(StreamsBinding::OpenAllEvents) => self.open_events(app, None)?,
+            Some(StreamsBinding::YankName) => {
+                let stream = state.streams.selected_stream(&session.streams);
+                if let Some(name) = stream.map(|s| s.stream_name.clone()) {
+                    yank(app, "the stream name", &name);
+                }
+            }
+            Some(StreamsBinding::Invocations) if session.is_lambda() => {
+                state.show_invocations();
+                self.load_events(app, None)?;
+            }
+            Some(StreamsBinding::Invocations) => {
+                let msg = "invocations are only shown for /aws/lambda/ groups";
+                app.data.status.set_error(msg.to_string());
+            }
+            None => (),
+        }
+        Ok(())
+    }
+
+    fn on_events_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let merged = app
+            .data
+            .sessions
+            .get(self.id)
+            .map(|session| session.is_merged())
+            .unwrap_or(false);
+        match find_binding(EVENTS_KEYS, key) {
+            Some(EventsBinding::Back) if merged => app.focus = AppFocus::Shell,
+            Some(EventsBinding::Back) => state.pane = SessionPane::Streams,
+            Some(EventsBinding::ToggleMute) if merged => {
+                // keys other than 1 to 9 bound to the toggle are ignored.
+                let i = match key {
+                    Key::Char(c) => c.to_digit(10).and_then(|d| (d as usize).checked_sub(1)),
+                    _ => None,
+                };
+                if let Some(i) = i {
+                    self.toggle_mute(app, i);
+                }
+            }
+            Some(EventsBinding::ToggleMute) => (),
+            Some(EventsBinding::ToggleDelta) => state.events.toggle_delta(),
+            Some(EventsBinding::CycleLevel) => {
+                state.events.cycle_min_level();
+                let msg = match state.events.min_level() {
+                    Some(level) => format!("hiding events below {}", level),
+                    None => "showing events of every level".to_string(),
+                };
+                app.data.status.set_info(msg);
+            }
+            Some(EventsBinding::Refresh) => fetch_log_events(app, self.id)?,
+            Some(EventsBinding::PreviousBucket) => state.histogram.select_previous(),
+            Some(EventsBinding::NextBucket) => state.histogram.select_next(),
+            Some(EventsBinding::CycleSplit) => state.histogram.cycle_split(),
+            Some(EventsBinding::Zoom) => self.zoom_into_bucket(app)?,
+            Some(EventsBinding::Detail) => {
+                if let Some(session) = app.data.sessions.get(self.id) {
+                    state.open_event_detail(session);
+                }
+            }
+            Some(EventsBinding::Bookmark) => self.toggle_bookmark(app),
+            Some(EventsBinding::ToggleAnchor) => state.events.toggle_anchor(),
+            Some(EventsBinding::Yank) => self.yank_events(app),
+            Some(EventsBinding::Context) => {
+                let opened = app
+                    .data
+                    .sessions
+                    .get(self.id)
+                    .map(|session| state.open_event_context(session))
+                    .unwrap_or(false);
+                if opened {
+                    fetch_event_context(app, self.id, ContextDirection::Before)?;
+                    fetch_event_context(app, self.id, ContextDirection::After)?;
+                }
+            }
+            Some(EventsBinding::ClearFilters) => {
+                if let Some(session) = app.data.sessions.get_mut(self.id) {
+                    session.event_filters.clear();
+                    session.filter_text = None;
+                }
+                fetch_log_events(app, self.id)?;
+            }
+            Some(EventsBinding::ClearColumns) => {
+                if let Some(session) = app.data.sessions.get_mut(self.id) {
+                    session.event_columns.clear();
+                }
+            }
+            None => (),
+        }
+        Ok(())
+    }
+
+    /// Bookmarks the selected event or removes its bookmark, saving the bookmarks.
+    fn toggle_bookmark(&self, app: &mut App) {
+        let (session, state) = match app.data.sessions.get(self.id) {
+            Some(session) => (
+                session,
+                app.widget_states.sessions.entry(self.id).or_default(),
+            ),
+            None => return,
+        };
+        let event = match state.events.selected_event(&session.events) {
+            Some(event) => event,
+            None => return,
+        };
+        let bookmark = Bookmark::new(&session.source, session.group_name_of(event), event);
+        let msg = if app.data.bookmarks.toggle(bookmark) {
+            "bookmarked the event"
+        } else {
+            "removed the bookmark"
+        };
+        match app.data.bookmarks.save() {
+            Ok(()) => app.data.status.set_info(msg.to_string()),
+            Err(e) => app.data.status.set_error(e.to_string()),
+        }
+    }
+
+    /// Links the selected event or stream, or the listed events or the group without one.
+    fn show_console_link(&self, app: &mut App) {
+        let (session, state) = match app.data.sessions.get(self.id) {
+            Some(session) => (
+                session,
+                app.widget_states.sessions.entry(self.id).or_default(),
+            ),
+            None => return,
+        };
+        let region = &session.source.region;
+        let range = session.time_range.resolve(&Utc::now());
+        let filter_pattern = session.filter_pattern();
+        let event = match state.pane {
+            SessionPane::Events => state.events.selected_event(&session.events),
+            _ => None,
+        };
+        let link = match (state.pane, event) {
+            (_, Some(event)) => event_link(
+                region,
+                session.group_name_of(event),
+                &event.stream_name,
+                &event.event_time,
+            ),
+            (SessionPane::Streams, None) => match state.streams.selected_stream(&session.streams) {
+                Some(stream) => events_link(
+                    region,
+                    &session.group.group_name,
+                    Some(&stream.stream_name),
+                    &range,
+                    filter_pattern.as_deref(),
+                ),
+                None => group_link(region, &session.group.group_name),
+            },
+            _ if session.is_merged() => return self.show_insights_link(app),
+            _ => events_link(
+                region,
+                &session.group.group_name,
+                session.events_stream.as_deref(),
+                &range,
+                filter_pattern.as_deref(),
+            ),
+        };
+        show_console_link(app, link);
+    }
+
+    /// Copies a link opening the group, stream, time range and filter of the session on startup.
+    fn yank_view_link(&self, app: &mut App) {
+        let uri = match app.data.sessions.get(self.id) {
+            Some(session) if session.is_merged() => {
+                let msg = "a merged view can not be linked";
+                app.data.status.set_error(msg.to_string());
+                return;
+            }
+            Some(session) => LaunchTarget::of_session(session).to_uri(),
+            None => return,
+        };
+        yank(app, "the link of the view", &uri);
+    }
+
+    /// Links a Logs Insights query over the groups and time range of the session.
+    fn show_insights_link(&self, app: &mut App) {
+        let session = match app.data.sessions.get(self.id) {
+            Some(session) => session,
+            None => return,
+        };
+        let group_names = if session.is_merged() {
+            session
+                .merged_groups
+                .iter()
+                .map(|g| g.group_name.as_str())
+                .collect()
+        } else {
+            vec![session.group.group_name.as_str()]
+        };
+        let link = insights_link(
+            &session.source.region,
+            &group_names,
+            session.events_stream.as_deref(),
+            &session.time_range.resolve(&Utc::now()),
+        );
+        show_console_link(app, link);
+    }
+
+    /// Copies the message of the selected event, or the marked range of events one per line.
+    fn yank_events(&self, app: &mut App) {
+        let (session, state) = match app.data.sessions.get(self.id) {
+            Some(session) => (
+                session,
+                app.widget_states.sessions.entry(self.id).or_default(),
+            ),
+            None => return,
+        };
+        let ranged = state.events.has_anchor();
+        let events = state.events.selected_range(&session.events);
+        if events.is_empty() {
+            return;
+        }
+        let text = events
+            .iter()
+            .map(|event| event.message.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let what = if ranged {
+            format!("{} events", events.len())
+        } else {
+            "the message".to_string()
+        };
+        state.events.clear_anchor();
+        yank(app, &what, &text);
+    }
+
+    /// Hides or shows the events of the nth group of a merged view.
+    fn toggle_mute(&self, app: &mut App, i: usize) {
+        let group = app
+            .data
+            .sessions
+            .get(self.id)
+            .and_then(|session| session.merged_groups.get(i));
+        let group_name = match group {
+            Some(group) => group.group_name.clone(),
+            None => return,
+        };
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let msg = if state.events.toggle_mute(&group_name) {
+            format!("muted {}", group_name)
+        } else {
+            format!("unmuted {}", group_name)
+        };
+        app.data.status.set_info(msg);
+    }
+
+    /// Narrows down the time range to the selected bucket of the histogram.
+    fn zoom_into_bucket(&self, app: &mut App) -> anyhow::Result<()> {
+        let session = match app.data.sessions.get_mut(self.id) {
+            Some(session) => session,
+            None => return Ok(()),
+        };
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let range = session.time_range.resolve(&Utc::now());
+        let bucket = match state.histogram.selected_range(range) {
+            Some(bucket) => bucket,
+            None => return Ok(()),
+        };
+
+        session.time_range = TimeRangeSpec::Between(bucket.start, bucket.end);
+        let msg = format!(
+            "time range: {}",
+            session.time_range.describe(&session.time_zone)
+        );
+        app.data.status.set_info(msg);
+        state.show_events();
+        fetch_log_events(app, self.id)
+    }
+
+    fn on_invocations_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        match find_binding(INVOCATIONS_KEYS, key) {
+            Some(InvocationsBinding::Back) => state.pane = SessionPane::Streams,
+            Some(InvocationsBinding::Refresh) => fetch_log_events(app, self.id)?,
+            Some(InvocationsBinding::Toggle) => {
+                if let Some(session) = app.data.sessions.get(self.id) {
+                    state.invocations.toggle(&session.invocations());
+                }
+            }
+            None => (),
+        }
+        Ok(())
+    }
+
+    /// Lists events of the stream, or of every stream, in the time range of the session.
+    fn open_events(&self, app: &mut App, stream_name: Option<String>) -> anyhow::Result<()> {
+        self.state_mut(app).show_events();
+        self.load_events(app, stream_name)
+    }
+
+    fn load_events(&self, app: &mut App, stream_name: Option<String>) -> anyhow::Result<()> {
+        if let Some(session) = app.data.sessions.get_mut(self.id) {
+            session.events_stream = stream_name;
+        }
+        fetch_log_events(app, self.id)
+    }
+}
+
+impl SessionHandler {
+    async fn on_popup_key(&self, app: &mut App, key: Key) {
+        let state = self.state_mut(app);
+        let confirm = match state.popup.as_mut() {
+            Some(SessionPopup::Delete(confirm)) => confirm,
+            Some(SessionPopup::TimeRange(_)) => return self.on_time_range_key(app, key),
+            Some(SessionPopup::EventDetail(_)) => return self.on_event_detail_key(app, key),
+            Some(SessionPopup::EventContext(_)) => return self.on_event_context_key(app, key),
+            None => return,
+        };
+
+        match find_binding(DELETE_KEYS, key) {
+            Some(DeleteBinding::ToggleDryRun) => confirm.toggle_dry_run(),
+            Some(DeleteBinding::Cancel) => state.close_popup(),
+            Some(DeleteBinding::Delete) if confirm.is_confirmed() => {
+                let (dry_run, message) = (confirm.is_dry_run(), confirm.dry_run_message());
+                let stream = match confirm.target() {
+                    DeleteTarget::Stream { stream, .. } => stream.clone(),
+                    _ => return,
+                };
+                state.close_popup();
+
+                if dry_run {
+                    app.data.status.set_info(message);
+                } else {
+                    app.dispatch_action(Action::DeleteLogStream(self.id, Box::new(stream)))
+                        .await;
+                }
+            }
+            Some(DeleteBinding::Delete) => (),
+            None => match key {
+                Key::Char(c) => confirm.push(c),
+                Key::BackSpace => confirm.pop(),
+                _ => (),
+            },
+        }
+    }
+}
+
+impl SessionHandler {
+    fn on_time_range_key(&self, app: &mut App, key: Key) {
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let picker = match state.popup.as_mut() {
+            Some(SessionPopup::TimeRange(picker)) => picker,
+            _ => return,
+        };
+
+        match find_binding(TIME_RANGE_KEYS, key) {
+            Some(TimeRangeBinding::SelectPrevious) => picker.select_previous(),
+            Some(TimeRangeBinding::SelectNext) => picker.select_next(),
+            Some(TimeRangeBinding::CycleZone) => picker.cycle_zone(),
+            Some(TimeRangeBinding::Cancel) => state.close_popup(),
+            Some(TimeRangeBinding::Help) => open_help(app),
+            Some(TimeRangeBinding::Apply) => {
+                let range = match picker.parse(&Utc::now()) {
+                    Some(range) => range,
+                    None => return,
+                };
+                let zone = picker.zone();
+                state.close_popup();
+                self.set_time_range(app, range, zone);
+            }
+            None => match key {
+                Key::Char(c) => picker.push(c),
+                Key::BackSpace => picker.pop(),
+                _ => (),
+            },
+        }
+    }
+
+    /// Lists events in the time range, refetching them unless the streams are shown.
+    pub fn set_time_range(&self, app: &mut App, range: TimeRangeSpec, zone: TimeZoneChoice) {
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let refetch = state.pane != SessionPane::Streams;
+        if let Some(session) = app.data.sessions.get_mut(self.id) {
+            session.time_range = range;
+            session.time_zone = zone;
+            let msg = format!("time range: {}", range.describe(&zone));
+            app.data.status.set_info(msg);
+        }
+        if refetch {
+            if let Err(e) = fetch_log_events(app, self.id) {
+                app.data.status.set_error(e.to_string());
+            }
+        }
+    }
+}
+
+impl SessionHandler {
+    fn on_event_detail_key(&self, app: &mut App, key: Key) {
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let detail = match state.popup.as_mut() {
+            Some(SessionPopup::EventDetail(detail)) => detail,
+            _ => return,
+        };
+
+        match find_binding(EVENT_DETAIL_KEYS, key) {
+            Some(EventDetailBinding::SelectPrevious) => detail.select_previous(),
+            Some(EventDetailBinding::SelectNext) => detail.select_next(),
+            Some(EventDetailBinding::ToggleTab) => detail.toggle_tab(),
+            Some(EventDetailBinding::ToggleCollapse) => detail.toggle_collapse(),
+            Some(EventDetailBinding::Close) => state.close_popup(),
+            Some(EventDetailBinding::Help) => open_help(app),
+            Some(EventDetailBinding::Filter) => {
+                let condition = match detail.selected_field().and_then(|f| f.filter_condition()) {
+                    Some(condition) => condition,
+                    None => return,
+                };
+                // JSON filter patterns never match messages with text around their JSON.
+                if !is_json_message(&detail.event().message) {
+                    let msg = "the message is not only JSON, so it cannot be filtered by a field";
+                    app.data.status.set_error(msg.to_string());
+                    return;
+                }
+                state.close_popup();
+
+                let added = app
+                    .data
+                    .sessions
+                    .get_mut(self.id)
+                    .map(|session| session.add_event_filter(condition.clone()))
+                    .unwrap_or(false);
+                if added {
+                    app.data.status.set_info(format!("filter: {}", condition));
+                    if let Err(e) = fetch_log_events(app, self.id) {
+                        app.data.status.set_error(e.to_string());
+                    }
+                }
+            }
+            Some(EventDetailBinding::Column) => {
+                let path = match detail.selected_field() {
+                    Some(field) => field.path.clone(),
+                    None => return,
+                };
+                state.close_popup();
+
+                if let Some(session) = app.data.sessions.get_mut(self.id) {
+                    let msg = format!("column: {}", path);
+                    if session.add_event_column(path) {
+                        app.data.status.set_info(msg);
+                    }
+                }
+            }
+            None => (),
+        }
+    }
+}
+
+impl SessionHandler {
+    fn on_event_context_key(&self, app: &mut App, key: Key) {
+        let state = app.widget_states.sessions.entry(self.id).or_default();
+        let context = match state.popup.as_mut() {
+            Some(SessionPopup::EventContext(context)) => context,
+            _ => return,
+        };
+
+        let direction = match find_binding(EVENT_CONTEXT_KEYS, key) {
+            Some(EventContextBinding::SelectPrevious) => return context.select_previous(),
+            Some(EventContextBinding::SelectNext) => return context.select_next(),
+            Some(EventContextBinding::Close) => return state.close_popup(),
+            Some(EventContextBinding::Help) => return open_help(app),
+            Some(EventContextBinding::Before) => ContextDirection::Before,
+            Some(EventContextBinding::After) => ContextDirection::After,
+            None => return,
+        };
+        if let Err(e) = fetch_event_context(app, self.id, direction) {
+            app.data.status.set_error(e.to_string());
+        }
+    }
+}
+
+#[async_trait]
+impl HandleAction for SessionHandler {
+    async fn handle_action(&self, app: &mut App, action: Action) -> anyhow::Result<()> {
+        match action {
+            Action::ReceiveLogStreams(_, generation, streams, done) => {
+                on_receive_log_streams(app, self.id, generation, streams, done).await
+            }
+            Action::FailLogStreams(_, generation, msg) => {
+                on_fail_log_streams(app, self.id, generation, msg).await
+            }
+            Action::DeleteLogStream(_, stream) => on_delete_log_stream(app, self.id, stream).await,
+            Action::RemoveLogStream(_, stream) => on_remove_log_stream(app, self.id, stream).await,
+            Action::ReceiveLogEvents(_, generation, events, done) => {
+                on_receive_log_events(app, self.id, generation, events, done).await
+            }
+            Action::FailLogEvents(_, generation, msg) => {
+                on_fail_log_events(app, self.id, generation, msg).await
+            }
+            Action::ReceiveEventContext(_, generation, direction, page) => {
+                on_receive_event_context(app, self.id, generation, direction, *page).await
+            }
+            Action::FailEventContext(_, generation, direction, msg) => {
+                on_fail_event_context(app, self.id, generation, direction, msg).await
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Lists streams of the session from scratch.
+pub fn fetch_log_streams(app: &mut App, id: SessionId) -> anyhow::Result<()> {
+    let session = match app.data.sessions.get_mut(id) {
+        Some(session) => session,
+        None => return Ok(()),
+    };
+    session.streams.clear();
+    session.streams.set_loading(true);
+    session.streams_generation += 1;
+    let generation = session.streams_generation;
+
+    let (source, group_name) = (session.source.clone(), session.group.group_name.clone());
+    let mut cursor = app.create_streams_cursor(&source, &group_name)?;
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let mut total = 0;
+        loop {
+            let (action, done) = match cursor.next().await {
+                Ok(Some(streams)) => {
+                    total += streams.len();
+                    let done = total >= MAX_STREAMS;
+                    (
+                        Action::ReceiveLogStreams(id, generation, streams, done),
+                        done,
+                    )
+                }
+                Ok(None) => (
+                    Action::ReceiveLogStreams(id, generation, Vec::new(), true),
+                    true,
+                ),
+                Err(e) => (
+                    Action::FailLogStreams(id, generation, format!("{}", e)),
+                    true,
+                ),
+            };
+
+            sender
+                .send(action)
+                .await
+                .expect("could not send a log streams response");
+            if done {
+                break;
+            }
+
+            // stay under the request rate limit of DescribeLogStreams.
+            delay_for(PAGE_INTERVAL).await;
+        }
+    });
+
+    Ok(())
+}
+
+/// Lists events of the session from scratch, in its time range.
+pub fn fetch_log_events(app: &mut App, id: SessionId) -> anyhow::Result<()> {
+    let session = match app.data.sessions.get_mut(id) {
+        Some(session) => session,
+        None => return Ok(()),
+    };
+    session.events.clear();
+    session.events.set_loading(true);
+    session.events_generation += 1;
+    let generation = session.events_generation;
+
+    let range = session.time_range.resolve(&Utc::now());
+    let (source, group_name) = (session.source.clone(), session.group.group_name.clone());
+    let stream_name = session.events_stream.clone();
+    let pattern = session.filter_pattern();
+    let merged_names = session
+        .merged_groups
+        .iter()
+        .map(|g| g.group_name.clone())
+        .collect::<Vec<_>>();
+    let mut cursor: Box<dyn CwlEventCursor + Send> = if merged_names.is_empty() {
+        Box::new(app.create_events_cursor(
+            &source,
+            &group_name,
+            stream_name.as_deref(),
+            &range,
+            pattern,
+        )?)
+    } else {
+        let cursors = merged_names
+            .into_iter()
+            .map(|name| {
+                let cursor =
+                    app.create_events_cursor(&source, &name, None, &range, pattern.clone())?;
+                Ok((name, cursor))
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        Box::new(MergedCwlEventCursor::new(cursors))
+    };
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let mut total = 0;
+        loop {
+            let (action, done) = match cursor.next().await {
+                Ok(Some(events)) => {
+                    total += events.len();
+                    let done = total >= MAX_EVENTS;
+                    (Action::ReceiveLogEvents(id, generation, events, done), done)
+                }
+                Ok(None) => (
+                    Action::ReceiveLogEvents(id, generation, Vec::new(), true),
+                    true,
+                ),
+                Err(e) => (
+                    Action::FailLogEvents(id, generation, format!("{}", e)),
+                    true,
+                ),
+            };
+
+            sender
+                .send(action)
+                .await
+                .expect("could not send a log events response");
+            if done {
+                break;
+            }
+
+            // stay under the request rate limit of FilterLogEvents.
+            delay_for(PAGE_INTERVAL).await;
+        }
+    });
+
+    Ok(())
+}
+
+fn event_context_mut(app: &mut App, id: SessionId) -> Option<&mut EventContextState> {
+    match app.widget_states.sessions.get_mut(&id)?.popup.as_mut() {
+        Some(SessionPopup::EventContext(context)) => Some(context),
+        _ => None,
+    }
+}
+
+/// Reads the next page of lines on a side of the event shown in the context popup.
+pub fn fetch_event_context(
+    app: &mut App,
+    id: SessionId,
+    direction: ContextDirection,
+) -> anyhow::Result<()> {
+    let source = match app.data.sessions.get(id) {
+        Some(session) => session.source.clone(),
+        None => return Ok(()),
+    };
+    let context = match event_context_mut(app, id) {
+        Some(context) => context,
+        None => return Ok(()),
+    };
+    // a page of the side is already on its way, reading it again would add its lines twice.
+    if context.is_loading_side(direction) {
+        return Ok(());
+    }
+    let generation = context.generation();
+    let position = context.context().next_position(direction);
+    let group_name = context.group_name().to_string();
+    let stream_name = context.context().target().stream_name.clone();
+    let position = match position {
+        Some(position) => position,
+        None => {
+            let msg = match direction {
+                ContextDirection::Before => "no earlier lines in the stream",
+                ContextDirection::After => "no later lines in the stream",
+            };
+            app.data.status.set_info(msg.to_string());
+            return Ok(());
+        }
+    };
+
+    let loader = app.create_event_context_loader(&source, &group_name, &stream_name)?;
+    if let Some(context) = event_context_mut(app, id) {
+        context.start_loading(direction);
+    }
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let action = match loader.load(direction, position, CONTEXT_LINES).await {
+            Ok(page) => Action::ReceiveEventContext(id, generation, direction, Box::new(page)),
+            Err(e) => Action::FailEventContext(id, generation, direction, format!("{}", e)),
+        };
+        sender
+            .send(action)
+            .await
+            .expect("could not send an event context response");
+    });
+
+    Ok(())
+}
+
+async fn on_receive_log_streams(
+    app: &mut App,
+    id: SessionId,
+    generation: u64,
+    streams: Vec<CwlStream>,
+    done: bool,
+) -> anyhow::Result<()> {
+    // pages of a listing replaced by a refresh are dropped.
+    let session = app.data.sessions.get_mut(id);
+    if let Some(session) = session.filter(|s| s.streams_generation == generation) {
+        session.streams.extend(streams.into_iter());
+        session.streams.set_loading(!done);
+    }
+    Ok(())
+}
+
+async fn on_fail_log_streams(
+    app: &mut App,
+    id: SessionId,
+    generation: u64,
+    msg: String,
+) -> anyhow::Result<()> {
+    match app.data.sessions.get_mut(id) {
+        Some(session) if session.streams_generation == generation => {
+            session.streams.set_loading(false)
+        }
+        _ => return Ok(()),
+    }
+    app.data.status.set_error(msg.clone());
+    app.data.debug_log(msg);
+    Ok(())
+}
+
+async fn on_receive_log_events(
+    app: &mut App,
+    id: SessionId,
+    generation: u64,
+    events: Vec<CwlEvent>,
+    done: bool,
+) -> anyhow::Result<()> {
+    // pages of a listing replaced by a refresh, a new time range or filter are dropped.
+    let session = app.data.sessions.get_mut(id);
+    if let Some(session) = session.filter(|s| s.events_generation == generation) {
+        session
+            .events
+            .extend_detected(events.into_iter(), &app.config.level_detector);
+        session.events.set_loading(!done);
+    }
+    Ok(())
+}
+
+async fn on_fail_log_events(
+    app: &mut App,
+    id: SessionId,
+    generation: u64,
+    msg: String,
+) -> anyhow::Result<()> {
+    match app.data.sessions.get_mut(id) {
+        Some(session) if session.events_generation == generation => {
+            session.events.set_loading(false)
+        }
+        _ => return Ok(()),
+    }
+    app.data.status.set_error(msg.clone());
+    app.data.debug_log(msg);
+    Ok(())
+}
+
+async fn on_receive_event_context(
+    app: &mut App,
+    id: SessionId,
+    generation: u64,
+    direction: ContextDirection,
+    mut page: CwlEventPage,
+) -> anyhow::Result<()> {
+    // pages read for a popup closed or opened again on another event are dropped.
+    let state = app.widget_states.sessions.get_mut(&id);
+    if let Some(context) = state.and_then(|s| s.event_context_mut(generation)) {
+        // lines of the context are not stored, so their JSON is parsed only here.
+        for event in page.events.iter_mut() {
+            event.level = app.config.level_detector.detect_message(&event.message);
+        }
+        context.extend(direction, page);
+    }
+    Ok(())
+}
+
+async fn on_fail_event_context(
+    app: &mut App,
+    id: SessionId,
+    generation: u64,
+    direction: ContextDirection,
+    msg: String,
+) -> anyhow::Result<()> {
+    let state = app.widget_states.sessions.get_mut(&id);
+    match state.and_then(|s| s.event_context_mut(generation)) {
+        Some(context) => context.fail_loading(direction),
+        None => return Ok(()),
+    }
+    app.data.status.set_error(msg.clone());
+    app.data.debug_log(msg);
+    Ok(())
+}
+
+async fn on_delete_log_stream(
+    app: &mut App,
+    id: SessionId,
+    stream: Box<CwlStream>,
+) -> anyhow::Result<()> {
+    let session = match app.data.sessions.get(id) {
+        Some(session) => session,
+        None => return Ok(()),
+    };
+    let ops = app.create_group_ops(&session.source)?;
+    let group_name = session.group.group_name.clone();
+    app.data
+        .status
+        .set_info(format!("deleting log stream {}...", stream.stream_name));
+
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let action = match ops.delete_stream(&group_name, &stream.stream_name).await {
+            Ok(()) => Action::RemoveLogStream(id, stream),
+            Err(e) => Action::Error(format!(
+                "could not delete log stream {}: {}",
+                stream.stream_name, e
+            )),
+        };
+        sender
+            .send(action)
+            .await
+            .expect("could not send a delete log stream response");
+    });
+
+    Ok(())
+}
+
+async fn on_remove_log_stream(
+    app: &mut App,
+    id: SessionId,
+    stream: Box<CwlStream>,
+) -> anyhow::Result<()> {
+    app.data
+        .status
+        .set_info(format!("deleted log stream {}", stream.stream_name));
+    if let Some(session) = app.data.sessions.get_mut(id) {
+        session.streams.remove(&stream);
+    }
+    Ok(())
+}
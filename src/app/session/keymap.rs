@@ -0,0 +1,418 @@
+use crate::app::keymap::KeyBinding;
+use crate::event::Key;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SessionBinding {
+    SelectPrevious,
+    SelectNext,
+    TimeRange,
+    CycleTimeDisplay,
+    CommandLine,
+    InsightsLink,
+    ConsoleLink,
+    YankViewLink,
+    Help,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum StreamsBinding {
+    Back,
+    Refresh,
+    Delete,
+    OpenEvents,
+    OpenAllEvents,
+    YankName,
+    Invocations,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EventsBinding {
+    Back,
+    ToggleMute,
+    ToggleDelta,
+    CycleLevel,
+    Refresh,
+    PreviousBucket,
+    NextBucket,
+    CycleSplit,
+    Zoom,
+    Detail,
+    Bookmark,
+    ToggleAnchor,
+    Yank,
+    Context,
+    ClearFilters,
+    ClearColumns,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InvocationsBinding {
+    Back,
+    Refresh,
+    Toggle,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TimeRangeBinding {
+    SelectPrevious,
+    SelectNext,
+    CycleZone,
+    Apply,
+    Cancel,
+    Help,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EventDetailBinding {
+    SelectPrevious,
+    SelectNext,
+    ToggleTab,
+    ToggleCollapse,
+    Filter,
+    Column,
+    Close,
+    Help,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EventContextBinding {
+    SelectPrevious,
+    SelectNext,
+    Before,
+    After,
+    Close,
+    Help,
+}
+
+/// Keys of a session whatever pane is shown.
+pub const SESSION_KEYS: &[KeyBinding<SessionBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up, Key::Char('k')],
+        SessionBinding::SelectPrevious,
+        "select the previous item",
+    ),
+    KeyBinding::new(
+        &[Key::Down, Key::Char('j')],
+        SessionBinding::SelectNext,
+        "select the next item",
+    ),
+    KeyBinding::new(
+        &[Key::Char('T')],
+        SessionBinding::TimeRange,
+        "pick the time range",
+    ),
+    KeyBinding::new(
+        &[Key::Char('Z')],
+        SessionBinding::CycleTimeDisplay,
+        "switch between local, UTC and relative times",
+    ),
+    KeyBinding::new(
+        &[Key::Char(':')],
+        SessionBinding::CommandLine,
+        "open the command line",
+    ),
+    KeyBinding::new(
+        &[Key::Char('Q')],
+        SessionBinding::InsightsLink,
+        "link a Logs Insights query of the view",
+    ),
+    KeyBinding::new(
+        &[Key::Char('U')],
+        SessionBinding::ConsoleLink,
+        "link the selected item in the console",
+    ),
+    KeyBinding::new(
+        &[Key::Char('u')],
+        SessionBinding::YankViewLink,
+        "copy a razy-cwlogs:// link to the view",
+    ),
+    KeyBinding::new(&[Key::Char('?')], SessionBinding::Help, "show this help"),
+];
+
+pub const STREAMS_KEYS: &[KeyBinding<StreamsBinding>] = &[
+    KeyBinding::new(&[Key::Esc], StreamsBinding::Back, "back to the groups"),
+    KeyBinding::new(
+        &[Key::Char('r')],
+        StreamsBinding::Refresh,
+        "list the streams again",
+    ),
+    KeyBinding::new(
+        &[Key::Char('D')],
+        StreamsBinding::Delete,
+        "delete the stream",
+    ),
+    KeyBinding::new(
+        &[Key::Enter],
+        StreamsBinding::OpenEvents,
+        "list events of the stream",
+    ),
+    KeyBinding::new(
+        &[Key::Char('E')],
+        StreamsBinding::OpenAllEvents,
+        "list events of every stream",
+    ),
+    KeyBinding::new(
+        &[Key::Char('y')],
+        StreamsBinding::YankName,
+        "copy the stream name",
+    ),
+    KeyBinding::new(
+        &[Key::Char('I')],
+        StreamsBinding::Invocations,
+        "list Lambda invocations",
+    ),
+];
+
+pub const EVENTS_KEYS: &[KeyBinding<EventsBinding>] = &[
+    KeyBinding::new(
+        &[Key::Esc],
+        EventsBinding::Back,
+        "back to the streams, or to the groups from merged events",
+    ),
+    KeyBinding::new(
+        &[
+            Key::Char('1'),
+            Key::Char('2'),
+            Key::Char('3'),
+            Key::Char('4'),
+            Key::Char('5'),
+            Key::Char('6'),
+            Key::Char('7'),
+            Key::Char('8'),
+            Key::Char('9'),
+        ],
+        EventsBinding::ToggleMute,
+        "hide or show events of the nth merged group",
+    ),
+    KeyBinding::new(
+        &[Key::Char('t')],
+        EventsBinding::ToggleDelta,
+        "show times from the previous event",
+    ),
+    KeyBinding::new(
+        &[Key::Char('L')],
+        EventsBinding::CycleLevel,
+        "hide events below the next level",
+    ),
+    KeyBinding::new(
+        &[Key::Char('r')],
+        EventsBinding::Refresh,
+        "list the events again",
+    ),
+    KeyBinding::new(
+        &[Key::Char('[')],
+        EventsBinding::PreviousBucket,
+        "select the previous bar of the histogram",
+    ),
+    KeyBinding::new(
+        &[Key::Char(']')],
+        EventsBinding::NextBucket,
+        "select the next bar of the histogram",
+    ),
+    KeyBinding::new(
+        &[Key::Char('H')],
+        EventsBinding::CycleSplit,
+        "split the histogram by level or filter",
+    ),
+    KeyBinding::new(
+        &[Key::Char('z')],
+        EventsBinding::Zoom,
+        "zoom into the selected bar",
+    ),
+    KeyBinding::new(
+        &[Key::Enter],
+        EventsBinding::Detail,
+        "show the event detail",
+    ),
+    KeyBinding::new(
+        &[Key::Char('m')],
+        EventsBinding::Bookmark,
+        "bookmark the event, or remove its bookmark",
+    ),
+    KeyBinding::new(
+        &[Key::Char('v')],
+        EventsBinding::ToggleAnchor,
+        "mark events from the selected one",
+    ),
+    KeyBinding::new(
+        &[Key::Char('y')],
+        EventsBinding::Yank,
+        "copy the event, or the marked events",
+    ),
+    KeyBinding::new(
+        &[Key::Char('x')],
+        EventsBinding::Context,
+        "show lines around the event in its stream",
+    ),
+    KeyBinding::new(
+        &[Key::Char('F')],
+        EventsBinding::ClearFilters,
+        "clear the filters",
+    ),
+    KeyBinding::new(
+        &[Key::Char('C')],
+        EventsBinding::ClearColumns,
+        "clear the field columns",
+    ),
+];
+
+pub const INVOCATIONS_KEYS: &[KeyBinding<InvocationsBinding>] = &[
+    KeyBinding::new(&[Key::Esc], InvocationsBinding::Back, "back to the streams"),
+    KeyBinding::new(
+        &[Key::Char('r')],
+        InvocationsBinding::Refresh,
+        "list the invocations again",
+    ),
+    KeyBinding::new(
+        &[Key::Enter, Key::Char(' ')],
+        InvocationsBinding::Toggle,
+        "expand or collapse the invocation",
+    ),
+];
+
+/// Keys of the time range picker, other characters type the range.
+pub const TIME_RANGE_KEYS: &[KeyBinding<TimeRangeBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up],
+        TimeRangeBinding::SelectPrevious,
+        "type the previous preset",
+    ),
+    KeyBinding::new(
+        &[Key::Down],
+        TimeRangeBinding::SelectNext,
+        "type the next preset",
+    ),
+    KeyBinding::new(
+        &[Key::Tab],
+        TimeRangeBinding::CycleZone,
+        "switch the time zone of times without offsets",
+    ),
+    KeyBinding::new(
+        &[Key::Enter],
+        TimeRangeBinding::Apply,
+        "list events in the typed range",
+    ),
+    KeyBinding::new(&[Key::Esc], TimeRangeBinding::Cancel, "cancel"),
+    KeyBinding::new(&[Key::Char('?')], TimeRangeBinding::Help, "show this help"),
+];
+
+pub const EVENT_DETAIL_KEYS: &[KeyBinding<EventDetailBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up, Key::Char('k')],
+        EventDetailBinding::SelectPrevious,
+        "select the previous field",
+    ),
+    KeyBinding::new(
+        &[Key::Down, Key::Char('j')],
+        EventDetailBinding::SelectNext,
+        "select the next field",
+    ),
+    KeyBinding::new(
+        &[Key::Tab],
+        EventDetailBinding::ToggleTab,
+        "switch between the message and its fields",
+    ),
+    KeyBinding::new(
+        &[Key::Enter, Key::Char(' ')],
+        EventDetailBinding::ToggleCollapse,
+        "collapse or expand the object or array on the line",
+    ),
+    KeyBinding::new(
+        &[Key::Char('f')],
+        EventDetailBinding::Filter,
+        "filter events by the value of the field",
+    ),
+    KeyBinding::new(
+        &[Key::Char('c')],
+        EventDetailBinding::Column,
+        "show the field as a column",
+    ),
+    KeyBinding::new(
+        &[Key::Esc, Key::Char('q')],
+        EventDetailBinding::Close,
+        "close the detail",
+    ),
+    KeyBinding::new(
+        &[Key::Char('?')],
+        EventDetailBinding::Help,
+        "show this help",
+    ),
+];
+
+pub const EVENT_CONTEXT_KEYS: &[KeyBinding<EventContextBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up, Key::Char('k')],
+        EventContextBinding::SelectPrevious,
+        "select the previous line",
+    ),
+    KeyBinding::new(
+        &[Key::Down, Key::Char('j')],
+        EventContextBinding::SelectNext,
+        "select the next line",
+    ),
+    KeyBinding::new(
+        &[Key::Char('b'), Key::Char('[')],
+        EventContextBinding::Before,
+        "load more lines before the event",
+    ),
+    KeyBinding::new(
+        &[Key::Char('f'), Key::Char(']')],
+        EventContextBinding::After,
+        "load more lines after the event",
+    ),
+    KeyBinding::new(
+        &[Key::Esc, Key::Char('q')],
+        EventContextBinding::Close,
+        "close the lines around the event",
+    ),
+    KeyBinding::new(
+        &[Key::Char('?')],
+        EventContextBinding::Help,
+        "show this help",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys_of<B>(keymap: &[KeyBinding<B>]) -> Vec<Key> {
+        keymap.iter().flat_map(|b| b.keys.iter().copied()).collect()
+    }
+
+    #[test]
+    fn test_keys_are_bound_once() {
+        let session = keys_of(SESSION_KEYS);
+        let panes = [
+            keys_of(STREAMS_KEYS),
+            keys_of(EVENTS_KEYS),
+            keys_of(INVOCATIONS_KEYS),
+        ];
+        for pane in panes.iter() {
+            let keys = session.iter().chain(pane.iter()).collect::<Vec<_>>();
+            for (i, key) in keys.iter().enumerate() {
+                assert!(!keys[i + 1..].contains(key), "{} is bound twice", key);
+            }
+        }
+
+        let popups = [
+            keys_of(TIME_RANGE_KEYS),
+            keys_of(EVENT_DETAIL_KEYS),
+            keys_of(EVENT_CONTEXT_KEYS),
+        ];
+        for keys in popups.iter() {
+            for (i, key) in keys.iter().enumerate() {
+                assert!(!keys[i + 1..].contains(key), "{} is bound twice", key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_time_range_keys_leave_the_range_typed() {
+        for key in keys_of(TIME_RANGE_KEYS) {
+            if let Key::Char(c) = key {
+                assert_eq!('?', c, "{} cannot be typed", key);
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::aws::cwlogs::event::CwlEvent;
+use crate::session::Session;
+use crate::ui::widget::delete::{DeleteConfirmState, DeleteTarget};
+use crate::ui::widget::event_context::EventContextState;
+use crate::ui::widget::event_detail::EventDetailState;
+use crate::ui::widget::events::EventsState;
+use crate::ui::widget::histogram::HistogramState;
+use crate::ui::widget::invocations::InvocationsState;
+use crate::ui::widget::stateful::table::StatefulTable;
+use crate::ui::widget::streams::StreamsState;
+use crate::ui::widget::time_range::TimeRangePickerState;
+
+/// Overlay shown on top of the session, receiving keys until it is closed.
+#[derive(Debug, Clone)]
+pub enum SessionPopup {
+    Delete(Box<DeleteConfirmState>),
+    TimeRange(Box<TimeRangePickerState>),
+    EventDetail(Box<EventDetailState>),
+    EventContext(Box<EventContextState>),
+}
+
+/// Table shown in the session.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SessionPane {
+    Streams,
+    Events,
+    Invocations,
+}
+
+impl Default for SessionPane {
+    fn default() -> Self {
+        SessionPane::Streams
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionState {
+    pub pane: SessionPane,
+    pub streams: StreamsState,
+    pub events: EventsState,
+    pub histogram: HistogramState,
+    pub invocations: InvocationsState,
+    pub popup: Option<SessionPopup>,
+    /// Openings of the context popup, so pages read for a popup opened before are dropped.
+    pub context_generation: u64,
+}
+
+impl SessionState {
+    pub fn select_next(&mut self, session: &Session) {
+        match self.pane {
+            SessionPane::Streams => {
+                StatefulTable::new(&mut self.streams, &session.streams).select_next();
+            }
+            SessionPane::Events => self.events.select_next(&session.events),
+            SessionPane::Invocations => self.invocations.select_next(&session.invocations()),
+        }
+    }
+
+    pub fn select_previous(&mut self, session: &Session) {
+        match self.pane {
+            SessionPane::Streams => {
+                StatefulTable::new(&mut self.streams, &session.streams).select_previous();
+            }
+            SessionPane::Events => self.events.select_previous(&session.events),
+            SessionPane::Invocations => self.invocations.select_previous(&session.invocations()),
+        }
+    }
+
+    /// Shows events from the top, to be listed from scratch.
+    pub fn show_events(&mut self) {
+        self.pane = SessionPane::Events;
+        self.events.reset();
+        self.histogram.reset();
+    }
+
+    /// Shows invocations of a Lambda function from the top, collapsed.
+    pub fn show_invocations(&mut self) {
+        self.pane = SessionPane::Invocations;
+        self.invocations.reset();
+    }
+
+    pub fn open_delete_confirm(&mut self, session: &Session) {
+        if let Some(stream) = self.streams.selected_stream(&session.streams) {
+            let target = DeleteTarget::Stream {
+                group_name: session.group.group_name.clone(),
+                stream: stream.clone(),
+            };
+            let confirm = DeleteConfirmState::new(target);
+            self.popup = Some(SessionPopup::Delete(Box::new(confirm)));
+        }
+    }
+
+    pub fn open_time_range_picker(&mut self, session: &Session) {
+        let picker = TimeRangePickerState::new(session.time_range, session.time_zone);
+        self.popup = Some(SessionPopup::TimeRange(Box::new(picker)));
+    }
+
+    pub fn open_event_detail(&mut self, session: &Session) {
+        if let Some(event) = self.events.selected_event(&session.events) {
+            let detail = EventDetailState::new(event.clone());
+            self.popup = Some(SessionPopup::EventDetail(Box::new(detail)));
+        }
+    }
+
+    /// Opens the lines around the selected event, to be read by GetLogEvents.
+    pub fn open_event_context(&mut self, session: &Session) -> bool {
+        let event = match self.events.selected_event(&session.events) {
+            Some(event) => event.clone(),
+            None => return false,
+        };
+        let group_name = session.group_name_of(&event).to_string();
+        self.open_context_of(group_name, event);
+        true
+    }
+
+    pub fn open_context_of(&mut self, group_name: String, event: CwlEvent) {
+        self.context_generation += 1;
+        let context = EventContextState::new(group_name, event, self.context_generation);
+        self.popup = Some(SessionPopup::EventContext(Box::new(context)));
+    }
+
+    /// The context popup, if it is still the one opened as the generation.
+    pub fn event_context_mut(&mut self, generation: u64) -> Option<&mut EventContextState> {
+        match self.popup.as_mut() {
+            Some(SessionPopup::EventContext(context)) if context.generation() == generation => {
+                Some(context)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn close_popup(&mut self) {
+        self.popup = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aws::cwlogs::event::{ContextDirection, CwlEventPage};
+
+    use super::*;
+
+    #[test]
+    fn test_event_context_generation() {
+        let mut state = SessionState::default();
+        state.open_context_of("group".to_string(), CwlEvent::for_test(1000));
+        let old = state.event_context_mut(1).map(|c| c.generation());
+        assert_eq!(Some(1), old);
+
+        // reopened on another event, a page read for the first popup is dropped.
+        state.open_context_of("group".to_string(), CwlEvent::for_test(2000));
+        assert!(state.event_context_mut(1).is_none());
+
+        let page = CwlEventPage {
+            events: vec![CwlEvent::for_test(2000)],
+            token: Some("f1".to_string()),
+        };
+        let context = state.event_context_mut(2).unwrap();
+        context.start_loading(ContextDirection::After);
+        assert!(context.is_loading_side(ContextDirection::After));
+        assert!(!context.is_loading_side(ContextDirection::Before));
+        context.extend(ContextDirection::After, page);
+        assert!(!context.is_loading());
+        assert_eq!(
+            2000,
+            context.context().target().event_time.timestamp_millis()
+        );
+        assert_eq!(1, context.context().events().len());
+    }
+}
@@ -0,0 +1,148 @@
+use chrono::Utc;
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::app::session::state::{SessionPane, SessionPopup, SessionState};
+use crate::session::Session;
+use crate::time::TimeDisplay;
+use crate::ui::theme::Theme;
+use crate::ui::widget::delete::DeleteConfirmWidget;
+use crate::ui::widget::event_context::EventContextWidget;
+use crate::ui::widget::event_detail::EventDetailWidget;
+use crate::ui::widget::events::{short_group_name, EventsWidget};
+use crate::ui::widget::histogram::HistogramWidget;
+use crate::ui::widget::invocations::InvocationsWidget;
+use crate::ui::widget::streams::StreamsWidget;
+use crate::ui::widget::time_range::TimeRangePickerWidget;
+use crate::ui::widget::CustomWidget;
+
+pub struct SessionWidgetSet {
+    pub streams: StreamsWidget,
+    pub events: EventsWidget,
+    pub histogram: HistogramWidget,
+    pub invocations: InvocationsWidget,
+    time_display: TimeDisplay,
+    pub delete_confirm: DeleteConfirmWidget,
+    pub time_range: TimeRangePickerWidget,
+    pub event_detail: EventDetailWidget,
+    pub event_context: EventContextWidget,
+}
+
+impl SessionWidgetSet {
+    pub fn new(theme: Theme, time_display: TimeDisplay) -> Self {
+        SessionWidgetSet {
+            streams: StreamsWidget::with_style(theme.active_widget).time_display(time_display),
+            events: EventsWidget::with_style(theme.active_widget)
+                .time_display(time_display)
+                .level_style(theme.levels)
+                .group_style(theme.groups),
+            histogram: HistogramWidget::with_style(theme.active_widget, theme.levels)
+                .time_display(time_display),
+            invocations: InvocationsWidget::with_style(theme.active_widget, theme.levels)
+                .time_display(time_display),
+            time_display,
+            delete_confirm: DeleteConfirmWidget::with_style(theme.active_widget),
+            time_range: TimeRangePickerWidget::with_style(theme.active_widget, theme.error),
+            event_detail: EventDetailWidget::with_style(theme.active_widget, theme.json)
+                .time_display(time_display),
+            event_context: EventContextWidget::with_style(theme.active_widget, theme.levels)
+                .time_display(time_display),
+        }
+    }
+}
+
+impl CustomWidget for SessionWidgetSet {
+    type Data = Session;
+    type State = SessionState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let range = data.time_range.resolve(&Utc::now());
+        let label = data.time_range.describe(&data.time_zone);
+        match state.pane {
+            SessionPane::Streams => self
+                .streams
+                .with_time_range(range, label)
+                .render_app_widget(area, buf, &data.streams, &mut state.streams),
+            SessionPane::Events => {
+                let groups = data
+                    .merged_groups
+                    .iter()
+                    .map(|g| g.group_name.clone())
+                    .collect::<Vec<_>>();
+                let target = if groups.is_empty() {
+                    data.events_stream
+                        .as_deref()
+                        .unwrap_or("every stream")
+                        .to_string()
+                } else {
+                    let legend = groups
+                        .iter()
+                        .enumerate()
+                        .map(|(i, name)| {
+                            let muted = if state.events.is_muted(name) {
+                                " (muted)"
+                            } else {
+                                ""
+                            };
+                            format!("<{}>: {}{}", i + 1, short_group_name(name), muted)
+                        })
+                        .collect::<Vec<_>>();
+                    format!("{} groups [{}]", groups.len(), legend.join(", "))
+                };
+                let mut title = format!(
+                    "Events of {} ({}, times in {})",
+                    target, label, self.time_display
+                );
+                if let Some(pattern) = data.filter_pattern() {
+                    title.push_str(&format!(" filter: {}", pattern));
+                }
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(8), Constraint::Min(0)])
+                    .split(area);
+                self.histogram
+                    .range(range)
+                    .min_level(state.events.min_level())
+                    .render_app_widget(chunks[0], buf, &data.events, &mut state.histogram);
+                self.events
+                    .title(title)
+                    .show_stream(data.events_stream.is_none())
+                    .columns(data.event_columns.clone())
+                    .groups(groups)
+                    .render_app_widget(chunks[1], buf, &data.events, &mut state.events);
+            }
+            SessionPane::Invocations => {
+                let title = format!("Invocations of {} ({})", data.group.group_name, label);
+                self.invocations
+                    .title(title)
+                    .loading(data.events.is_loading())
+                    .render_app_widget(area, buf, &data.invocations(), &mut state.invocations);
+            }
+        }
+
+        // popups
+        match state.popup.as_mut() {
+            Some(SessionPopup::Delete(confirm)) => {
+                self.delete_confirm
+                    .render_app_widget(area, buf, &(), confirm);
+            }
+            Some(SessionPopup::TimeRange(picker)) => {
+                self.time_range.render_app_widget(area, buf, &(), picker);
+            }
+            Some(SessionPopup::EventDetail(detail)) => {
+                self.event_detail.render_app_widget(area, buf, &(), detail);
+            }
+            Some(SessionPopup::EventContext(context)) => {
+                self.event_context
+                    .render_app_widget(area, buf, &(), context);
+            }
+            None => (),
+        }
+    }
+}
@@ -1,3 +1,4 @@
 pub mod handler;
+pub mod keymap;
 pub mod state;
 pub mod widget;
@@ -1,34 +1,391 @@
+use std::fs;
+use std::str::FromStr;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use chrono::{Local, Utc};
+use futures::future::abortable;
+use rusoto_core::Region;
+use tokio::time::delay_for;
 
 use crate::app::handler::{HandleAction, HandleKey};
-use crate::app::shell::state::ShellSelection;
+use crate::app::header::command::open_command_line;
+use crate::app::help::open_help;
+use crate::app::keymap::find_binding;
+use crate::app::session::handler::{fetch_event_context, fetch_log_events, fetch_log_streams};
+use crate::app::session::state::SessionState;
+use crate::app::shell::keymap::{
+    BookmarksBinding, DeleteBinding, PopupBinding, ShellBinding, TagInputBinding, BOOKMARKS_KEYS,
+    DELETE_KEYS, GROUPS_KEYS, POPUP_KEYS, SHELL_KEYS, TAG_INPUT_KEYS,
+};
+use crate::app::shell::state::{ShellPopup, ShellSelection};
 use crate::app::{App, AppFocus};
-use crate::aws::cwlogs::group::{CwlGroup, CwlGroupCursor};
+use crate::aws::cwlogs::console::{group_link, ConsoleLink};
+use crate::aws::cwlogs::event::ContextDirection;
+use crate::aws::cwlogs::group::{
+    run_bulk, CwlGroup, CwlGroupBulkOp, CwlGroupBulkResult, CwlGroupCacheState, CwlGroupCursor,
+    CwlGroupDetail, CwlGroupDetailLoader, CwlGroupOps, CwlGroupSource, CwlRetention,
+};
 use crate::aws::profile::ProfileName;
+use crate::aws::Arn;
+use crate::bookmark::Bookmark;
+use crate::clipboard;
 use crate::event::{Action, Key};
+use crate::launch::LaunchTarget;
 use crate::preset::{Preset, PresetName};
-use crate::query::{query_from, Query};
+use crate::query::group::GroupQuery;
+use crate::query::query_from;
+use crate::session::Session;
+use crate::time::TimeRangeSpec;
+use crate::ui::widget::delete::DeleteTarget;
+use crate::ui::widget::groups::{to_csv, BulkAction, BulkSummaryState, GroupDetailData};
+
+const PAGE_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Requests running at once in a bulk operation, to stay under the API rate limits.
+const BULK_CONCURRENCY: usize = 4;
+
+/// Events listed on either side of a bookmarked event when jumping back to it.
+const BOOKMARK_MINUTES: i64 = 5;
 
 pub struct ShellHandler;
 
 #[async_trait]
 impl HandleKey for ShellHandler {
     async fn handle_key(&self, app: &mut App, key: Key) -> anyhow::Result<()> {
+        if app.widget_states.shell.popup.is_some() {
+            on_popup_key(app, key).await;
+            return Ok(());
+        }
+
+        if key == app.config.quit_key {
+            return on_quit(app).await;
+        }
+        let binding = match app.widget_states.shell.selection {
+            Some(ShellSelection::Groups) => {
+                find_binding(SHELL_KEYS, key).or_else(|| find_binding(GROUPS_KEYS, key))
+            }
+            _ => find_binding(SHELL_KEYS, key),
+        };
+        let shell = &mut app.widget_states.shell;
+        match binding {
+            Some(ShellBinding::SelectPrevious) => shell.select_previous(&app.data),
+            Some(ShellBinding::SelectNext) => shell.select_next(&app.data),
+            Some(ShellBinding::Enter) => on_enter(app).await?,
+            Some(ShellBinding::ClearFocus) => shell.clear_focus(),
+            Some(ShellBinding::Search) => change_focus(app, AppFocus::Header),
+            Some(ShellBinding::CommandLine) => open_command_line(app),
+            Some(ShellBinding::CycleTimeDisplay) => cycle_time_display(app),
+            Some(ShellBinding::Bookmarks) => shell.open_bookmarks(),
+            Some(ShellBinding::Help) => open_help(app),
+            Some(ShellBinding::Refresh) => on_refresh(app).await?,
+            Some(ShellBinding::CycleSort) => shell.cycle_group_sort(&mut app.data),
+            Some(ShellBinding::ReverseSort) => shell.reverse_group_sort(&mut app.data),
+            Some(ShellBinding::Columns) => shell.open_column_chooser(),
+            Some(ShellBinding::Detail) => on_show_detail(app).await,
+            Some(ShellBinding::Retention) => shell.open_retention_editor(&app.data),
+            Some(ShellBinding::Delete) => shell.open_delete_confirm(&app.data),
+            Some(ShellBinding::Mark) => shell.toggle_mark(&app.data),
+            Some(ShellBinding::MarkAll) => shell.toggle_mark_all(&app.data),
+            Some(ShellBinding::Bulk) => shell.open_bulk_menu(&app.data),
+            Some(ShellBinding::ToggleView) => shell.toggle_groups_view(),
+            Some(ShellBinding::Collapse) => shell.collapse_group_node(&app.data),
+            Some(ShellBinding::Expand) => shell.expand_group_node(&app.data),
+            Some(ShellBinding::NodePreset) => on_apply_group_node_preset(app).await,
+            Some(ShellBinding::YankName) => on_yank_group(app, false),
+            Some(ShellBinding::YankArn) => on_yank_group(app, true),
+            Some(ShellBinding::ConsoleLink) => on_show_group_link(app),
+            Some(ShellBinding::Analytics) => shell.open_analytics(app.config.storage_cost),
+            None => (),
+        }
+
+        Ok(())
+    }
+}
+
+async fn on_popup_key(app: &mut App, key: Key) {
+    match app.widget_states.shell.popup {
+        Some(ShellPopup::Delete(_)) => return on_delete_key(app, key).await,
+        Some(ShellPopup::TagInput(_)) => return on_tag_input_key(app, key).await,
+        Some(ShellPopup::Bookmarks(_)) => return on_bookmarks_key(app, key).await,
+        _ => (),
+    }
+
+    let shell = &mut app.widget_states.shell;
+    match find_binding(POPUP_KEYS, key) {
+        Some(PopupBinding::SelectPrevious) => shell.select_previous_in_popup(),
+        Some(PopupBinding::SelectNext) => shell.select_next_in_popup(),
+        Some(PopupBinding::Choose) => match shell.popup {
+            Some(ShellPopup::BulkMenu(_)) => on_bulk_menu_enter(app),
+            _ => shell.execute_popup_item(),
+        },
+        Some(PopupBinding::Confirm) => {
+            if let Some(action) = shell.confirm_popup(&app.data) {
+                app.dispatch_action(action).await;
+            }
+        }
+        Some(PopupBinding::Deepen) => shell.deepen_popup(),
+        Some(PopupBinding::Shallow) => shell.shallow_popup(),
+        Some(PopupBinding::Cancel) => shell.cancel_popup(),
+        Some(PopupBinding::Close) => shell.close_popup(),
+        Some(PopupBinding::Help) => open_help(app),
+        None => (),
+    }
+}
+
+/// Keys are typed into the confirmation, except for the ones controlling the popup.
+async fn on_delete_key(app: &mut App, key: Key) {
+    let shell = &mut app.widget_states.shell;
+    let confirm = match shell.popup.as_mut() {
+        Some(ShellPopup::Delete(confirm)) => confirm,
+        _ => return,
+    };
+
+    match find_binding(DELETE_KEYS, key) {
+        Some(DeleteBinding::ToggleDryRun) => confirm.toggle_dry_run(),
+        Some(DeleteBinding::Cancel) => shell.close_popup(),
+        Some(DeleteBinding::Delete) if confirm.is_confirmed() => {
+            let (dry_run, message) = (confirm.is_dry_run(), confirm.dry_run_message());
+            let target = confirm.target().clone();
+            shell.close_popup();
+
+            if dry_run {
+                app.data.status.set_info(message);
+                return;
+            }
+            let source = match app.data.groups_source().cloned() {
+                Some(source) => source,
+                None => return,
+            };
+            let action = match target {
+                DeleteTarget::Group(group) => Action::DeleteLogGroup(source, Box::new(group)),
+                DeleteTarget::Groups(groups) => {
+                    Action::RunBulkOperation(source, groups, CwlGroupBulkOp::Delete)
+                }
+                DeleteTarget::Stream { .. } => return,
+            };
+            app.dispatch_action(action).await;
+        }
+        Some(DeleteBinding::Delete) => (),
+        None => match key {
+            Key::Char(c) => confirm.push(c),
+            Key::BackSpace => confirm.pop(),
+            _ => (),
+        },
+    }
+}
+
+async fn on_tag_input_key(app: &mut App, key: Key) {
+    let shell = &mut app.widget_states.shell;
+    let input = match shell.popup.as_mut() {
+        Some(ShellPopup::TagInput(input)) => input,
+        _ => return,
+    };
+
+    match find_binding(TAG_INPUT_KEYS, key) {
+        Some(TagInputBinding::Cancel) => shell.close_popup(),
+        Some(TagInputBinding::Apply) => {
+            let op = match input.parse() {
+                Some(op) => op,
+                None => return,
+            };
+            shell.close_popup();
+
+            let groups = shell
+                .marked_groups(&app.data)
+                .into_iter()
+                .cloned()
+                .collect();
+            if let Some(source) = app.data.groups_source().cloned() {
+                app.dispatch_action(Action::RunBulkOperation(source, groups, op))
+                    .await;
+            }
+        }
+        None => match key {
+            Key::Char(c) => input.push(c),
+            Key::BackSpace => input.pop(),
+            _ => (),
+        },
+    }
+}
+
+async fn on_bookmarks_key(app: &mut App, key: Key) {
+    let shell = &mut app.widget_states.shell;
+    let state = match shell.popup.as_mut() {
+        Some(ShellPopup::Bookmarks(state)) => state,
+        _ => return,
+    };
+    let bookmarks = &mut app.data.bookmarks;
+
+    if state.is_editing() {
         match key {
-            k if k == app.config.quit_key => on_quit(app).await?,
-            Key::Up | Key::Char('k') => app.widget_states.shell.select_previous(&app.data),
-            Key::Down | Key::Char('j') => app.widget_states.shell.select_next(&app.data),
-            Key::Enter => on_enter(app).await?,
-            Key::Esc => app.widget_states.shell.clear_focus(),
-            Key::Char('/') => change_focus(app, AppFocus::Header),
+            Key::Char(c) => state.push(c),
+            Key::BackSpace => state.pop(),
+            Key::Esc => {
+                state.take_note();
+            }
+            Key::Enter => {
+                if let (Some(i), Some(note)) = (state.selected(), state.take_note()) {
+                    bookmarks.set_note(i, &note);
+                    save_bookmarks(app);
+                }
+            }
             _ => (),
         }
+        return;
+    }
 
-        Ok(())
+    match find_binding(BOOKMARKS_KEYS, key) {
+        Some(BookmarksBinding::SelectPrevious) => state.select_previous(bookmarks),
+        Some(BookmarksBinding::SelectNext) => state.select_next(bookmarks),
+        Some(BookmarksBinding::Close) => shell.close_popup(),
+        Some(BookmarksBinding::Help) => open_help(app),
+        Some(BookmarksBinding::Note) => {
+            if let Some(bookmark) = state.selected().and_then(|i| bookmarks.get(i)) {
+                state.edit_note(bookmark);
+            }
+        }
+        Some(BookmarksBinding::Remove) => {
+            let removed = state.selected().and_then(|i| bookmarks.remove(i));
+            if removed.is_some() {
+                state.clamp(bookmarks);
+                save_bookmarks(app);
+            }
+        }
+        Some(BookmarksBinding::Open) => {
+            let bookmark = state.selected().and_then(|i| bookmarks.get(i)).cloned();
+            if let Some(bookmark) = bookmark {
+                shell.close_popup();
+                if let Err(e) = open_bookmark(app, bookmark).await {
+                    app.data.status.set_error(e.to_string());
+                }
+            }
+        }
+        None => (),
+    }
+}
+
+fn save_bookmarks(app: &mut App) {
+    if let Err(e) = app.data.bookmarks.save() {
+        app.data.status.set_error(e.to_string());
+    }
+}
+
+/// Opens the events of the stream around the bookmarked event, with the lines around it.
+/// Groups of the bookmark's profile are listed first when they are not shown.
+async fn open_bookmark(app: &mut App, bookmark: Bookmark) -> anyhow::Result<()> {
+    let source = app.data.groups_source().cloned().filter(|source| {
+        source.profile.to_string() == bookmark.profile && source.region.name() == bookmark.region
+    });
+    let group = source.as_ref().and_then(|_| {
+        app.data
+            .groups
+            .order_by_name_asc()
+            .find(|g| g.group_name == bookmark.group_name)
+            .cloned()
+    });
+    let range = bookmark_range(&bookmark);
+    let (source, group) = match (source, group) {
+        (Some(source), Some(group)) => (source, group),
+        _ => {
+            let target = LaunchTarget {
+                profile: Some(ProfileName::from(bookmark.profile.as_str())),
+                region: Region::from_str(&bookmark.region).ok(),
+                group_name: Some(bookmark.group_name.clone()),
+                stream_name: Some(bookmark.stream_name.clone()),
+                since: Some(range.expression()),
+                filter: None,
+                context: Some(Box::new(bookmark.event())),
+            };
+            launch(app, target).await;
+            return Ok(());
+        }
+    };
+
+    let id = match app.data.sessions.find_by_group(&group.arn) {
+        Some(id) => id,
+        None => {
+            let id = app.data.create_session(Session::new(source, group));
+            fetch_log_streams(app, id)?;
+            id
+        }
+    };
+    if let Some(session) = app.data.sessions.get_mut(id) {
+        session.time_range = range;
+        session.events_stream = Some(bookmark.stream_name.clone());
+    }
+    let state = app.widget_states.sessions.entry(id).or_default();
+    state.show_events();
+    state.open_context_of(bookmark.group_name.clone(), bookmark.event());
+    change_focus(app, AppFocus::Session(id));
+
+    fetch_log_events(app, id)?;
+    fetch_event_context(app, id, ContextDirection::Before)?;
+    fetch_event_context(app, id, ContextDirection::After)
+}
+
+/// Minutes around the bookmarked event.
+fn bookmark_range(bookmark: &Bookmark) -> TimeRangeSpec {
+    let time = bookmark.event_time();
+    TimeRangeSpec::Between(
+        time - chrono::Duration::minutes(BOOKMARK_MINUTES),
+        time + chrono::Duration::minutes(BOOKMARK_MINUTES),
+    )
+}
+
+fn on_bulk_menu_enter(app: &mut App) {
+    let shell = &mut app.widget_states.shell;
+    let action = match shell.popup.as_ref() {
+        Some(ShellPopup::BulkMenu(menu)) => menu.selected(),
+        _ => None,
+    };
+
+    match action {
+        Some(BulkAction::Export) => {
+            shell.close_popup();
+            export_marked_groups(app);
+        }
+        Some(BulkAction::MergeEvents) => {
+            shell.close_popup();
+            if let Err(e) = open_merged_session(app) {
+                app.data.status.set_error(e.to_string());
+            }
+        }
+        Some(action) => shell.open_bulk_action(action, &app.data),
+        None => (),
     }
 }
 
-async fn on_quit(app: &mut App) -> anyhow::Result<()> {
+/// Writes the marked groups, or every listed group without marks, to a CSV file in the current
+/// directory.
+pub fn export_marked_groups(app: &mut App) {
+    let shell = &app.widget_states.shell;
+    let mut groups = shell.marked_groups(&app.data);
+    if groups.is_empty() {
+        groups = app
+            .data
+            .groups
+            .matched()
+            .into_iter()
+            .map(|m| m.item)
+            .collect();
+    }
+    let csv = to_csv(&groups, shell.groups.columns());
+    let path = format!("log-groups-{}.csv", Local::now().format("%Y%m%d-%H%M%S"));
+
+    match fs::write(&path, csv) {
+        Ok(()) => {
+            let msg = format!("exported {} groups to {}", groups.len(), path);
+            app.data.status.set_info(msg);
+        }
+        Err(e) => {
+            let msg = format!("could not export groups to {}: {}", path, e);
+            app.data.status.set_error(msg);
+        }
+    }
+}
+
+pub async fn on_quit(app: &mut App) -> anyhow::Result<()> {
     app.shared_state().lock().await.stop_running();
     Ok(())
 }
@@ -46,6 +403,77 @@ async fn on_enter(app: &mut App) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn on_apply_group_node_preset(app: &mut App) {
+    if let Some(action) = app.widget_states.shell.apply_group_node_preset(&app.data) {
+        app.dispatch_action(action).await;
+    }
+}
+
+fn on_yank_group(app: &mut App, arn: bool) {
+    let group = match app.widget_states.shell.selected_group(&app.data) {
+        Some(group) => group,
+        None => return,
+    };
+    let (what, text) = if arn {
+        ("the ARN", group.arn.to_string())
+    } else {
+        ("the group name", group.group_name.clone())
+    };
+    yank(app, what, &text);
+}
+
+fn on_show_group_link(app: &mut App) {
+    let group = app.widget_states.shell.selected_group(&app.data);
+    let link = match (app.data.groups_source(), group) {
+        (Some(source), Some(group)) => group_link(&source.region, &group.group_name),
+        _ => return,
+    };
+    show_console_link(app, link);
+}
+
+async fn on_show_detail(app: &mut App) {
+    let shell = &app.widget_states.shell;
+    if shell.selection != Some(ShellSelection::Groups) {
+        return;
+    }
+
+    let group = shell.selected_group(&app.data).cloned();
+    let source = app.data.groups_source().cloned();
+    if let (Some(source), Some(group)) = (source, group) {
+        app.dispatch_action(Action::RequestLogGroupDetail(source, Box::new(group)))
+            .await;
+    }
+}
+
+/// Shows times in the next of local time, UTC, relative and the configured zone.
+pub fn cycle_time_display(app: &mut App) {
+    let display = app.data.time_display.next(app.config.time_display);
+    app.data.time_display = display;
+    app.data
+        .status
+        .set_info(format!("time display: {}", display));
+}
+
+/// Copies the text to the clipboard of the terminal, telling what was copied.
+pub fn yank(app: &mut App, what: &str, text: &str) {
+    match clipboard::copy(text) {
+        Ok(size) => app
+            .data
+            .status
+            .set_info(format!("copied {} ({} bytes)", what, size)),
+        Err(e) => app.data.status.set_error(e.to_string()),
+    }
+}
+
+/// Shows the link as a hyperlink in the status, copying its URL as well.
+pub fn show_console_link(app: &mut App, link: ConsoleLink) {
+    let text = match clipboard::copy(&link.url) {
+        Ok(_) => format!("{} (link copied)", link.label),
+        Err(e) => format!("{} ({})", link.label, e),
+    };
+    app.data.status.set_link(text, link.url);
+}
+
 fn change_focus(app: &mut App, focus: AppFocus) {
     app.focus = focus;
 }
@@ -54,36 +482,101 @@ fn change_focus(app: &mut App, focus: AppFocus) {
 impl HandleAction for ShellHandler {
     async fn handle_action(&self, app: &mut App, action: Action) -> anyhow::Result<()> {
         match action {
-            Action::Search(s) => on_query(app, query_from(s)).await,
+            Action::Search(s) => on_query(app, s).await,
             Action::RequestLogGroups(profile, preset) => {
                 on_request_log_groups(app, profile, preset).await
             }
-            Action::ReceiveLogGroups(groups) => on_receive_log_groups(app, groups).await,
+            Action::ReceiveLogGroups(source, generation, groups, state) => {
+                on_receive_log_groups(app, source, generation, groups, state).await
+            }
+            Action::FailLogGroups(source, generation, msg) => {
+                on_fail_log_groups(app, source, generation, msg).await
+            }
+            Action::RequestLogGroupDetail(source, group) => {
+                on_request_log_group_detail(app, source, group).await
+            }
+            Action::ReceiveLogGroupDetail(arn, detail) => {
+                on_receive_log_group_detail(app, arn, detail).await
+            }
+            Action::FailLogGroupDetail(arn, msg) => on_fail_log_group_detail(app, arn, msg).await,
+            Action::SetLogGroupRetention(source, group, retention) => {
+                on_set_log_group_retention(app, source, group, retention).await
+            }
+            Action::ReceiveLogGroupRetention(group) => {
+                on_receive_log_group_retention(app, group).await
+            }
+            Action::DeleteLogGroup(source, group) => on_delete_log_group(app, source, group).await,
+            Action::RemoveLogGroup(group) => on_remove_log_group(app, group).await,
+            Action::RunBulkOperation(source, groups, op) => {
+                on_run_bulk_operation(app, source, groups, op).await
+            }
+            Action::ReceiveBulkResult(result) => on_receive_bulk_result(app, *result).await,
+            Action::OpenSession(source, group) => on_open_session(app, source, group).await,
             Action::Error(msg) => on_error(app, msg).await,
+            // session actions are handled by the session handler.
+            Action::ReceiveLogStreams(..)
+            | Action::FailLogStreams(..)
+            | Action::DeleteLogStream(..)
+            | Action::RemoveLogStream(..)
+            | Action::ReceiveLogEvents(..)
+            | Action::FailLogEvents(..)
+            | Action::ReceiveEventContext(..)
+            | Action::FailEventContext(..) => Ok(()),
         }
     }
 }
 
-async fn on_query(app: &mut App, q: Option<Query>) -> anyhow::Result<()> {
+async fn on_query(app: &mut App, s: String) -> anyhow::Result<()> {
     if let Some(selection) = app.widget_states.shell.selection {
         match selection {
-            ShellSelection::Presets => app.data.presets.set_query(q),
-            ShellSelection::Profiles => app.data.profiles.set_query(q),
-            ShellSelection::Groups => {
-                let profile = app.widget_states.shell.selected_profile(&app.data).cloned();
-                if let Some(profile) = profile {
-                    let preset = Preset::new(
-                        PresetName::from("anonymous"),
-                        q.map(|q| q.word().to_string()),
-                    );
-                    app.dispatch_action(Action::RequestLogGroups(profile, Some(preset)))
-                        .await;
+            ShellSelection::Presets => app.data.presets.set_query(query_from(s)),
+            ShellSelection::Profiles => app.data.profiles.set_query(query_from(s)),
+            ShellSelection::Groups => on_group_query(app, s).await,
+        }
+    }
+
+    Ok(())
+}
+
+async fn on_group_query(app: &mut App, s: String) {
+    if s.trim().is_empty() {
+        app.data.groups.set_query(None);
+        // groups listed with the prefix of the query are replaced by the groups of the account.
+        let source = app
+            .data
+            .groups_source()
+            .filter(|s| s.prefix.is_some())
+            .cloned();
+        if let Some(source) = source {
+            let account = source.without_prefix();
+            app.data.show_groups(account.clone(), None);
+            if app.data.group_cache.state(&account).is_none() {
+                if let Err(e) = fetch_log_groups(app, account) {
+                    app.data.status.set_error(e.to_string());
                 }
             }
         }
+        return;
     }
 
-    Ok(())
+    let q = match GroupQuery::from_str(&s) {
+        Ok(q) => q,
+        Err(e) => {
+            app.data.search.set_error(e.to_string());
+            change_focus(app, AppFocus::Header);
+            return;
+        }
+    };
+
+    // the account has too many groups to search locally, narrow them down on the server side.
+    let account = app.data.groups_source().map(CwlGroupSource::without_prefix);
+    if let (Some(account), Some(prefix)) = (account, q.name_prefix()) {
+        if app.data.group_cache.state(&account) == Some(CwlGroupCacheState::Truncated) {
+            request_log_groups_with_prefix(app, account.profile, prefix).await;
+        }
+    }
+
+    app.data.groups.set_query(Some(q));
 }
 
 async fn on_request_log_groups(
@@ -91,45 +584,457 @@ async fn on_request_log_groups(
     profile_name: ProfileName,
     preset: Option<Preset>,
 ) -> anyhow::Result<()> {
-    app.data.debug_log(format!(
-        "create new cursor with profile:{:?}, preset:{:?}",
-        profile_name, preset
-    ));
-
     let preset = preset.unwrap_or_else(Preset::default);
-    let mut cursor = app.create_groups_cursor(profile_name, preset)?;
-    app.data.set_groups_cursor(cursor.clone());
+    let account = CwlGroupSource::new(profile_name.clone(), app.region(&profile_name));
+
+    // list every group of the account once and narrow them down locally, unless the account has
+    // too many groups to list.
+    let prefix = preset.group_name_prefix;
+    let source = match (app.data.group_cache.state(&account), prefix.as_ref()) {
+        (Some(CwlGroupCacheState::Truncated), Some(prefix)) => account.with_prefix(prefix.clone()),
+        _ => account,
+    };
+
+    app.data.show_groups(source.clone(), prefix);
+    if app.data.group_cache.state(&source).is_none() {
+        fetch_log_groups(app, source)?;
+    }
+
+    Ok(())
+}
+
+fn fetch_log_groups(app: &mut App, source: CwlGroupSource) -> anyhow::Result<()> {
+    app.data
+        .debug_log(format!("create new cursor with source:{:?}", source));
+
+    let mut cursor = app.create_groups_cursor(&source)?;
+    let generation = app.data.group_cache.start_loading(source.clone());
+    app.data.reload_groups();
+
+    let max_groups = app.config.max_cached_groups;
+    let mut sender = app.action_sender();
+    let task_source = source.clone();
+    let (task, abort) = abortable(async move {
+        let mut total = 0;
+        loop {
+            let (action, done) = match cursor.next().await {
+                Ok(Some(groups)) => {
+                    total += groups.len();
+                    let state = if total >= max_groups {
+                        CwlGroupCacheState::Truncated
+                    } else {
+                        CwlGroupCacheState::Loading
+                    };
+                    let action =
+                        Action::ReceiveLogGroups(source.clone(), generation, groups, state);
+                    (action, state != CwlGroupCacheState::Loading)
+                }
+                Ok(None) => {
+                    let state = CwlGroupCacheState::Complete;
+                    (
+                        Action::ReceiveLogGroups(source.clone(), generation, Vec::new(), state),
+                        true,
+                    )
+                }
+                Err(e) => (
+                    Action::FailLogGroups(source.clone(), generation, format!("{}", e)),
+                    true,
+                ),
+            };
+
+            sender
+                .send(action)
+                .await
+                .expect("could not send a log groups response");
+            if done {
+                break;
+            }
+
+            // stay under the request rate limit of DescribeLogGroups.
+            delay_for(PAGE_INTERVAL).await;
+        }
+    });
+    // a listing replaced by a refresh stops reading pages, which would only be dropped.
+    app.data.set_groups_task(task_source, abort);
+    tokio::spawn(task);
+
+    Ok(())
+}
+
+async fn on_receive_log_groups(
+    app: &mut App,
+    source: CwlGroupSource,
+    generation: u64,
+    groups: Vec<CwlGroup>,
+    state: CwlGroupCacheState,
+) -> anyhow::Result<()> {
+    app.data
+        .debug_log(format!("receive {} log groups", groups.len()));
+
+    // pages of a listing replaced by a refresh are dropped.
+    if !app.data.append_groups(&source, generation, groups, state) {
+        return Ok(());
+    }
+    if app.data.launch.is_some() && app.data.groups_source() == Some(&source) {
+        open_launch_target(app, &source, state)?;
+    }
+
+    // the account has too many groups, ask the server for groups with the prefix instead.
+    let is_shown = app.data.groups_source() == Some(&source);
+    if state == CwlGroupCacheState::Truncated && is_shown && source.prefix.is_none() {
+        if let Some(prefix) = app.data.groups_prefix().map(str::to_string) {
+            request_log_groups_with_prefix(app, source.profile, prefix).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Lists the groups of the target's profile, to open its group once it is listed.
+pub async fn launch(app: &mut App, target: LaunchTarget) {
+    let profile = target.profile_or_default();
+    app.data.region = target.region.clone();
+    app.widget_states.shell.selection = Some(ShellSelection::Groups);
+    let preset = target
+        .group_name
+        .clone()
+        .map(|name| Preset::new(PresetName::from(name.as_str()), Some(name)));
+    if target.group_name.is_some() {
+        app.data.launch = Some(target);
+    }
+    app.dispatch_action(Action::RequestLogGroups(profile, preset))
+        .await;
+}
+
+/// Opens the events of the launch target once its group is listed.
+fn open_launch_target(
+    app: &mut App,
+    source: &CwlGroupSource,
+    state: CwlGroupCacheState,
+) -> anyhow::Result<()> {
+    let group_name = app
+        .data
+        .launch
+        .as_ref()
+        .and_then(|target| target.group_name.clone())
+        .unwrap_or_default();
+    let group = app
+        .data
+        .groups
+        .order_by_name_asc()
+        .find(|g| g.group_name == group_name)
+        .cloned();
+    // groups with the name as a prefix are listed next when the account has too many groups.
+    let listed = match state {
+        CwlGroupCacheState::Loading => false,
+        CwlGroupCacheState::Truncated => source.prefix.is_some(),
+        CwlGroupCacheState::Complete => true,
+    };
+    let group = match group {
+        Some(group) => group,
+        None if listed => {
+            app.data.launch = None;
+            let msg = format!("log group {} is not found", group_name);
+            app.data.status.set_error(msg);
+            return Ok(());
+        }
+        None => return Ok(()),
+    };
+    let target = match app.data.launch.take() {
+        Some(target) => target,
+        None => return Ok(()),
+    };
+
+    let mut session = Session::new(source.clone(), group);
+    if let Some(since) = target.since.as_ref() {
+        match TimeRangeSpec::parse(since, &session.time_zone, &Utc::now()) {
+            Ok(range) => session.time_range = range,
+            Err(e) => app.data.status.set_error(e.to_string()),
+        }
+    }
+    session.events_stream = target.stream_name;
+    session.filter_text = target.filter;
+    let group_name = session.group.group_name.clone();
+    let id = app.data.create_session(session);
+    let mut state = SessionState::default();
+    state.show_events();
+    if let Some(event) = target.context.clone() {
+        state.open_context_of(group_name, *event);
+    }
+    app.widget_states.sessions.insert(id, state);
+    change_focus(app, AppFocus::Session(id));
+    fetch_log_streams(app, id)?;
+    fetch_log_events(app, id)?;
+    if target.context.is_some() {
+        fetch_event_context(app, id, ContextDirection::Before)?;
+        fetch_event_context(app, id, ContextDirection::After)?;
+    }
+    Ok(())
+}
+
+async fn on_fail_log_groups(
+    app: &mut App,
+    source: CwlGroupSource,
+    generation: u64,
+    msg: String,
+) -> anyhow::Result<()> {
+    if !app.data.group_cache.is_loading(&source, generation) {
+        return Ok(());
+    }
+    app.data.group_cache.invalidate(&source);
+    if app.data.groups_source() == Some(&source) {
+        app.data.reload_groups();
+        app.data.launch = None;
+    }
+
+    on_error(app, msg).await
+}
+
+async fn on_request_log_group_detail(
+    app: &mut App,
+    source: CwlGroupSource,
+    group: Box<CwlGroup>,
+) -> anyhow::Result<()> {
+    let loader = app.create_group_detail_loader(&source)?;
+    app.data.group_detail = Some(GroupDetailData::new(*group.clone()));
+    app.widget_states.shell.open_group_detail();
+
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let action = match loader.load(&group.group_name).await {
+            Ok(detail) => Action::ReceiveLogGroupDetail(group.arn, detail),
+            Err(e) => Action::FailLogGroupDetail(group.arn, format!("{}", e)),
+        };
+        sender
+            .send(action)
+            .await
+            .expect("could not send a log group detail response");
+    });
+
+    Ok(())
+}
+
+async fn on_receive_log_group_detail(
+    app: &mut App,
+    arn: Arn,
+    detail: CwlGroupDetail,
+) -> anyhow::Result<()> {
+    if let Some(data) = app.data.group_detail.as_mut() {
+        data.set_detail(&arn, detail);
+    }
+    Ok(())
+}
+
+async fn on_fail_log_group_detail(app: &mut App, arn: Arn, msg: String) -> anyhow::Result<()> {
+    if let Some(data) = app.data.group_detail.as_mut() {
+        data.set_error(&arn, msg.clone());
+    }
+    on_error(app, msg).await
+}
+
+async fn on_set_log_group_retention(
+    app: &mut App,
+    source: CwlGroupSource,
+    group: Box<CwlGroup>,
+    retention: CwlRetention,
+) -> anyhow::Result<()> {
+    let ops = app.create_group_ops(&source)?;
+    app.data.status.set_info(format!(
+        "changing retention of {} to {}...",
+        group.group_name, retention
+    ));
 
     let mut sender = app.action_sender();
     tokio::spawn(async move {
-        let r = cursor
-            .next()
+        let action = match ops.set_retention(&group.group_name, retention).await {
+            Ok(()) => {
+                let mut group = group;
+                group.retention = retention.as_duration();
+                Action::ReceiveLogGroupRetention(group)
+            }
+            Err(e) => Action::Error(format!(
+                "could not change retention of {}: {}",
+                group.group_name, e
+            )),
+        };
+        sender
+            .send(action)
             .await
-            .map(|groups| groups.unwrap_or_else(Vec::new));
+            .expect("could not send a retention response");
+    });
+
+    Ok(())
+}
+
+async fn on_receive_log_group_retention(app: &mut App, group: Box<CwlGroup>) -> anyhow::Result<()> {
+    app.data.status.set_info(format!(
+        "changed retention of {} to {}",
+        group.group_name,
+        CwlRetention::from(group.retention)
+    ));
+    app.data.group_cache.replace(&group);
+    app.data.groups.replace(*group);
+    Ok(())
+}
 
-        let action = match r {
-            Ok(groups) => Action::ReceiveLogGroups(groups),
-            Err(e) => Action::Error(format!("{}", e)),
+async fn on_delete_log_group(
+    app: &mut App,
+    source: CwlGroupSource,
+    group: Box<CwlGroup>,
+) -> anyhow::Result<()> {
+    let ops = app.create_group_ops(&source)?;
+    app.data
+        .status
+        .set_info(format!("deleting log group {}...", group.group_name));
+
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let action = match ops.delete_group(&group.group_name).await {
+            Ok(()) => Action::RemoveLogGroup(group),
+            Err(e) => Action::Error(format!(
+                "could not delete log group {}: {}",
+                group.group_name, e
+            )),
         };
         sender
             .send(action)
             .await
-            .expect("could not send a log groups response")
+            .expect("could not send a delete log group response");
     });
 
     Ok(())
 }
 
-async fn on_receive_log_groups(app: &mut App, groups: Vec<CwlGroup>) -> anyhow::Result<()> {
+async fn on_remove_log_group(app: &mut App, group: Box<CwlGroup>) -> anyhow::Result<()> {
     app.data
-        .debug_log(format!("receive {} log groups", groups.len()));
+        .status
+        .set_info(format!("deleted log group {}", group.group_name));
+    remove_group(app, &group);
+    Ok(())
+}
+
+fn remove_group(app: &mut App, group: &CwlGroup) {
+    app.data.group_cache.remove(group);
+    app.data.groups.remove(group);
+
+    // the session of the group has nothing to show anymore.
+    if let Some(id) = app.data.sessions.find_by_group(&group.arn) {
+        app.data.sessions.remove(id);
+        app.widget_states.sessions.remove(&id);
+        if app.focus == AppFocus::Session(id) {
+            change_focus(app, AppFocus::Shell);
+        }
+    }
+}
+
+async fn on_run_bulk_operation(
+    app: &mut App,
+    source: CwlGroupSource,
+    groups: Vec<CwlGroup>,
+    op: CwlGroupBulkOp,
+) -> anyhow::Result<()> {
+    let ops = app.create_group_ops(&source)?;
+    app.data
+        .status
+        .set_info(format!("{} on {} groups...", op, groups.len()));
+
+    let mut sender = app.action_sender();
+    tokio::spawn(async move {
+        let result = run_bulk(&ops, groups, op, BULK_CONCURRENCY).await;
+        sender
+            .send(Action::ReceiveBulkResult(Box::new(result)))
+            .await
+            .expect("could not send a bulk operation result");
+    });
 
-    app.data.groups.clear();
-    app.data.groups.extend(groups.into_iter());
     Ok(())
 }
 
+async fn on_receive_bulk_result(app: &mut App, result: CwlGroupBulkResult) -> anyhow::Result<()> {
+    for group in result.succeeded() {
+        match result.op {
+            CwlGroupBulkOp::Delete => remove_group(app, group),
+            CwlGroupBulkOp::SetRetention(_) => {
+                app.data.group_cache.replace(group);
+                app.data.groups.replace(group.clone());
+            }
+            CwlGroupBulkOp::AddTags(_) | CwlGroupBulkOp::RemoveTags(_) => (),
+        }
+    }
+
+    if result.failed().next().is_some() {
+        app.data.status.set_error(result.summary());
+    } else {
+        app.data.status.set_info(result.summary());
+    }
+    app.widget_states
+        .shell
+        .open_bulk_summary(BulkSummaryState::new(result));
+    Ok(())
+}
+
+async fn on_open_session(
+    app: &mut App,
+    source: CwlGroupSource,
+    group: Box<CwlGroup>,
+) -> anyhow::Result<()> {
+    if let Some(id) = app.data.sessions.find_by_group(&group.arn) {
+        change_focus(app, AppFocus::Session(id));
+        return Ok(());
+    }
+
+    let id = app.data.create_session(Session::new(source, *group));
+    app.widget_states
+        .sessions
+        .insert(id, SessionState::default());
+    change_focus(app, AppFocus::Session(id));
+    fetch_log_streams(app, id)
+}
+
+/// Opens the events of the marked groups merged in time order.
+fn open_merged_session(app: &mut App) -> anyhow::Result<()> {
+    let groups = app
+        .widget_states
+        .shell
+        .marked_groups(&app.data)
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    if groups.len() < 2 {
+        let msg = "mark two or more groups to merge their events";
+        app.data.status.set_error(msg.to_string());
+        return Ok(());
+    }
+    let source = match app.data.groups_source().cloned() {
+        Some(source) => source,
+        None => return Ok(()),
+    };
+
+    let id = app.data.create_session(Session::merged(source, groups));
+    let mut state = SessionState::default();
+    state.show_events();
+    app.widget_states.sessions.insert(id, state);
+    change_focus(app, AppFocus::Session(id));
+    fetch_log_events(app, id)
+}
+
+async fn on_refresh(app: &mut App) -> anyhow::Result<()> {
+    if let Some(source) = app.data.groups_source().cloned() {
+        app.data.group_cache.invalidate(&source);
+        fetch_log_groups(app, source)?;
+    }
+
+    Ok(())
+}
+
+async fn request_log_groups_with_prefix(app: &mut App, profile: ProfileName, prefix: String) {
+    let preset = Preset::new(PresetName::from("anonymous"), Some(prefix));
+    app.dispatch_action(Action::RequestLogGroups(profile, Some(preset)))
+        .await;
+}
+
 async fn on_error(app: &mut App, msg: String) -> anyhow::Result<()> {
+    app.data.status.set_error(msg.clone());
     app.data.debug_log(msg);
     Ok(())
 }
@@ -0,0 +1,338 @@
+use crate::app::keymap::KeyBinding;
+use crate::event::Key;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ShellBinding {
+    SelectPrevious,
+    SelectNext,
+    Enter,
+    ClearFocus,
+    Search,
+    CommandLine,
+    CycleTimeDisplay,
+    Bookmarks,
+    Help,
+    Refresh,
+    CycleSort,
+    ReverseSort,
+    Columns,
+    Detail,
+    Retention,
+    Delete,
+    Mark,
+    MarkAll,
+    Bulk,
+    ToggleView,
+    Collapse,
+    Expand,
+    NodePreset,
+    YankName,
+    YankArn,
+    ConsoleLink,
+    Analytics,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BookmarksBinding {
+    SelectPrevious,
+    SelectNext,
+    Open,
+    Note,
+    Remove,
+    Close,
+    Help,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DeleteBinding {
+    ToggleDryRun,
+    Delete,
+    Cancel,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TagInputBinding {
+    Apply,
+    Cancel,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PopupBinding {
+    SelectPrevious,
+    SelectNext,
+    Choose,
+    Confirm,
+    Deepen,
+    Shallow,
+    Cancel,
+    Close,
+    Help,
+}
+
+/// Keys of the shell whatever is listed, besides the configured quit key.
+pub const SHELL_KEYS: &[KeyBinding<ShellBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up, Key::Char('k')],
+        ShellBinding::SelectPrevious,
+        "select the previous item",
+    ),
+    KeyBinding::new(
+        &[Key::Down, Key::Char('j')],
+        ShellBinding::SelectNext,
+        "select the next item",
+    ),
+    KeyBinding::new(
+        &[Key::Enter],
+        ShellBinding::Enter,
+        "focus the list, or open the selected item",
+    ),
+    KeyBinding::new(&[Key::Esc], ShellBinding::ClearFocus, "leave the list"),
+    KeyBinding::new(&[Key::Char('/')], ShellBinding::Search, "search the list"),
+    KeyBinding::new(
+        &[Key::Char(':')],
+        ShellBinding::CommandLine,
+        "open the command line",
+    ),
+    KeyBinding::new(
+        &[Key::Char('Z')],
+        ShellBinding::CycleTimeDisplay,
+        "switch between local, UTC and relative times",
+    ),
+    KeyBinding::new(
+        &[Key::Char('B')],
+        ShellBinding::Bookmarks,
+        "show the bookmarks",
+    ),
+    KeyBinding::new(&[Key::Char('?')], ShellBinding::Help, "show this help"),
+];
+
+/// Keys of the listed log groups.
+pub const GROUPS_KEYS: &[KeyBinding<ShellBinding>] = &[
+    KeyBinding::new(
+        &[Key::Char('r')],
+        ShellBinding::Refresh,
+        "list the groups again",
+    ),
+    KeyBinding::new(
+        &[Key::Char('s')],
+        ShellBinding::CycleSort,
+        "sort by the next column",
+    ),
+    KeyBinding::new(
+        &[Key::Char('S')],
+        ShellBinding::ReverseSort,
+        "reverse the sort",
+    ),
+    KeyBinding::new(
+        &[Key::Char('c')],
+        ShellBinding::Columns,
+        "choose the columns",
+    ),
+    KeyBinding::new(
+        &[Key::Char('d')],
+        ShellBinding::Detail,
+        "show the group detail",
+    ),
+    KeyBinding::new(
+        &[Key::Char('e')],
+        ShellBinding::Retention,
+        "edit the retention of the group",
+    ),
+    KeyBinding::new(&[Key::Char('D')], ShellBinding::Delete, "delete the group"),
+    KeyBinding::new(&[Key::Char(' ')], ShellBinding::Mark, "mark the group"),
+    KeyBinding::new(
+        &[Key::Char('a')],
+        ShellBinding::MarkAll,
+        "mark every listed group, or none",
+    ),
+    KeyBinding::new(
+        &[Key::Char('b')],
+        ShellBinding::Bulk,
+        "run a bulk action on the marked groups",
+    ),
+    KeyBinding::new(
+        &[Key::Char('t')],
+        ShellBinding::ToggleView,
+        "switch between the table and the tree",
+    ),
+    KeyBinding::new(
+        &[Key::Left, Key::Char('h')],
+        ShellBinding::Collapse,
+        "collapse the node of the tree",
+    ),
+    KeyBinding::new(
+        &[Key::Right, Key::Char('l')],
+        ShellBinding::Expand,
+        "expand the node of the tree",
+    ),
+    KeyBinding::new(
+        &[Key::Char('p')],
+        ShellBinding::NodePreset,
+        "list only the groups under the node",
+    ),
+    KeyBinding::new(
+        &[Key::Char('y')],
+        ShellBinding::YankName,
+        "copy the group name",
+    ),
+    KeyBinding::new(
+        &[Key::Char('Y')],
+        ShellBinding::YankArn,
+        "copy the group ARN",
+    ),
+    KeyBinding::new(
+        &[Key::Char('U')],
+        ShellBinding::ConsoleLink,
+        "link the group in the console",
+    ),
+    KeyBinding::new(
+        &[Key::Char('A')],
+        ShellBinding::Analytics,
+        "show the storage and cost of the groups",
+    ),
+];
+
+/// Keys of the bookmarks, unless a note is typed.
+pub const BOOKMARKS_KEYS: &[KeyBinding<BookmarksBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up, Key::Char('k')],
+        BookmarksBinding::SelectPrevious,
+        "select the previous bookmark",
+    ),
+    KeyBinding::new(
+        &[Key::Down, Key::Char('j')],
+        BookmarksBinding::SelectNext,
+        "select the next bookmark",
+    ),
+    KeyBinding::new(
+        &[Key::Enter],
+        BookmarksBinding::Open,
+        "open the bookmarked event",
+    ),
+    KeyBinding::new(
+        &[Key::Char('n')],
+        BookmarksBinding::Note,
+        "write a note on the bookmark",
+    ),
+    KeyBinding::new(
+        &[Key::Char('d')],
+        BookmarksBinding::Remove,
+        "remove the bookmark",
+    ),
+    KeyBinding::new(
+        &[Key::Esc, Key::Char('q')],
+        BookmarksBinding::Close,
+        "close the bookmarks",
+    ),
+    KeyBinding::new(&[Key::Char('?')], BookmarksBinding::Help, "show this help"),
+];
+
+/// Keys of the delete confirmation of groups and streams, other characters type the name.
+pub const DELETE_KEYS: &[KeyBinding<DeleteBinding>] = &[
+    KeyBinding::new(
+        &[Key::Tab],
+        DeleteBinding::ToggleDryRun,
+        "switch between a dry run and deleting",
+    ),
+    KeyBinding::new(
+        &[Key::Enter],
+        DeleteBinding::Delete,
+        "delete once the name is typed",
+    ),
+    KeyBinding::new(&[Key::Esc], DeleteBinding::Cancel, "cancel"),
+];
+
+/// Keys of the tag input of the marked groups, other characters type the tags.
+pub const TAG_INPUT_KEYS: &[KeyBinding<TagInputBinding>] = &[
+    KeyBinding::new(
+        &[Key::Enter],
+        TagInputBinding::Apply,
+        "change the tags of the marked groups",
+    ),
+    KeyBinding::new(&[Key::Esc], TagInputBinding::Cancel, "cancel"),
+];
+
+/// Keys of the other popups over the groups, like the column chooser or the analytics.
+pub const POPUP_KEYS: &[KeyBinding<PopupBinding>] = &[
+    KeyBinding::new(
+        &[Key::Up, Key::Char('k')],
+        PopupBinding::SelectPrevious,
+        "select the previous item",
+    ),
+    KeyBinding::new(
+        &[Key::Down, Key::Char('j')],
+        PopupBinding::SelectNext,
+        "select the next item",
+    ),
+    KeyBinding::new(
+        &[Key::Enter, Key::Char(' ')],
+        PopupBinding::Choose,
+        "choose the item, like a column to show or a retention",
+    ),
+    KeyBinding::new(
+        &[Key::Char('y')],
+        PopupBinding::Confirm,
+        "apply the chosen retention",
+    ),
+    KeyBinding::new(
+        &[Key::Char('+')],
+        PopupBinding::Deepen,
+        "total the analytics by longer name prefixes",
+    ),
+    KeyBinding::new(
+        &[Key::Char('-')],
+        PopupBinding::Shallow,
+        "total the analytics by shorter name prefixes",
+    ),
+    KeyBinding::new(
+        &[Key::Esc, Key::Char('n')],
+        PopupBinding::Cancel,
+        "back out of the confirmation, or close the popup",
+    ),
+    KeyBinding::new(
+        &[Key::Char('c'), Key::Char('d')],
+        PopupBinding::Close,
+        "close the popup",
+    ),
+    KeyBinding::new(&[Key::Char('?')], PopupBinding::Help, "show this help"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys_of<B>(keymap: &[KeyBinding<B>]) -> Vec<Key> {
+        keymap.iter().flat_map(|b| b.keys.iter().copied()).collect()
+    }
+
+    #[test]
+    fn test_keys_are_bound_once() {
+        let shell = keys_of(SHELL_KEYS);
+        let views = [
+            shell
+                .iter()
+                .chain(keys_of(GROUPS_KEYS).iter())
+                .copied()
+                .collect(),
+            keys_of(BOOKMARKS_KEYS),
+            keys_of(DELETE_KEYS),
+            keys_of(TAG_INPUT_KEYS),
+            keys_of(POPUP_KEYS),
+        ];
+        for keys in views.iter() {
+            for (i, key) in keys.iter().enumerate() {
+                assert!(!keys[i + 1..].contains(key), "{} is bound twice", key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_text_inputs_leave_characters_typed() {
+        for key in keys_of(DELETE_KEYS)
+            .into_iter()
+            .chain(keys_of(TAG_INPUT_KEYS))
+        {
+            assert!(!matches!(key, Key::Char(_)), "{} cannot be typed", key);
+        }
+    }
+}
@@ -3,10 +3,17 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 
 use crate::app::data::AppData;
+use crate::aws::cwlogs::group::CwlGroup;
+use crate::aws::cwlogs::group::{CwlGroupBulkOp, StorageCostConfig};
 use crate::aws::profile::ProfileName;
 use crate::event::Action;
-use crate::preset::Preset;
-use crate::ui::widget::groups::GroupsStates;
+use crate::preset::{Preset, PresetName};
+use crate::ui::widget::bookmarks::BookmarksState;
+use crate::ui::widget::delete::{DeleteConfirmState, DeleteTarget};
+use crate::ui::widget::groups::{
+    AnalyticsState, BulkAction, BulkMenuState, BulkSummaryState, ColumnChooserState,
+    GroupDetailState, GroupsStates, GroupsView, RetentionEditorState, TagInputState,
+};
 use crate::ui::widget::presets::PresetsState;
 use crate::ui::widget::profiles::ProfilesState;
 use crate::ui::widget::stateful::list::StatefulList;
@@ -54,6 +61,20 @@ impl Default for ShellSelection {
     }
 }
 
+/// Overlay shown on top of the shell, receiving keys until it is closed.
+#[derive(Debug, Clone)]
+pub enum ShellPopup {
+    Columns(ColumnChooserState),
+    Detail(GroupDetailState),
+    Retention(Box<RetentionEditorState>),
+    Delete(Box<DeleteConfirmState>),
+    BulkMenu(BulkMenuState),
+    TagInput(TagInputState),
+    BulkSummary(Box<BulkSummaryState>),
+    Analytics(AnalyticsState),
+    Bookmarks(BookmarksState),
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ShellState {
     pub selection: Option<ShellSelection>,
@@ -62,6 +83,7 @@ pub struct ShellState {
     pub profiles: ProfilesState,
     pub groups: GroupsStates,
     pub query: String,
+    pub popup: Option<ShellPopup>,
 }
 
 impl ShellState {
@@ -112,6 +134,243 @@ impl ShellState {
         self.presets.selected_preset(&data.presets)
     }
 
+    pub fn selected_group<'a>(&self, data: &'a AppData) -> Option<&'a CwlGroup> {
+        self.groups.selected_group(&data.groups)
+    }
+
+    pub fn cycle_group_sort(&mut self, data: &mut AppData) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.groups.cycle_sort_column(&mut data.groups);
+        }
+    }
+
+    pub fn reverse_group_sort(&mut self, data: &mut AppData) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.groups.reverse_sort_order(&mut data.groups);
+        }
+    }
+
+    pub fn toggle_groups_view(&mut self) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.groups.toggle_view();
+        }
+    }
+
+    fn is_groups_tree(&self) -> bool {
+        self.selection == Some(ShellSelection::Groups) && self.groups.view() == GroupsView::Tree
+    }
+
+    pub fn expand_group_node(&mut self, data: &AppData) {
+        if self.is_groups_tree() {
+            self.groups.tree_mut().expand(&data.groups);
+        }
+    }
+
+    pub fn collapse_group_node(&mut self, data: &AppData) {
+        if self.is_groups_tree() {
+            self.groups.tree_mut().collapse(&data.groups);
+        }
+    }
+
+    /// Lists only the groups under the selected node, as a preset named after the prefix.
+    pub fn apply_group_node_preset(&mut self, data: &AppData) -> Option<Action> {
+        if !self.is_groups_tree() {
+            return None;
+        }
+        let profile = data.groups_source()?.profile.clone();
+        let prefix = self.groups.tree().selected_path(&data.groups)?;
+        let preset = Preset::new(PresetName::from(prefix.as_str()), Some(prefix));
+        Some(Action::RequestLogGroups(profile, Some(preset)))
+    }
+
+    pub fn open_column_chooser(&mut self) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.popup = Some(ShellPopup::Columns(ColumnChooserState::default()));
+        }
+    }
+
+    pub fn open_group_detail(&mut self) {
+        self.popup = Some(ShellPopup::Detail(GroupDetailState::default()));
+    }
+
+    pub fn open_retention_editor(&mut self, data: &AppData) {
+        if self.selection != Some(ShellSelection::Groups) {
+            return;
+        }
+        if let Some(group) = self.selected_group(data) {
+            let editor = RetentionEditorState::new(group.clone());
+            self.popup = Some(ShellPopup::Retention(Box::new(editor)));
+        }
+    }
+
+    pub fn open_delete_confirm(&mut self, data: &AppData) {
+        if self.selection != Some(ShellSelection::Groups) {
+            return;
+        }
+        if let Some(group) = self.selected_group(data) {
+            let confirm = DeleteConfirmState::new(DeleteTarget::Group(group.clone()));
+            self.popup = Some(ShellPopup::Delete(Box::new(confirm)));
+        }
+    }
+
+    pub fn toggle_mark(&mut self, data: &AppData) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.groups.toggle_mark(&data.groups);
+        }
+    }
+
+    pub fn toggle_mark_all(&mut self, data: &AppData) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.groups.toggle_mark_all(&data.groups);
+        }
+    }
+
+    pub fn marked_groups<'a>(&self, data: &'a AppData) -> Vec<&'a CwlGroup> {
+        self.groups.marked_groups(&data.groups)
+    }
+
+    pub fn open_bulk_menu(&mut self, data: &AppData) {
+        if !self.marked_groups(data).is_empty() {
+            self.popup = Some(ShellPopup::BulkMenu(BulkMenuState::default()));
+        }
+    }
+
+    /// Opens the popup to fill in the bulk action for the marked groups.
+    pub fn open_bulk_action(&mut self, action: BulkAction, data: &AppData) {
+        let groups = self
+            .marked_groups(data)
+            .into_iter()
+            .cloned()
+            .collect::<Vec<_>>();
+        self.popup = match action {
+            BulkAction::SetRetention => Some(ShellPopup::Retention(Box::new(
+                RetentionEditorState::for_groups(groups),
+            ))),
+            BulkAction::AddTags => Some(ShellPopup::TagInput(TagInputState::add())),
+            BulkAction::RemoveTags => Some(ShellPopup::TagInput(TagInputState::remove())),
+            BulkAction::Delete => Some(ShellPopup::Delete(Box::new(DeleteConfirmState::new(
+                DeleteTarget::Groups(groups),
+            )))),
+            BulkAction::Export | BulkAction::MergeEvents => None,
+        };
+    }
+
+    pub fn open_bulk_summary(&mut self, summary: BulkSummaryState) {
+        self.popup = Some(ShellPopup::BulkSummary(Box::new(summary)));
+    }
+
+    pub fn open_analytics(&mut self, config: StorageCostConfig) {
+        if self.selection == Some(ShellSelection::Groups) {
+            self.popup = Some(ShellPopup::Analytics(AnalyticsState::new(config)));
+        }
+    }
+
+    pub fn open_bookmarks(&mut self) {
+        self.popup = Some(ShellPopup::Bookmarks(BookmarksState::default()));
+    }
+
+    /// Shows more detail in the popup, e.g. longer prefixes in the analytics.
+    pub fn deepen_popup(&mut self) {
+        if let Some(ShellPopup::Analytics(analytics)) = self.popup.as_mut() {
+            analytics.deepen();
+        }
+    }
+
+    pub fn shallow_popup(&mut self) {
+        if let Some(ShellPopup::Analytics(analytics)) = self.popup.as_mut() {
+            analytics.shallow();
+        }
+    }
+
+    pub fn close_popup(&mut self) {
+        self.popup = None;
+    }
+
+    /// Backs out of the confirmation of the popup, or closes the popup.
+    pub fn cancel_popup(&mut self) {
+        match self.popup.as_mut() {
+            Some(ShellPopup::Retention(editor)) if editor.confirming().is_some() => {
+                editor.cancel();
+            }
+            _ => self.close_popup(),
+        }
+    }
+
+    /// Applies the change waiting for confirmation in the popup.
+    pub fn confirm_popup(&mut self, data: &AppData) -> Option<Action> {
+        let action = match self.popup.as_ref() {
+            Some(ShellPopup::Retention(editor)) => {
+                let source = data.groups_source().cloned();
+                match (source, editor.confirming(), editor.groups()) {
+                    (Some(source), Some(retention), [group]) => Some(Action::SetLogGroupRetention(
+                        source,
+                        Box::new(group.clone()),
+                        retention,
+                    )),
+                    (Some(source), Some(retention), groups) => Some(Action::RunBulkOperation(
+                        source,
+                        groups.to_vec(),
+                        CwlGroupBulkOp::SetRetention(retention),
+                    )),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        if action.is_some() {
+            self.close_popup();
+        }
+        action
+    }
+
+    pub fn select_next_in_popup(&mut self) {
+        match self.popup.as_mut() {
+            Some(ShellPopup::Columns(chooser)) => {
+                StatefulList::new(chooser, self.groups.columns()).select_next();
+            }
+            Some(ShellPopup::Detail(detail)) => detail.scroll_down(),
+            Some(ShellPopup::Retention(editor)) => editor.select_next(),
+            Some(ShellPopup::BulkMenu(menu)) => menu.select_next(),
+            Some(ShellPopup::BulkSummary(summary)) => summary.scroll_down(),
+            Some(ShellPopup::Delete(_))
+            | Some(ShellPopup::TagInput(_))
+            | Some(ShellPopup::Analytics(_))
+            | Some(ShellPopup::Bookmarks(_))
+            | None => (),
+        }
+    }
+
+    pub fn select_previous_in_popup(&mut self) {
+        match self.popup.as_mut() {
+            Some(ShellPopup::Columns(chooser)) => {
+                StatefulList::new(chooser, self.groups.columns()).select_previous();
+            }
+            Some(ShellPopup::Detail(detail)) => detail.scroll_up(),
+            Some(ShellPopup::Retention(editor)) => editor.select_previous(),
+            Some(ShellPopup::BulkMenu(menu)) => menu.select_previous(),
+            Some(ShellPopup::BulkSummary(summary)) => summary.scroll_up(),
+            Some(ShellPopup::Delete(_))
+            | Some(ShellPopup::TagInput(_))
+            | Some(ShellPopup::Analytics(_))
+            | Some(ShellPopup::Bookmarks(_))
+            | None => (),
+        }
+    }
+
+    pub fn execute_popup_item(&mut self) {
+        match self.popup.as_mut() {
+            Some(ShellPopup::Columns(chooser)) => {
+                if let Some(i) = chooser.selected() {
+                    self.groups.columns_mut().toggle(i);
+                }
+            }
+            Some(ShellPopup::Detail(_)) => (),
+            Some(ShellPopup::Retention(editor)) => editor.choose(),
+            _ => (),
+        }
+    }
+
     fn widget_order(&self) -> WidgetOrder {
         self.selection
             .and_then(|s| SHELL_WIDGET_ORDER.get(&s))
@@ -137,6 +396,9 @@ impl ShellState {
             Some(ShellSelection::Profiles) => {
                 StatefulList::new(&mut self.profiles, &data.profiles).select_next();
             }
+            Some(ShellSelection::Groups) if self.groups.view() == GroupsView::Tree => {
+                self.groups.tree_mut().select_next(&data.groups);
+            }
             Some(ShellSelection::Groups) => {
                 StatefulTable::new(&mut self.groups, &data.groups).select_next();
             }
@@ -152,6 +414,9 @@ impl ShellState {
             Some(ShellSelection::Profiles) => {
                 StatefulList::new(&mut self.profiles, &data.profiles).select_previous();
             }
+            Some(ShellSelection::Groups) if self.groups.view() == GroupsView::Tree => {
+                self.groups.tree_mut().select_previous(&data.groups);
+            }
             Some(ShellSelection::Groups) => {
                 StatefulTable::new(&mut self.groups, &data.groups).select_previous();
             }
@@ -173,6 +438,14 @@ impl ShellState {
     }
 
     fn load_log_streams_action(&mut self, data: &AppData) -> Option<Action> {
-        None
+        // a node of the tree without a group of the same name only opens.
+        if self.is_groups_tree() && self.selected_group(data).is_none() {
+            self.groups.tree_mut().toggle(&data.groups);
+            return None;
+        }
+
+        let source = data.groups_source()?.clone();
+        let group = self.selected_group(data)?.clone();
+        Some(Action::OpenSession(source, Box::new(group)))
     }
 }
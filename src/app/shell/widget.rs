@@ -2,9 +2,15 @@ use tui::buffer::Buffer;
 use tui::layout::{Constraint, Direction, Layout, Rect};
 
 use crate::app::data::AppData;
-use crate::app::shell::state::{ShellSelection, ShellState};
+use crate::app::shell::state::{ShellPopup, ShellSelection, ShellState};
+use crate::time::TimeDisplay;
 use crate::ui::theme::Theme;
-use crate::ui::widget::groups::GroupsWidget;
+use crate::ui::widget::bookmarks::BookmarksWidget;
+use crate::ui::widget::delete::DeleteConfirmWidget;
+use crate::ui::widget::groups::{
+    AnalyticsWidget, BulkMenuWidget, BulkSummaryWidget, ColumnChooserWidget, GroupDetailWidget,
+    GroupsWidget, RetentionEditorWidget, TagInputWidget,
+};
 use crate::ui::widget::presets::PresetsWidget;
 use crate::ui::widget::profiles::ProfilesWidget;
 use crate::ui::widget::CustomWidget;
@@ -13,10 +19,19 @@ pub struct ShellWidgetSet {
     pub presets: PresetsWidget,
     pub profiles: ProfilesWidget,
     pub groups: GroupsWidget,
+    pub column_chooser: ColumnChooserWidget,
+    pub group_detail: GroupDetailWidget,
+    pub retention_editor: RetentionEditorWidget,
+    pub delete_confirm: DeleteConfirmWidget,
+    pub bulk_menu: BulkMenuWidget,
+    pub tag_input: TagInputWidget,
+    pub bulk_summary: BulkSummaryWidget,
+    pub analytics: AnalyticsWidget,
+    pub bookmarks: BookmarksWidget,
 }
 
 impl ShellWidgetSet {
-    pub fn new(theme: Theme, state: &ShellState) -> Self {
+    pub fn new(theme: Theme, state: &ShellState, time_display: TimeDisplay) -> Self {
         let selected = if state.has_focus() {
             theme.active_widget
         } else {
@@ -34,7 +49,17 @@ impl ShellWidgetSet {
         ShellWidgetSet {
             presets: PresetsWidget::with_style(presets),
             profiles: ProfilesWidget::with_style(profiles),
-            groups: GroupsWidget::with_style(groups),
+            groups: GroupsWidget::with_style(groups).time_display(time_display),
+            column_chooser: ColumnChooserWidget::with_style(theme.active_widget),
+            group_detail: GroupDetailWidget::with_style(theme.active_widget, theme.error)
+                .time_display(time_display),
+            retention_editor: RetentionEditorWidget::with_style(theme.active_widget),
+            delete_confirm: DeleteConfirmWidget::with_style(theme.active_widget),
+            bulk_menu: BulkMenuWidget::with_style(theme.active_widget),
+            tag_input: TagInputWidget::with_style(theme.active_widget, theme.error),
+            bulk_summary: BulkSummaryWidget::with_style(theme.active_widget, theme.error),
+            analytics: AnalyticsWidget::with_style(theme.active_widget, theme.error),
+            bookmarks: BookmarksWidget::with_style(theme.active_widget).time_display(time_display),
         }
     }
 }
@@ -71,5 +96,45 @@ impl CustomWidget for ShellWidgetSet {
         // right widgets
         self.groups
             .render_app_widget(right, buf, &data.groups, &mut state.groups);
+
+        // popups
+        match state.popup.as_mut() {
+            Some(ShellPopup::Columns(chooser)) => {
+                self.column_chooser
+                    .render_app_widget(area, buf, state.groups.columns(), chooser);
+            }
+            Some(ShellPopup::Detail(detail)) => {
+                if let Some(data) = data.group_detail.as_ref() {
+                    self.group_detail.render_app_widget(area, buf, data, detail);
+                }
+            }
+            Some(ShellPopup::Retention(editor)) => {
+                self.retention_editor
+                    .render_app_widget(area, buf, &(), editor);
+            }
+            Some(ShellPopup::Delete(confirm)) => {
+                self.delete_confirm
+                    .render_app_widget(area, buf, &(), confirm);
+            }
+            Some(ShellPopup::BulkMenu(menu)) => {
+                let marked = state.groups.marked_groups(&data.groups).len();
+                self.bulk_menu.render_app_widget(area, buf, &marked, menu);
+            }
+            Some(ShellPopup::TagInput(input)) => {
+                self.tag_input.render_app_widget(area, buf, &(), input);
+            }
+            Some(ShellPopup::BulkSummary(summary)) => {
+                self.bulk_summary.render_app_widget(area, buf, &(), summary);
+            }
+            Some(ShellPopup::Analytics(analytics)) => {
+                self.analytics
+                    .render_app_widget(area, buf, &data.groups, analytics);
+            }
+            Some(ShellPopup::Bookmarks(bookmarks)) => {
+                self.bookmarks
+                    .render_app_widget(area, buf, &data.bookmarks, bookmarks);
+            }
+            None => (),
+        }
     }
 }
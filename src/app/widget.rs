@@ -2,12 +2,22 @@ use tui::buffer::Buffer;
 use tui::layout::{Constraint, Direction, Layout, Rect};
 use tui::widgets::Widget;
 
+use std::collections::BTreeMap;
+
+use crate::app::config::AppConfig;
 use crate::app::data::AppData;
 use crate::app::header::state::HeaderState;
 use crate::app::header::widget::HeaderWidgetSet;
+use crate::app::session::state::SessionState;
+use crate::app::session::widget::SessionWidgetSet;
 use crate::app::shell::state::ShellState;
 use crate::app::shell::widget::ShellWidgetSet;
+use crate::app::AppFocus;
+use crate::session::SessionId;
 use crate::ui::widget::debug::{DebugState, DebugWidget};
+use crate::ui::widget::groups::GroupsStates;
+use crate::ui::widget::help::{HelpState, HelpWidget};
+use crate::ui::widget::status::{StatusState, StatusWidget};
 use crate::ui::widget::CustomWidget;
 
 #[derive(Debug, Clone, Default)]
@@ -15,29 +25,54 @@ pub struct AppWidgetStates {
     pub debug: DebugState,
     pub shell: ShellState,
     pub header: HeaderState,
+    pub sessions: BTreeMap<SessionId, SessionState>,
+    pub status: StatusState,
+    /// The help shown over the focused view.
+    pub help: Option<HelpState>,
+}
+
+impl AppWidgetStates {
+    pub fn with_config(config: &AppConfig) -> AppWidgetStates {
+        let mut states = AppWidgetStates::default();
+        states.shell.groups = GroupsStates::with_columns(config.group_columns.clone());
+        states
+    }
 }
 
 pub struct AppWidgetSet<'a> {
     pub data: &'a AppData,
     pub states: &'a mut AppWidgetStates,
+    pub focus: AppFocus,
     pub header: HeaderWidgetSet,
     pub shell: ShellWidgetSet,
+    pub session: SessionWidgetSet,
+    pub status: StatusWidget,
+    pub help: HelpWidget,
     pub debug: Option<DebugWidget>,
 }
 
 impl<'a> AppWidgetSet<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         data: &'a AppData,
         states: &'a mut AppWidgetStates,
+        focus: AppFocus,
         header: HeaderWidgetSet,
         shell: ShellWidgetSet,
+        session: SessionWidgetSet,
+        status: StatusWidget,
+        help: HelpWidget,
         debug: Option<DebugWidget>,
     ) -> AppWidgetSet<'a> {
         AppWidgetSet {
             data,
             states,
+            focus,
             header,
             shell,
+            session,
+            status,
+            help,
             debug,
         }
     }
@@ -49,10 +84,15 @@ impl<'a> Widget for AppWidgetSet<'a> {
             &[
                 Constraint::Length(3),
                 Constraint::Percentage(80),
+                Constraint::Length(1),
                 Constraint::Length(10),
             ]
         } else {
-            &[Constraint::Length(3), Constraint::Percentage(100)]
+            &[
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ]
         };
 
         let chunks = Layout::default()
@@ -62,11 +102,28 @@ impl<'a> Widget for AppWidgetSet<'a> {
 
         self.header
             .render_app_widget(chunks[0], buf, &self.data, &mut self.states.header);
-        self.shell
-            .render_app_widget(chunks[1], buf, self.data, &mut self.states.shell);
+        let session = match self.focus {
+            AppFocus::Session(id) => self
+                .data
+                .sessions
+                .get(id)
+                .zip(self.states.sessions.get_mut(&id)),
+            _ => None,
+        };
+        match session {
+            Some((data, state)) => self.session.render_app_widget(chunks[1], buf, data, state),
+            None => self
+                .shell
+                .render_app_widget(chunks[1], buf, self.data, &mut self.states.shell),
+        }
+        if let Some(help) = self.states.help.as_mut() {
+            self.help.render_app_widget(chunks[1], buf, &(), help);
+        }
+        self.status
+            .render_app_widget(chunks[2], buf, &self.data.status, &mut self.states.status);
         if let Some(debug) = self.debug {
             debug.render_app_widget(
-                chunks[2],
+                chunks[3],
                 buf,
                 self.data.debug().unwrap(),
                 &mut self.states.debug,
@@ -7,6 +7,31 @@ impl Arn {
     pub fn str(&self) -> &str {
         &self.0
     }
+
+    /// `arn:partition:service:region:account-id:resource`
+    fn part(&self, i: usize) -> Option<&str> {
+        self.0.splitn(6, ':').nth(i).filter(|s| !s.is_empty())
+    }
+
+    pub fn partition(&self) -> Option<&str> {
+        self.part(1)
+    }
+
+    pub fn service(&self) -> Option<&str> {
+        self.part(2)
+    }
+
+    pub fn region(&self) -> Option<&str> {
+        self.part(3)
+    }
+
+    pub fn account_id(&self) -> Option<&str> {
+        self.part(4)
+    }
+
+    pub fn resource(&self) -> Option<&str> {
+        self.part(5)
+    }
 }
 
 impl<T: Into<String>> From<T> for Arn {
@@ -33,4 +58,21 @@ mod tests {
             Arn::from("arn:aws:logs:ap-northeast-1:000000000000:log-group:log_group_name").str()
         )
     }
+
+    #[test]
+    fn test_parts() {
+        let arn = Arn::from("arn:aws:logs:ap-northeast-1:000000000000:log-group:/aws/lambda/f:*");
+        assert_eq!(Some("aws"), arn.partition());
+        assert_eq!(Some("logs"), arn.service());
+        assert_eq!(Some("ap-northeast-1"), arn.region());
+        assert_eq!(Some("000000000000"), arn.account_id());
+        assert_eq!(Some("log-group:/aws/lambda/f:*"), arn.resource());
+
+        let arn = Arn::from("arn:aws:s3:::bucket");
+        assert_eq!(None, arn.region());
+        assert_eq!(None, arn.account_id());
+        assert_eq!(Some("bucket"), arn.resource());
+
+        assert_eq!(None, Arn::from("invalid").region());
+    }
 }
@@ -1,4 +1,5 @@
 pub mod client;
+pub mod console;
 pub mod event;
 pub mod group;
 mod mapper;
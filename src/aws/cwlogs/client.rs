@@ -1,9 +1,11 @@
+use std::str::FromStr;
+
 use rusoto_core::credential::{CredentialsError, ProfileProvider};
 use rusoto_core::{HttpClient, Region};
 use rusoto_logs::CloudWatchLogsClient;
 use thiserror::Error;
 
-use crate::aws::profile::ProfileName;
+use crate::aws::profile::{default_config_path, ProfileName};
 use crate::collection::AsStr;
 use rusoto_core::request::TlsError;
 
@@ -22,12 +24,29 @@ impl ClientFactory {
     pub fn new_client(
         &self,
         profile_name: ProfileName,
+        region: Region,
     ) -> Result<CloudWatchLogsClient, ClientFactoryError> {
         let dispatcher = HttpClient::new()?;
         let provider = ProfileProvider::with_default_credentials(profile_name.as_str())?;
-        // FIXME: get region as an argument.
-        let region = Region::default();
         let client = CloudWatchLogsClient::new_with(dispatcher, provider, region);
         Ok(client)
     }
+
+    /// Resolves the region from `AWS_DEFAULT_REGION` or `AWS_REGION`, then from the profile in
+    /// the shared config file. Falls back to the default region of rusoto.
+    pub fn region(&self, profile_name: &ProfileName) -> Region {
+        let from_env = std::env::var("AWS_DEFAULT_REGION")
+            .or_else(|_| std::env::var("AWS_REGION"))
+            .ok();
+        let from_profile = || {
+            default_config_path()
+                .map(|path| ProfileProvider::with_configuration(path, profile_name.as_str()))
+                .and_then(|provider| provider.region_from_profile().ok().flatten())
+        };
+
+        from_env
+            .or_else(from_profile)
+            .and_then(|name| Region::from_str(&name).ok())
+            .unwrap_or_default()
+    }
 }
@@ -0,0 +1,232 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusoto_core::Region;
+
+use crate::time::TimeRange;
+
+/// Fields listed by a Logs Insights query opened from a session.
+const INSIGHTS_QUERY: &str = "fields @timestamp, @logStream, @message\n| sort @timestamp desc";
+
+/// A page of the CloudWatch console, with a short description of what it shows.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConsoleLink {
+    pub label: String,
+    pub url: String,
+}
+
+/// Bytes `encodeURIComponent` of JavaScript leaves as they are.
+fn is_unreserved(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&b)
+}
+
+/// Percent-encodes like `encodeURIComponent`, with the escape character in place of `%`.
+pub(crate) fn encode_component(s: &str, escape: char) -> String {
+    s.bytes()
+        .map(|b| {
+            if is_unreserved(b) {
+                (b as char).to_string()
+            } else {
+                format!("{}{:02X}", escape, b)
+            }
+        })
+        .collect()
+}
+
+/// The console reads the part after `#` with `$` in place of `%`, so a name is encoded twice.
+fn encode_name(s: &str) -> String {
+    encode_component(&encode_component(s, '%'), '$')
+}
+
+/// Parameters of a page, like `$3Fstart$3D1$26end$3D2`.
+fn encode_params(params: &[(&str, String)]) -> String {
+    let query = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, encode_component(v, '%')))
+        .collect::<Vec<_>>()
+        .join("&");
+    encode_component(&format!("?{}", query), '$')
+}
+
+fn home(region: &Region) -> String {
+    format!(
+        "https://{0}.console.aws.amazon.com/cloudwatch/home?region={0}",
+        region.name()
+    )
+}
+
+/// The group with its streams.
+pub fn group_link(region: &Region, group_name: &str) -> ConsoleLink {
+    ConsoleLink {
+        label: group_name.to_string(),
+        url: format!(
+            "{}#logsV2:log-groups/log-group/{}",
+            home(region),
+            encode_name(group_name)
+        ),
+    }
+}
+
+/// Events of the stream, or of every stream without one, in the time range.
+pub fn events_link(
+    region: &Region,
+    group_name: &str,
+    stream_name: Option<&str>,
+    range: &TimeRange,
+    filter_pattern: Option<&str>,
+) -> ConsoleLink {
+    let mut params = vec![
+        ("start", range.start_time().to_string()),
+        ("end", range.end_time().to_string()),
+    ];
+    if let Some(pattern) = filter_pattern {
+        params.push(("filterPattern", pattern.to_string()));
+    }
+    let group = group_link(region, group_name);
+    let (label, path) = match stream_name {
+        Some(stream_name) => (
+            format!("{} > {}", group.label, stream_name),
+            format!("/log-events/{}", encode_name(stream_name)),
+        ),
+        None => (
+            format!("{} > events", group.label),
+            "/log-events".to_string(),
+        ),
+    };
+    ConsoleLink {
+        label,
+        url: format!("{}{}{}", group.url, path, encode_params(&params)),
+    }
+}
+
+/// The event in its stream, listed from its time.
+pub fn event_link(
+    region: &Region,
+    group_name: &str,
+    stream_name: &str,
+    time: &DateTime<Utc>,
+) -> ConsoleLink {
+    let range = TimeRange {
+        start: *time,
+        end: *time + chrono::Duration::milliseconds(1),
+    };
+    let link = events_link(region, group_name, Some(stream_name), &range, None);
+    ConsoleLink {
+        label: format!("{} @ {}", link.label, time.to_rfc3339()),
+        ..link
+    }
+}
+
+/// Quotes a string of the query detail, which the console reads with `*` in place of `%`.
+/// Characters with a meaning in the detail, like `~` and `'`, are always encoded.
+fn rison_string(s: &str) -> String {
+    let escaped = s
+        .bytes()
+        .map(|b| {
+            if b.is_ascii_alphanumeric() || b"-_.".contains(&b) {
+                (b as char).to_string()
+            } else {
+                format!("*{:02x}", b)
+            }
+        })
+        .collect::<String>();
+    format!("'{}", escaped)
+}
+
+fn rison_time(time: &DateTime<Utc>) -> String {
+    rison_string(&time.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+/// A Logs Insights query over the groups in the time range, of the stream if given.
+pub fn insights_link(
+    region: &Region,
+    group_names: &[&str],
+    stream_name: Option<&str>,
+    range: &TimeRange,
+) -> ConsoleLink {
+    let query = match stream_name {
+        Some(stream_name) => format!(
+            "{}\n| filter @logStream = '{}'",
+            INSIGHTS_QUERY,
+            stream_name.replace('\'', "\\'")
+        ),
+        None => INSIGHTS_QUERY.to_string(),
+    };
+    let sources = group_names
+        .iter()
+        .map(|g| format!("~{}", rison_string(g)))
+        .collect::<String>();
+    let detail = format!(
+        "~(end~{}~start~{}~timeType~'ABSOLUTE~tz~'UTC~editorString~{}~source~({}))",
+        rison_time(&range.end),
+        rison_time(&range.start),
+        rison_string(&query),
+        sources
+    );
+    ConsoleLink {
+        label: format!("Logs Insights of {}", group_names.join(", ")),
+        url: format!(
+            "{}#logsV2:logs-insights{}{}",
+            home(region),
+            encode_component("?queryDetail=", '$'),
+            detail
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn range() -> TimeRange {
+        TimeRange {
+            start: Utc.timestamp(1_600_000_000, 0),
+            end: Utc.timestamp(1_600_003_600, 0),
+        }
+    }
+
+    #[test]
+    fn test_group_link() {
+        assert_eq!(
+            "https://ap-northeast-1.console.aws.amazon.com/cloudwatch/home?region=ap-northeast-1\
+             #logsV2:log-groups/log-group/$252Faws$252Flambda$252Fapi",
+            group_link(&Region::ApNortheast1, "/aws/lambda/api").url
+        );
+    }
+
+    #[test]
+    fn test_events_link() {
+        let link = events_link(
+            &Region::UsEast1,
+            "app",
+            Some("2020/09/13/[$LATEST]abc"),
+            &range(),
+            Some("{ $.level = \"error\" }"),
+        );
+        assert_eq!("app > 2020/09/13/[$LATEST]abc", link.label);
+        assert!(link.url.ends_with(
+            "#logsV2:log-groups/log-group/app/log-events/\
+             2020$252F09$252F13$252F$255B$2524LATEST$255Dabc\
+             $3Fstart$3D1600000000000$26end$3D1600003600000\
+             $26filterPattern$3D$257B$2520$2524.level$2520$253D$2520$2522error$2522$2520$257D"
+        ));
+
+        let link = events_link(&Region::UsEast1, "app", None, &range(), None);
+        assert!(link
+            .url
+            .ends_with("log-group/app/log-events$3Fstart$3D1600000000000$26end$3D1600003600000"));
+    }
+
+    #[test]
+    fn test_insights_link() {
+        let link = insights_link(&Region::UsEast1, &["/app/api", "db"], None, &range());
+        assert!(link.url.contains(
+            "#logsV2:logs-insights$3FqueryDetail$3D~(end~'2020-09-13T13*3a26*3a40.000Z~"
+        ));
+        assert!(link
+            .url
+            .contains("editorString~'fields*20*40timestamp*2c*20"));
+        assert!(link.url.ends_with("~source~(~'*2fapp*2fapi~'db))"));
+        assert_eq!("Logs Insights of /app/api, db", link.label);
+    }
+}
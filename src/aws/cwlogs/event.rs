@@ -1,12 +1,33 @@
+mod context;
 mod cursor;
+mod histogram;
+mod invocation;
+mod level;
+mod merge;
 mod model;
 mod store;
 
+pub use context::CwlEventContext;
+pub use context::CwlEventContextError;
+pub use context::CwlEventContextLoader;
+pub use context::CwlEventPage;
+pub use context::RusotoCwlEventContextLoader;
+pub use context::{ContextDirection, ContextPosition};
+
 pub use cursor::CwlEventCursor;
 pub use cursor::CwlEventCursorError;
 pub use cursor::RusotoCwlEventCursor;
 
+pub use histogram::Histogram;
+
+pub use invocation::{group_invocations, Invocation, InvocationStats, Percentiles};
+
+pub use level::{LevelDetector, LevelRule, LogLevel, ParseLevelRuleError, ParseLogLevelError};
+
+pub use merge::MergedCwlEventCursor;
+
 pub use model::CwlEvent;
+pub use model::EventId;
 pub use model::ParseLogEventError;
 
 pub use store::CwlEventStore;
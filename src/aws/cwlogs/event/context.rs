@@ -0,0 +1,275 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusoto_core::RusotoError;
+use rusoto_logs::{
+    CloudWatchLogs, CloudWatchLogsClient, GetLogEventsError, GetLogEventsRequest, OutputLogEvent,
+};
+use thiserror::Error;
+
+use crate::aws::cwlogs::event::model::{CwlEvent, EventId, ParseLogEventError};
+use crate::aws::cwlogs::mapper::{map_field, map_unix_epoch_millis};
+
+/// Which side of an event its context is read on.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ContextDirection {
+    Before,
+    After,
+}
+
+/// Where a page of the context starts, the event's time at first and a token to go further.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ContextPosition {
+    Time(DateTime<Utc>),
+    Token(String),
+}
+
+/// Events of a stream read by GetLogEvents, with the token of the next page.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CwlEventPage {
+    pub events: Vec<CwlEvent>,
+    pub token: Option<String>,
+}
+
+/// GetLogEvents returns no event ids, lines of the context are told apart by their position.
+fn context_event(stream_name: &str, event: OutputLogEvent) -> Result<CwlEvent, ParseLogEventError> {
+    Ok(CwlEvent {
+        event_id: EventId::from(""),
+        ingestion_time: map_unix_epoch_millis(event.ingestion_time, "ingestion_time", Utc)?,
+        stream_name: stream_name.to_string(),
+        message: map_field(event.message, "message")?,
+        event_time: map_unix_epoch_millis(event.timestamp, "timestamp", Utc)?,
+        level: None,
+        group_name: None,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum CwlEventContextError {
+    #[error("parse error")]
+    Parse {
+        #[from]
+        source: ParseLogEventError,
+    },
+
+    #[error("could not get log events. cause:{source}")]
+    Rusoto {
+        #[from]
+        source: RusotoError<GetLogEventsError>,
+    },
+}
+
+#[async_trait]
+pub trait CwlEventContextLoader {
+    async fn load(
+        &self,
+        direction: ContextDirection,
+        position: ContextPosition,
+        lines: i64,
+    ) -> Result<CwlEventPage, CwlEventContextError>;
+}
+
+/// Reads the lines around an event in its stream.
+#[derive(Clone)]
+pub struct RusotoCwlEventContextLoader {
+    client: CloudWatchLogsClient,
+    group_name: String,
+    stream_name: String,
+}
+
+impl RusotoCwlEventContextLoader {
+    pub fn new(client: CloudWatchLogsClient, group_name: String, stream_name: String) -> Self {
+        RusotoCwlEventContextLoader {
+            client,
+            group_name,
+            stream_name,
+        }
+    }
+}
+
+#[async_trait]
+impl CwlEventContextLoader for RusotoCwlEventContextLoader {
+    async fn load(
+        &self,
+        direction: ContextDirection,
+        position: ContextPosition,
+        lines: i64,
+    ) -> Result<CwlEventPage, CwlEventContextError> {
+        let mut req = GetLogEventsRequest {
+            log_group_name: self.group_name.clone(),
+            log_stream_name: self.stream_name.clone(),
+            limit: Some(lines),
+            start_from_head: Some(direction == ContextDirection::After),
+            ..Default::default()
+        };
+        match (direction, position) {
+            // the end time is exclusive, the event itself is read after it.
+            (ContextDirection::Before, ContextPosition::Time(t)) => {
+                req.end_time = Some(t.timestamp_millis())
+            }
+            (ContextDirection::After, ContextPosition::Time(t)) => {
+                req.start_time = Some(t.timestamp_millis())
+            }
+            (_, ContextPosition::Token(token)) => req.next_token = Some(token),
+        }
+
+        let res = self.client.get_log_events(req).await?;
+        let events = res
+            .events
+            .unwrap_or_default()
+            .into_iter()
+            .map(|e| context_event(&self.stream_name, e))
+            .collect::<Result<Vec<_>, _>>()?;
+        let token = match direction {
+            ContextDirection::Before => res.next_backward_token,
+            ContextDirection::After => res.next_forward_token,
+        };
+        Ok(CwlEventPage { events, token })
+    }
+}
+
+/// Lines of a stream around an event, growing a page at a time on either side.
+#[derive(Debug, Clone)]
+pub struct CwlEventContext {
+    target: CwlEvent,
+    events: Vec<CwlEvent>,
+    backward_token: Option<String>,
+    forward_token: Option<String>,
+    /// Whether the first page of each side is read, before which there is no token.
+    started: (bool, bool),
+}
+
+impl CwlEventContext {
+    pub fn new(target: CwlEvent) -> Self {
+        CwlEventContext {
+            target,
+            events: Vec::new(),
+            backward_token: None,
+            forward_token: None,
+            started: (false, false),
+        }
+    }
+
+    pub fn target(&self) -> &CwlEvent {
+        &self.target
+    }
+
+    pub fn events(&self) -> &[CwlEvent] {
+        &self.events
+    }
+
+    /// Position of the event among the lines, once they are read.
+    pub fn target_index(&self) -> Option<usize> {
+        self.events.iter().position(|e| {
+            e.event_time == self.target.event_time && e.message == self.target.message
+        })
+    }
+
+    /// Where the next page of the side starts, or `None` if the stream has no more lines.
+    pub fn next_position(&self, direction: ContextDirection) -> Option<ContextPosition> {
+        let (started, token) = match direction {
+            ContextDirection::Before => (self.started.0, &self.backward_token),
+            ContextDirection::After => (self.started.1, &self.forward_token),
+        };
+        if started {
+            token.clone().map(ContextPosition::Token)
+        } else {
+            Some(ContextPosition::Time(self.target.event_time))
+        }
+    }
+
+    /// Adds the lines of a page on its side, returns how many lines were added.
+    /// The side ends when the page gives back the token it was read with, as a page may be
+    /// empty in the middle of the stream.
+    pub fn extend(&mut self, direction: ContextDirection, page: CwlEventPage) -> usize {
+        let count = page.events.len();
+        let (started, token) = match direction {
+            ContextDirection::Before => (&mut self.started.0, &mut self.backward_token),
+            ContextDirection::After => (&mut self.started.1, &mut self.forward_token),
+        };
+        *token = if *started && page.token == *token {
+            None
+        } else {
+            page.token
+        };
+        *started = true;
+
+        match direction {
+            ContextDirection::Before => {
+                self.events.splice(0..0, page.events);
+            }
+            ContextDirection::After => self.events.extend(page.events),
+        }
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    /// Lines of the context have no ids.
+    fn event(secs: i64) -> CwlEvent {
+        CwlEvent::for_test(secs * 1000).id("")
+    }
+
+    fn page(secs: &[i64], token: &str) -> CwlEventPage {
+        CwlEventPage {
+            events: secs.iter().map(|s| event(*s)).collect(),
+            token: Some(token.to_string()),
+        }
+    }
+
+    fn times(context: &CwlEventContext) -> Vec<i64> {
+        context
+            .events()
+            .iter()
+            .map(|e| e.event_time.timestamp())
+            .collect()
+    }
+
+    #[test]
+    fn test_context() {
+        let mut context = CwlEventContext::new(event(5));
+        let at = Some(ContextPosition::Time(Utc.timestamp(5, 0)));
+        assert_eq!(at, context.next_position(ContextDirection::Before));
+        assert_eq!(None, context.target_index());
+
+        assert_eq!(
+            2,
+            context.extend(ContextDirection::After, page(&[5, 6], "f1"))
+        );
+        assert_eq!(
+            2,
+            context.extend(ContextDirection::Before, page(&[3, 4], "b1"))
+        );
+        assert_eq!(vec![3, 4, 5, 6], times(&context));
+        assert_eq!(Some(2), context.target_index());
+        assert_eq!(
+            Some(ContextPosition::Token("b1".to_string())),
+            context.next_position(ContextDirection::Before)
+        );
+
+        context.extend(ContextDirection::Before, page(&[1, 2], "b2"));
+        assert_eq!(vec![1, 2, 3, 4, 5, 6], times(&context));
+        assert_eq!(Some(4), context.target_index());
+
+        // an empty page with a new token does not end the side.
+        context.extend(ContextDirection::Before, page(&[], "b3"));
+        assert_eq!(
+            Some(ContextPosition::Token("b3".to_string())),
+            context.next_position(ContextDirection::Before)
+        );
+        context.extend(ContextDirection::Before, page(&[0], "b4"));
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 6], times(&context));
+        assert_eq!(Some(5), context.target_index());
+
+        // the token comes back unchanged at the start of the stream.
+        context.extend(ContextDirection::Before, page(&[], "b4"));
+        assert_eq!(None, context.next_position(ContextDirection::Before));
+        context.extend(ContextDirection::After, page(&[7], "f1"));
+        assert_eq!(None, context.next_position(ContextDirection::After));
+        assert_eq!(vec![0, 1, 2, 3, 4, 5, 6, 7], times(&context));
+    }
+}
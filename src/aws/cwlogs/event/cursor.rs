@@ -44,6 +44,7 @@ pub struct RusotoCwlEventCursor {
     client: CloudWatchLogsClient,
     request: FilterLogEventsRequest,
     next_token: Option<String>,
+    has_next: bool,
 }
 
 impl RusotoCwlEventCursor {
@@ -55,6 +56,7 @@ impl RusotoCwlEventCursor {
             client,
             request,
             next_token: None,
+            has_next: true,
         }
     }
 
@@ -67,9 +69,13 @@ impl RusotoCwlEventCursor {
 #[async_trait]
 impl CwlEventCursor for RusotoCwlEventCursor {
     async fn next(&mut self) -> Result<Option<Vec<CwlEvent>>, CwlEventCursorError> {
+        if !self.has_next {
+            return Ok(None);
+        }
         self.request.next_token = self.next_token.take();
 
         let res = self.filter_events().await?;
+        self.has_next = res.next_token.is_some();
         self.next_token = res.next_token;
 
         let events = cwl_events_from(res.events)?;
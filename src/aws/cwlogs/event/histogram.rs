@@ -0,0 +1,133 @@
+use chrono::{DateTime, Duration, Utc};
+
+use crate::aws::cwlogs::event::model::CwlEvent;
+use crate::time::TimeRange;
+
+/// Counts of events per time bucket, in one or more series.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Histogram {
+    range: TimeRange,
+    buckets: usize,
+    series: Vec<Vec<u64>>,
+}
+
+impl Histogram {
+    /// Counts events in `buckets` buckets of the range, into the series given by `series_of`.
+    /// Events out of the range or without a series are not counted.
+    pub fn new<'a, F>(
+        range: TimeRange,
+        buckets: usize,
+        series_count: usize,
+        events: impl Iterator<Item = &'a CwlEvent>,
+        series_of: F,
+    ) -> Self
+    where
+        F: Fn(&CwlEvent) -> Option<usize>,
+    {
+        let mut histogram = Histogram {
+            range,
+            buckets,
+            series: vec![vec![0; buckets]; series_count],
+        };
+        for event in events {
+            let bucket = histogram.bucket_of(&event.event_time);
+            if let (Some(bucket), Some(series)) = (bucket, series_of(event)) {
+                if let Some(counts) = histogram.series.get_mut(series) {
+                    counts[bucket] += 1;
+                }
+            }
+        }
+        histogram
+    }
+
+    pub fn buckets(&self) -> usize {
+        self.buckets
+    }
+
+    fn span_millis(&self) -> i64 {
+        (self.range.end - self.range.start)
+            .num_milliseconds()
+            .max(1)
+    }
+
+    /// Width of a bucket, the range divided evenly.
+    pub fn bucket_width(&self) -> Duration {
+        Duration::milliseconds(self.span_millis() / self.buckets().max(1) as i64)
+    }
+
+    pub fn bucket_of(&self, time: &DateTime<Utc>) -> Option<usize> {
+        if !self.range.contains(time) || self.buckets() == 0 {
+            return None;
+        }
+        let offset = (*time - self.range.start).num_milliseconds() as i128;
+        let i = offset * self.buckets() as i128 / self.span_millis() as i128;
+        Some((i as usize).min(self.buckets() - 1))
+    }
+
+    /// The time range of the bucket, to zoom into it.
+    pub fn bucket_range(&self, i: usize) -> TimeRange {
+        let boundary = |i: usize| {
+            let offset = self.span_millis() as i128 * i as i128 / self.buckets().max(1) as i128;
+            self.range.start + Duration::milliseconds(offset as i64)
+        };
+        TimeRange {
+            start: boundary(i),
+            end: boundary(i + 1),
+        }
+    }
+
+    pub fn series(&self, i: usize) -> &[u64] {
+        &self.series[i]
+    }
+
+    pub fn series_count(&self) -> usize {
+        self.series.len()
+    }
+
+    /// Count of the bucket in every series.
+    pub fn total(&self, bucket: usize) -> u64 {
+        self.series.iter().filter_map(|s| s.get(bucket)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn event(secs: i64) -> CwlEvent {
+        CwlEvent::for_test(secs * 1000)
+    }
+
+    #[test]
+    fn test_histogram() {
+        let range = TimeRange {
+            start: Utc.timestamp(0, 0),
+            end: Utc.timestamp(60, 0),
+        };
+        let events = [
+            event(0),
+            event(5),
+            event(14),
+            event(15),
+            event(59),
+            event(60),
+        ];
+        // even seconds in the first series, odd ones in the second.
+        let histogram = Histogram::new(range, 4, 2, events.iter(), |e| {
+            Some((e.event_time.timestamp() % 2) as usize)
+        });
+
+        assert_eq!(4, histogram.buckets());
+        assert_eq!(Duration::seconds(15), histogram.bucket_width());
+        assert_eq!(&[2, 0, 0, 0], histogram.series(0));
+        assert_eq!(&[1, 1, 0, 1], histogram.series(1));
+        assert_eq!(3, histogram.total(0));
+        assert_eq!(None, histogram.bucket_of(&Utc.timestamp(60, 0)));
+
+        let bucket = histogram.bucket_range(1);
+        assert_eq!(Utc.timestamp(15, 0), bucket.start);
+        assert_eq!(Utc.timestamp(30, 0), bucket.end);
+    }
+}
@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::aws::cwlogs::event::level::LogLevel;
+use crate::aws::cwlogs::event::model::CwlEvent;
+
+/// A Lambda invocation, made from the events between its START and REPORT lines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub request_id: String,
+    pub stream_name: String,
+    pub start: DateTime<Utc>,
+    pub duration_ms: Option<f64>,
+    pub billed_duration_ms: Option<f64>,
+    pub memory_size_mb: Option<u64>,
+    pub max_memory_used_mb: Option<u64>,
+    /// Initialization time of a cold start.
+    pub init_duration_ms: Option<f64>,
+    pub has_error: bool,
+    pub events: Vec<CwlEvent>,
+}
+
+impl Invocation {
+    fn new(request_id: &str, event: &CwlEvent) -> Self {
+        Invocation {
+            request_id: request_id.to_string(),
+            stream_name: event.stream_name.clone(),
+            start: event.event_time,
+            duration_ms: None,
+            billed_duration_ms: None,
+            memory_size_mb: None,
+            max_memory_used_mb: None,
+            init_duration_ms: None,
+            has_error: false,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn is_cold_start(&self) -> bool {
+        self.init_duration_ms.is_some()
+    }
+
+    fn apply_report(&mut self, message: &str) {
+        for column in message.split('\t').map(str::trim) {
+            let (name, value) = match column.find(": ") {
+                Some(i) => (&column[..i], &column[i + 2..]),
+                None => continue,
+            };
+            let millis = || value.trim_end_matches(" ms").parse::<f64>().ok();
+            let megabytes = || value.trim_end_matches(" MB").parse::<u64>().ok();
+            match name {
+                "Duration" => self.duration_ms = millis(),
+                "Billed Duration" => self.billed_duration_ms = millis(),
+                "Memory Size" => self.memory_size_mb = megabytes(),
+                "Max Memory Used" => self.max_memory_used_mb = megabytes(),
+                "Init Duration" => self.init_duration_ms = millis(),
+                "Status" if value != "success" => self.has_error = true,
+                _ => (),
+            }
+        }
+    }
+}
+
+/// The request id of START, END and REPORT lines.
+fn platform_request_id(message: &str) -> Option<(&str, &str)> {
+    let (kind, rest) = message.split_at(message.find(' ')?);
+    if !matches!(kind, "START" | "END" | "REPORT") {
+        return None;
+    }
+    let rest = rest.trim_start().strip_prefix("RequestId: ")?;
+    let end = rest.find(|c: char| c.is_whitespace()).unwrap_or(rest.len());
+    Some((kind, &rest[..end]))
+}
+
+fn is_request_id(s: &str) -> bool {
+    s.len() == 36
+        && s.chars().filter(|c| *c == '-').count() == 4
+        && s.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// The request id written by the runtimes, like `<time>\t<request id>\tINFO\t<message>` of
+/// Node.js or `[INFO]\t<time>\t<request id>\t<message>` of Python.
+fn runtime_request_id(message: &str) -> Option<&str> {
+    message
+        .splitn(4, ['\t', ' '])
+        .take(3)
+        .find(|s| is_request_id(s))
+}
+
+/// Groups events of a Lambda function into invocations, in the order they started.
+/// Lines without a request id belong to the running invocation of their stream.
+pub fn group_invocations<'a>(events: impl Iterator<Item = &'a CwlEvent>) -> Vec<Invocation> {
+    let mut invocations: Vec<Invocation> = Vec::new();
+    let mut by_request_id = HashMap::new();
+    let mut running = HashMap::new();
+
+    for event in events {
+        let message = event.message.trim_end();
+        let (kind, request_id) = match platform_request_id(message) {
+            Some((kind, id)) => (Some(kind), Some(id)),
+            None => (None, runtime_request_id(message)),
+        };
+        let request_id = match request_id.or_else(|| running.get(&event.stream_name).copied()) {
+            Some(id) => id,
+            None => continue,
+        };
+
+        let i = *by_request_id.entry(request_id).or_insert_with(|| {
+            invocations.push(Invocation::new(request_id, event));
+            invocations.len() - 1
+        });
+        let invocation = &mut invocations[i];
+        match kind {
+            Some("START") => {
+                running.insert(&event.stream_name, request_id);
+                invocation.start = invocation.start.min(event.event_time);
+            }
+            Some("END") => {
+                running.remove(&event.stream_name);
+            }
+            Some(_) => invocation.apply_report(message),
+            None => {
+                if event.level >= Some(LogLevel::Error) || message.contains("Task timed out") {
+                    invocation.has_error = true;
+                }
+            }
+        }
+        invocation.events.push(event.clone());
+    }
+
+    invocations.sort_by_key(|invocation| invocation.start);
+    invocations
+}
+
+/// Percentiles of a measure over invocations.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Percentiles {
+    /// Nearest-rank percentiles, or `None` without values.
+    pub fn of(mut values: Vec<f64>) -> Option<Percentiles> {
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let rank = |p: f64| {
+            let i = (p * values.len() as f64).ceil() as usize;
+            values[i.max(1) - 1]
+        };
+        Some(Percentiles {
+            p50: rank(0.5),
+            p95: rank(0.95),
+            max: values[values.len() - 1],
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvocationStats {
+    pub count: usize,
+    pub errors: usize,
+    pub cold_starts: usize,
+    pub duration_ms: Option<Percentiles>,
+    pub billed_duration_ms: Option<Percentiles>,
+    pub max_memory_used_mb: Option<Percentiles>,
+    pub init_duration_ms: Option<Percentiles>,
+}
+
+impl InvocationStats {
+    pub fn new(invocations: &[Invocation]) -> Self {
+        let percentiles = |f: fn(&Invocation) -> Option<f64>| {
+            Percentiles::of(invocations.iter().filter_map(f).collect())
+        };
+        InvocationStats {
+            count: invocations.len(),
+            errors: invocations.iter().filter(|i| i.has_error).count(),
+            cold_starts: invocations.iter().filter(|i| i.is_cold_start()).count(),
+            duration_ms: percentiles(|i| i.duration_ms),
+            billed_duration_ms: percentiles(|i| i.billed_duration_ms),
+            max_memory_used_mb: percentiles(|i| i.max_memory_used_mb.map(|m| m as f64)),
+            init_duration_ms: percentiles(|i| i.init_duration_ms),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const ID1: &str = "5b1d2c3e-0000-4000-8000-000000000001";
+    const ID2: &str = "5b1d2c3e-0000-4000-8000-000000000002";
+
+    fn event(ms: i64, message: &str) -> CwlEvent {
+        CwlEvent::for_test(ms).message(message)
+    }
+
+    #[test]
+    fn test_group_invocations() {
+        let events = [
+            event(1, "INIT_START Runtime Version: nodejs:14"),
+            event(2, &format!("START RequestId: {} Version: $LATEST\n", ID1)),
+            event(
+                3,
+                &format!("2021-01-02T03:04:05.678Z\t{}\tINFO\thello\n", ID1),
+            ),
+            event(4, "a line without a request id\n"),
+            event(5, &format!("END RequestId: {}\n", ID1)),
+            event(
+                6,
+                &format!(
+                    "REPORT RequestId: {}\tDuration: 12.50 ms\tBilled Duration: 13 ms\t\
+                     Memory Size: 128 MB\tMax Memory Used: 70 MB\tInit Duration: 150.25 ms\t\n",
+                    ID1
+                ),
+            ),
+            event(7, &format!("START RequestId: {} Version: $LATEST\n", ID2)),
+            event(
+                8,
+                &format!(
+                    "2021-01-02T03:04:05.800Z {} Task timed out after 3.00 seconds",
+                    ID2
+                ),
+            ),
+            event(9, &format!("END RequestId: {}\n", ID2)),
+            event(
+                10,
+                &format!(
+                    "REPORT RequestId: {}\tDuration: 3000.00 ms\tBilled Duration: 3000 ms\t\
+                     Memory Size: 128 MB\tMax Memory Used: 80 MB\t\n",
+                    ID2
+                ),
+            ),
+            event(11, "a line after the invocations\n"),
+        ];
+
+        let invocations = group_invocations(events.iter());
+        assert_eq!(2, invocations.len());
+
+        let first = &invocations[0];
+        assert_eq!(ID1, first.request_id);
+        assert_eq!(Utc.timestamp_millis(2), first.start);
+        assert_eq!(Some(12.5), first.duration_ms);
+        assert_eq!(Some(13.0), first.billed_duration_ms);
+        assert_eq!(Some(128), first.memory_size_mb);
+        assert_eq!(Some(70), first.max_memory_used_mb);
+        assert_eq!(Some(150.25), first.init_duration_ms);
+        assert!(first.is_cold_start());
+        assert!(!first.has_error);
+        assert_eq!(5, first.events.len());
+
+        let second = &invocations[1];
+        assert_eq!(ID2, second.request_id);
+        assert!(!second.is_cold_start());
+        assert!(second.has_error);
+        assert_eq!(4, second.events.len());
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(None, Percentiles::of(Vec::new()));
+        let p = Percentiles::of((1..=100).rev().map(f64::from).collect()).unwrap();
+        assert_eq!((50.0, 95.0, 100.0), (p.p50, p.p95, p.max));
+        let p = Percentiles::of(vec![3.0]).unwrap();
+        assert_eq!((3.0, 3.0, 3.0), (p.p50, p.p95, p.max));
+    }
+}
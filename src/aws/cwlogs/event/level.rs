@@ -0,0 +1,260 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::json::{parse_message, JsonPath};
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[error("unknown log level `{0}`")]
+pub struct ParseLogLevelError(String);
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 6] = [
+        LogLevel::Trace,
+        LogLevel::Debug,
+        LogLevel::Info,
+        LogLevel::Warn,
+        LogLevel::Error,
+        LogLevel::Fatal,
+    ];
+
+    /// Numeric levels of pino and bunyan, 30 for info.
+    fn from_number(n: f64) -> LogLevel {
+        match n {
+            n if n <= 10.0 => LogLevel::Trace,
+            n if n <= 20.0 => LogLevel::Debug,
+            n if n <= 30.0 => LogLevel::Info,
+            n if n <= 40.0 => LogLevel::Warn,
+            n if n <= 50.0 => LogLevel::Error,
+            _ => LogLevel::Fatal,
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = ParseLogLevelError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" | "dbg" => Ok(LogLevel::Debug),
+            "info" | "information" | "notice" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" | "err" => Ok(LogLevel::Error),
+            "fatal" | "critical" | "crit" | "panic" | "alert" | "emerg" | "emergency" => {
+                Ok(LogLevel::Fatal)
+            }
+            _ => Err(ParseLogLevelError(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+            LogLevel::Fatal => "FATAL",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[error("unknown level rule `{0}`, expected json:<path>, lambda or prefix")]
+pub struct ParseLevelRuleError(String);
+
+/// A way of finding the level in a message.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum LevelRule {
+    /// A field of JSON messages like `$.level`, either a name or a number of pino.
+    Json(JsonPath),
+    /// The third column of Lambda's `<time>\t<request id>\t<LEVEL>\t<message>`.
+    Lambda,
+    /// The first word of the message like `[ERROR]`, `WARN:` or `INFO `.
+    Prefix,
+}
+
+impl LevelRule {
+    fn detect(&self, message: &str, json: Option<&Value>) -> Option<LogLevel> {
+        match self {
+            LevelRule::Json(path) => match json.and_then(|json| path.lookup(json))? {
+                Value::String(s) => LogLevel::from_str(s).ok(),
+                Value::Number(n) => n.as_f64().map(LogLevel::from_number),
+                _ => None,
+            },
+            LevelRule::Lambda => {
+                let columns = message.splitn(4, '\t').collect::<Vec<_>>();
+                match columns.as_slice() {
+                    [time, _, level, _] if time.starts_with(|c: char| c.is_ascii_digit()) => {
+                        LogLevel::from_str(level).ok()
+                    }
+                    _ => None,
+                }
+            }
+            LevelRule::Prefix => {
+                let s = message.trim_start();
+                let (s, bracket) = match s.strip_prefix('[') {
+                    Some(s) => (s, true),
+                    None => (s, false),
+                };
+                let end = s
+                    .find(|c: char| !c.is_ascii_alphabetic())
+                    .unwrap_or(s.len());
+                let rest = &s[end..];
+                let closed = if bracket {
+                    rest.starts_with(']')
+                } else {
+                    rest.is_empty() || rest.starts_with(|c: char| c == ':' || c.is_whitespace())
+                };
+                if closed {
+                    LogLevel::from_str(&s[..end]).ok()
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for LevelRule {
+    type Err = ParseLevelRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseLevelRuleError(s.to_string());
+        match s.trim() {
+            "lambda" => Ok(LevelRule::Lambda),
+            "prefix" => Ok(LevelRule::Prefix),
+            s => s
+                .strip_prefix("json:")
+                .and_then(|path| JsonPath::from_str(path).ok())
+                .map(LevelRule::Json)
+                .ok_or_else(error),
+        }
+    }
+}
+
+impl TryFrom<String> for LevelRule {
+    type Error = ParseLevelRuleError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        LevelRule::from_str(&s)
+    }
+}
+
+/// Classifies messages into levels by the first rule finding one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LevelDetector {
+    rules: Vec<LevelRule>,
+}
+
+impl LevelDetector {
+    pub fn new(rules: Vec<LevelRule>) -> Self {
+        LevelDetector { rules }
+    }
+
+    /// Finds the level of a message whose JSON is already parsed.
+    pub fn detect(&self, message: &str, json: Option<&Value>) -> Option<LogLevel> {
+        self.rules
+            .iter()
+            .find_map(|rule| rule.detect(message, json))
+    }
+
+    /// Finds the level of a message, parsing its JSON only if a rule needs it.
+    pub fn detect_message(&self, message: &str) -> Option<LogLevel> {
+        let needs_json = self.rules.iter().any(|r| matches!(r, LevelRule::Json(_)));
+        let json = if needs_json {
+            parse_message(message)
+        } else {
+            None
+        };
+        self.detect(message, json.as_ref())
+    }
+}
+
+impl Default for LevelDetector {
+    fn default() -> Self {
+        let json = |path: &str| LevelRule::Json(JsonPath::from_str(path).unwrap());
+        LevelDetector::new(vec![
+            json("$.level"),
+            json("$.severity"),
+            LevelRule::Lambda,
+            LevelRule::Prefix,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level() {
+        assert_eq!(Ok(LogLevel::Warn), LogLevel::from_str("Warning"));
+        assert_eq!(Ok(LogLevel::Fatal), LogLevel::from_str("CRITICAL"));
+        assert!(LogLevel::from_str("verbose").is_err());
+        assert!(LogLevel::Debug < LogLevel::Info);
+        assert_eq!("WARN ", format!("{:5}", LogLevel::Warn));
+    }
+
+    #[test]
+    fn test_detect() {
+        let detector = LevelDetector::default();
+        let detect = |s| detector.detect_message(s);
+
+        assert_eq!(
+            Some(LogLevel::Error),
+            detect(r#"{"level": "error", "msg": "x"}"#)
+        );
+        assert_eq!(Some(LogLevel::Warn), detect(r#"{"severity": "WARNING"}"#));
+        assert_eq!(
+            Some(LogLevel::Info),
+            detect(r#"{"level": 30, "msg": "pino"}"#)
+        );
+        assert_eq!(
+            Some(LogLevel::Debug),
+            detect("2021-01-02T03:04:05.678Z\tc0ffee\tDEBUG\tstarted")
+        );
+        assert_eq!(
+            Some(LogLevel::Error),
+            detect("[ERROR]\t2021-01-02T03:04:05.678Z\tc0ffee\tfailed")
+        );
+        assert_eq!(Some(LogLevel::Warn), detect("WARN: disk is almost full"));
+        assert_eq!(Some(LogLevel::Info), detect("INFO started"));
+        assert_eq!(None, detect("Warnings are not levels"));
+        assert_eq!(None, detect("[ERROR failed"));
+        assert_eq!(None, detect("START RequestId: c0ffee"));
+    }
+
+    #[test]
+    fn test_rules() {
+        let detector = LevelDetector::new(vec![LevelRule::from_str("json:$.log.lvl").unwrap()]);
+        assert_eq!(
+            Some(LogLevel::Trace),
+            detector.detect_message(r#"{"log": {"lvl": "trace"}}"#)
+        );
+        assert_eq!(None, detector.detect_message("[ERROR] failed"));
+
+        assert_eq!(Ok(LevelRule::Lambda), LevelRule::from_str("lambda"));
+        assert!(LevelRule::from_str("json:level").is_err());
+        assert!(LevelRule::from_str("regex").is_err());
+    }
+}
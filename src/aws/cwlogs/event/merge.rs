@@ -0,0 +1,235 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+
+use crate::aws::cwlogs::event::cursor::{CwlEventCursor, CwlEventCursorError};
+use crate::aws::cwlogs::event::model::CwlEvent;
+
+/// Takes events up to the time from the front of each queue, merged in time order.
+/// Each queue must be in time order, every event is taken without the time.
+fn merge_until(queues: &mut [VecDeque<CwlEvent>], until: Option<DateTime<Utc>>) -> Vec<CwlEvent> {
+    let is_due = |event: &CwlEvent| until.map(|t| event.event_time <= t).unwrap_or(true);
+    let mut heads = queues
+        .iter()
+        .enumerate()
+        .filter_map(|(i, q)| {
+            q.front()
+                .filter(|e| is_due(e))
+                .map(|e| Reverse((e.event_time, i)))
+        })
+        .collect::<BinaryHeap<_>>();
+
+    let mut merged = Vec::new();
+    while let Some(Reverse((_, i))) = heads.pop() {
+        merged.extend(queues[i].pop_front());
+        if let Some(next) = queues[i].front().filter(|e| is_due(e)) {
+            heads.push(Reverse((next.event_time, i)));
+        }
+    }
+    merged
+}
+
+struct MergeSource<C> {
+    group_name: String,
+    cursor: C,
+    queue: VecDeque<CwlEvent>,
+    done: bool,
+}
+
+/// Events of several groups in time order, reading a cursor per group concurrently.
+///
+/// An event is only returned once every group still being read has reached its time,
+/// so events of a slower group are never returned out of order.
+pub struct MergedCwlEventCursor<C> {
+    sources: Vec<MergeSource<C>>,
+}
+
+impl<C> MergedCwlEventCursor<C> {
+    /// Merges the cursors, each given with the name of its group.
+    pub fn new(cursors: Vec<(String, C)>) -> Self {
+        let sources = cursors
+            .into_iter()
+            .map(|(group_name, cursor)| MergeSource {
+                group_name,
+                cursor,
+                queue: VecDeque::new(),
+                done: false,
+            })
+            .collect();
+        MergedCwlEventCursor { sources }
+    }
+
+    /// The earliest time every unfinished group has reached, `None` when all are done or
+    /// a group has not returned any events yet.
+    fn watermark(&self) -> Option<DateTime<Utc>> {
+        self.sources
+            .iter()
+            .filter(|s| !s.done)
+            .map(|s| s.queue.back().map(|e| e.event_time))
+            .min()
+            .flatten()
+    }
+}
+
+fn with_group(mut events: Vec<CwlEvent>, group_name: &str) -> Vec<CwlEvent> {
+    events.sort_by_key(|e| e.event_time);
+    for event in events.iter_mut() {
+        event.group_name = Some(group_name.to_string());
+    }
+    events
+}
+
+#[async_trait]
+impl<C> CwlEventCursor for MergedCwlEventCursor<C>
+where
+    C: CwlEventCursor + Send + Sync,
+{
+    /// Reads the next page of every group without queued events, and returns the events
+    /// every group has reached. The page may be empty while a group is still behind.
+    async fn next(&mut self) -> Result<Option<Vec<CwlEvent>>, CwlEventCursorError> {
+        let pending = self
+            .sources
+            .iter_mut()
+            .filter(|s| !s.done && s.queue.is_empty())
+            .map(|s| async move {
+                let page = s.cursor.next().await;
+                (s, page)
+            });
+        for (source, page) in join_all(pending).await {
+            match page? {
+                Some(events) => source.queue.extend(with_group(events, &source.group_name)),
+                None => source.done = true,
+            }
+        }
+
+        let all_done = self.sources.iter().all(|s| s.done);
+        if all_done && self.sources.iter().all(|s| s.queue.is_empty()) {
+            return Ok(None);
+        }
+        let until = self.watermark();
+        if until.is_none() && !all_done {
+            return Ok(Some(Vec::new()));
+        }
+
+        let mut queues = self
+            .sources
+            .iter_mut()
+            .map(|s| std::mem::take(&mut s.queue))
+            .collect::<Vec<_>>();
+        let merged = merge_until(&mut queues, until);
+        for (source, queue) in self.sources.iter_mut().zip(queues) {
+            source.queue = queue;
+        }
+        Ok(Some(merged))
+    }
+
+    async fn refresh(&self) -> Result<Option<Vec<CwlEvent>>, CwlEventCursorError> {
+        let pages = join_all(self.sources.iter().map(|s| s.cursor.refresh())).await;
+        let mut queues = Vec::new();
+        for (source, page) in self.sources.iter().zip(pages) {
+            let events = page?.unwrap_or_default();
+            queues.push(with_group(events, &source.group_name).into());
+        }
+        Ok(Some(merge_until(&mut queues, None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn event(secs: i64, stream_name: &str) -> CwlEvent {
+        CwlEvent::for_test(secs * 1000).stream(stream_name)
+    }
+
+    fn times(events: &[CwlEvent]) -> Vec<i64> {
+        events.iter().map(|e| e.event_time.timestamp()).collect()
+    }
+
+    struct FakeCursor {
+        pages: VecDeque<Vec<CwlEvent>>,
+    }
+
+    impl FakeCursor {
+        fn new(pages: Vec<Vec<i64>>) -> Self {
+            let pages = pages
+                .into_iter()
+                .map(|page| page.into_iter().map(|s| event(s, "s")).collect())
+                .collect();
+            FakeCursor { pages }
+        }
+    }
+
+    #[async_trait]
+    impl CwlEventCursor for FakeCursor {
+        async fn next(&mut self) -> Result<Option<Vec<CwlEvent>>, CwlEventCursorError> {
+            Ok(self.pages.pop_front())
+        }
+
+        async fn refresh(&self) -> Result<Option<Vec<CwlEvent>>, CwlEventCursorError> {
+            Ok(self.pages.front().cloned())
+        }
+    }
+
+    #[test]
+    fn test_merge_until() {
+        let mut queues = vec![
+            VecDeque::from(vec![event(1, "a"), event(4, "a"), event(9, "a")]),
+            VecDeque::from(vec![event(2, "b"), event(3, "b")]),
+            VecDeque::from(vec![event(4, "c"), event(5, "c")]),
+        ];
+
+        let merged = merge_until(&mut queues, Some(Utc.timestamp(4, 0)));
+        assert_eq!(vec![1, 2, 3, 4, 4], times(&merged));
+        assert_eq!(vec![9], times(&Vec::from(queues[0].clone())));
+        assert!(queues[1].is_empty());
+        assert_eq!(vec![5], times(&Vec::from(queues[2].clone())));
+
+        let merged = merge_until(&mut queues, None);
+        assert_eq!(vec![5, 9], times(&merged));
+    }
+
+    #[tokio::test]
+    async fn test_merged_cursor() {
+        let mut cursor = MergedCwlEventCursor::new(vec![
+            (
+                "api".to_string(),
+                FakeCursor::new(vec![vec![1, 5], vec![8]]),
+            ),
+            (
+                "db".to_string(),
+                FakeCursor::new(vec![vec![], vec![2, 3, 9]]),
+            ),
+        ]);
+
+        // db has no events yet, so nothing is known to be in order.
+        assert_eq!(
+            Some(vec![]),
+            cursor.next().await.unwrap().map(|e| times(&e))
+        );
+        // db reached 9 and api 5.
+        let page = cursor.next().await.unwrap().unwrap();
+        assert_eq!(vec![1, 2, 3, 5], times(&page));
+        assert_eq!(
+            vec!["api", "db", "db", "api"],
+            page.iter()
+                .map(|e| e.group_name.as_deref().unwrap())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(vec![8]),
+            cursor.next().await.unwrap().map(|e| times(&e))
+        );
+        // api is done, so db is not waited for.
+        assert_eq!(
+            Some(vec![9]),
+            cursor.next().await.unwrap().map(|e| times(&e))
+        );
+        assert_eq!(None, cursor.next().await.unwrap());
+    }
+}
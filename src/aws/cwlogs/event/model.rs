@@ -4,11 +4,13 @@ use chrono::{DateTime, Utc};
 use rusoto_logs::FilteredLogEvent;
 use thiserror::Error;
 
+use crate::aws::cwlogs::event::level::LogLevel;
 use crate::aws::cwlogs::mapper::{map_field, map_string_field, map_unix_epoch_millis};
 use crate::aws::errors::MissingFieldError;
+use crate::collection::AsStr;
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct EventId(String);
 
 impl From<String> for EventId {
@@ -23,6 +25,12 @@ impl From<&str> for EventId {
     }
 }
 
+impl AsStr for EventId {
+    fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 pub enum ParseLogEventError {
     #[error("missing field")]
@@ -39,6 +47,10 @@ pub struct CwlEvent {
     pub stream_name: String,
     pub message: String,
     pub event_time: DateTime<Utc>,
+    /// Level of the message, classified after the event is received.
+    pub level: Option<LogLevel>,
+    /// Group of the event in a merged view of several groups.
+    pub group_name: Option<String>,
 }
 
 impl PartialOrd for CwlEvent {
@@ -68,10 +80,51 @@ impl TryFrom<FilteredLogEvent> for CwlEvent {
             stream_name,
             message,
             event_time,
+            level: None,
+            group_name: None,
         })
     }
 }
 
+#[cfg(test)]
+impl CwlEvent {
+    /// An event of the stream `stream` at the time in milliseconds, identified by the time.
+    pub fn for_test(millis: i64) -> CwlEvent {
+        use chrono::TimeZone;
+
+        CwlEvent {
+            event_id: EventId::from(millis.to_string()),
+            ingestion_time: Utc.timestamp_millis(millis),
+            stream_name: "stream".to_string(),
+            message: format!("event at {}", millis),
+            event_time: Utc.timestamp_millis(millis),
+            level: None,
+            group_name: None,
+        }
+    }
+
+    pub fn id(self, id: &str) -> CwlEvent {
+        CwlEvent {
+            event_id: EventId::from(id),
+            ..self
+        }
+    }
+
+    pub fn stream(self, stream_name: &str) -> CwlEvent {
+        CwlEvent {
+            stream_name: stream_name.to_string(),
+            ..self
+        }
+    }
+
+    pub fn message(self, message: &str) -> CwlEvent {
+        CwlEvent {
+            message: message.to_string(),
+            ..self
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,6 +150,8 @@ mod tests {
                 stream_name: String::from("STREAM-NAME"),
                 message: String::from("MESSAGE"),
                 event_time: Utc.ymd(2020, 11, 2).and_hms_milli(11, 22, 33, 111),
+                level: None,
+                group_name: None,
             }),
             CwlEvent::try_from(log_event())
         )
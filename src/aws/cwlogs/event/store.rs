@@ -1,37 +1,176 @@
-use crate::aws::cwlogs::event::model::CwlEvent;
-use crate::collection::store::Store;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::aws::cwlogs::event::level::LevelDetector;
+use crate::aws::cwlogs::event::model::{CwlEvent, EventId};
+use crate::collection::{AsStr, Length};
+use crate::json::parse_message;
 
 #[derive(Debug)]
 pub struct CwlEventStore {
-    events: Store<CwlEvent>,
+    /// Events ordered by time, then by stream and id.
+    events: Vec<CwlEvent>,
+    /// JSON of the message of every stored event, parsed once when the event is added.
+    json: HashMap<EventId, Option<Value>>,
+    loading: bool,
 }
 
 impl CwlEventStore {
-    pub fn new(events: Store<CwlEvent>) -> Self {
-        CwlEventStore { events }
+    pub fn new(events: impl Iterator<Item = CwlEvent>) -> Self {
+        let mut store = CwlEventStore {
+            events: Vec::new(),
+            json: HashMap::new(),
+            loading: false,
+        };
+        store.extend(events);
+        store
     }
 
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.json.clear();
+    }
+
+    /// Adds the event in time order. Events already stored are ignored.
     pub fn insert(&mut self, event: CwlEvent) {
-        self.events.insert(event);
+        if !self.json.contains_key(&event.event_id) {
+            let json = parse_message(&event.message);
+            self.insert_parsed(event, json);
+        }
+    }
+
+    /// Adds the event like `insert`, finding its level from the JSON parsed for the store.
+    pub fn insert_detected(&mut self, mut event: CwlEvent, detector: &LevelDetector) {
+        if !self.json.contains_key(&event.event_id) {
+            let json = parse_message(&event.message);
+            event.level = detector.detect(&event.message, json.as_ref());
+            self.insert_parsed(event, json);
+        }
+    }
+
+    fn insert_parsed(&mut self, event: CwlEvent, json: Option<Value>) {
+        self.json.insert(event.event_id.clone(), json);
+
+        let i = self
+            .events
+            .binary_search_by(|e| time_order(e).cmp(&time_order(&event)))
+            .unwrap_or_else(|i| i);
+        self.events.insert(i, event);
     }
 
     pub fn extend(&mut self, events: impl Iterator<Item = CwlEvent>) {
-        self.events.extend(events);
+        events.for_each(|event| self.insert(event));
+    }
+
+    pub fn extend_detected(
+        &mut self,
+        events: impl Iterator<Item = CwlEvent>,
+        detector: &LevelDetector,
+    ) {
+        events.for_each(|event| self.insert_detected(event, detector));
+    }
+
+    /// JSON of the message of the stored event, if the message has one.
+    pub fn json(&self, event: &CwlEvent) -> Option<&Value> {
+        self.json.get(&event.event_id).and_then(Option::as_ref)
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
     }
 
     pub fn order_by_asc(&self) -> impl Iterator<Item = &CwlEvent> {
-        self.events
-            .order_by_asc_ref(|event| (event.event_time, &event.stream_name))
+        self.events.iter()
     }
 
     pub fn order_by_desc(&self) -> impl Iterator<Item = &CwlEvent> {
-        self.events
-            .order_by_desc_ref(|event| (event.event_time, &event.stream_name))
+        self.events.iter().rev()
     }
 }
 
+fn time_order(event: &CwlEvent) -> (DateTime<Utc>, &str, &str) {
+    (
+        event.event_time,
+        event.stream_name.as_str(),
+        event.event_id.as_str(),
+    )
+}
+
 impl Default for CwlEventStore {
     fn default() -> Self {
-        CwlEventStore::new(Store::default())
+        CwlEventStore::new(std::iter::empty())
+    }
+}
+
+impl Length for CwlEventStore {
+    fn len(&self) -> usize {
+        self.events.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::aws::cwlogs::event::LogLevel;
+
+    use super::*;
+
+    fn ids(store: &CwlEventStore) -> Vec<&str> {
+        store.order_by_asc().map(|e| e.event_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_insert_in_time_order() {
+        let mut store = CwlEventStore::default();
+        store.extend(
+            vec![
+                CwlEvent::for_test(3000).id("c"),
+                CwlEvent::for_test(1000).id("a").stream("web"),
+                CwlEvent::for_test(1000).id("b").stream("api"),
+            ]
+            .into_iter(),
+        );
+        store.extend(
+            vec![
+                CwlEvent::for_test(2000).id("d"),
+                CwlEvent::for_test(1000).id("a").stream("web"),
+            ]
+            .into_iter(),
+        );
+        assert_eq!(vec!["b", "a", "d", "c"], ids(&store));
+        assert_eq!(4, store.len());
+        assert_eq!(
+            vec!["c", "d", "a", "b"],
+            store
+                .order_by_desc()
+                .map(|e| e.event_id.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json = CwlEvent::for_test(1000).message(r#"INFO {"id": 1}"#);
+        let plain = CwlEvent::for_test(2000).message("plain text");
+        let store = CwlEventStore::new(vec![json.clone(), plain.clone()].into_iter());
+        assert_eq!(Some(&serde_json::json!({"id": 1})), store.json(&json));
+        assert_eq!(None, store.json(&plain));
+    }
+
+    #[test]
+    fn test_extend_detected() {
+        let json = CwlEvent::for_test(1000).message(r#"{"level": "warn"}"#);
+        let plain = CwlEvent::for_test(2000).message("ERROR: failed");
+        let mut store = CwlEventStore::default();
+        store.extend_detected(vec![json, plain].into_iter(), &LevelDetector::default());
+        assert_eq!(
+            vec![Some(LogLevel::Warn), Some(LogLevel::Error)],
+            store.order_by_asc().map(|e| e.level).collect::<Vec<_>>()
+        );
     }
 }
@@ -1,12 +1,57 @@
+mod analytics;
+mod bulk;
+mod cache;
 mod cursor;
+mod detail;
 mod model;
+mod ops;
+mod retention;
 mod store;
+mod tree;
+
+pub use analytics::monthly_cost;
+pub use analytics::name_prefix;
+pub use analytics::CwlGroupAnalytics;
+pub use analytics::PrefixUsage;
+pub use analytics::StorageCostConfig;
+
+pub use bulk::parse_tag_keys;
+pub use bulk::parse_tags;
+pub use bulk::run_bulk;
+pub use bulk::CwlGroupBulkOp;
+pub use bulk::CwlGroupBulkOutcome;
+pub use bulk::CwlGroupBulkResult;
+pub use bulk::ParseTagsError;
+
+pub use cache::CwlGroupCache;
+pub use cache::CwlGroupCacheState;
+pub use cache::CwlGroupSource;
 
 pub use cursor::CwlGroupCursor;
 pub use cursor::CwlGroupCursorError;
 pub use cursor::RusotoCwlGroupCursor;
 
+pub use detail::CwlGroupDetail;
+pub use detail::CwlGroupDetailError;
+pub use detail::CwlGroupDetailLoader;
+pub use detail::CwlMetricFilter;
+pub use detail::CwlMetricTransformation;
+pub use detail::CwlSubscriptionFilter;
+pub use detail::RusotoCwlGroupDetailLoader;
+
 pub use model::CwlGroup;
 pub use model::ParseLogGroupError;
 
+pub use ops::CwlGroupOps;
+pub use ops::CwlGroupOpsError;
+pub use ops::RusotoCwlGroupOps;
+
+pub use retention::CwlRetention;
+
 pub use store::CwlGroupStore;
+pub use store::GroupSort;
+pub use store::GroupSortColumn;
+
+pub use tree::CwlGroupTree;
+pub use tree::CwlGroupTreeNode;
+pub use tree::CwlGroupTreeRow;
@@ -0,0 +1,169 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::aws::cwlogs::group::model::CwlGroup;
+use crate::size::Size;
+
+const BYTES_IN_GIB: f64 = (1u64 << 30) as f64;
+
+/// Settings of the storage cost estimation.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct StorageCostConfig {
+    /// Price of storing 1 GiB for a month, in USD.
+    pub price_per_gib: f64,
+    pub top_groups: usize,
+    /// Number of `/` separated segments of group names to total by.
+    pub prefix_depth: usize,
+}
+
+impl Default for StorageCostConfig {
+    fn default() -> Self {
+        StorageCostConfig {
+            price_per_gib: 0.03,
+            top_groups: 20,
+            prefix_depth: 2,
+        }
+    }
+}
+
+/// Estimated monthly cost of storing the size, ignoring compression and free tier.
+pub fn monthly_cost(size: Size, price_per_gib: f64) -> f64 {
+    size.in_bytes() as f64 / BYTES_IN_GIB * price_per_gib
+}
+
+/// Returns the first `depth` segments of the group name, e.g. `/aws/lambda` of
+/// `/aws/lambda/foo` for depth 2.
+pub fn name_prefix(name: &str, depth: usize) -> &str {
+    let start = if name.starts_with('/') { 1 } else { 0 };
+    name[start..]
+        .match_indices('/')
+        .nth(depth.max(1) - 1)
+        .map(|(i, _)| &name[..start + i])
+        .unwrap_or(name)
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PrefixUsage {
+    pub prefix: String,
+    pub group_count: usize,
+    pub stored: Size,
+    pub no_retention_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CwlGroupAnalytics<'a> {
+    pub total: Size,
+    pub group_count: usize,
+    pub no_retention_count: usize,
+    /// Usages per name prefix, from the largest one.
+    pub prefixes: Vec<PrefixUsage>,
+    /// The largest groups.
+    pub top: Vec<&'a CwlGroup>,
+}
+
+impl<'a> CwlGroupAnalytics<'a> {
+    pub fn new(
+        groups: impl IntoIterator<Item = &'a CwlGroup>,
+        prefix_depth: usize,
+        top_groups: usize,
+    ) -> Self {
+        let mut groups = groups.into_iter().collect::<Vec<_>>();
+        groups.sort_by(|a, b| b.stored.cmp(&a.stored).then(a.cmp(b)));
+
+        let mut prefixes = BTreeMap::new();
+        for group in groups.iter() {
+            let prefix = name_prefix(&group.group_name, prefix_depth);
+            let usage = prefixes.entry(prefix).or_insert_with(|| PrefixUsage {
+                prefix: prefix.to_string(),
+                group_count: 0,
+                stored: Size::new(0),
+                no_retention_count: 0,
+            });
+            usage.group_count += 1;
+            usage.stored = Size::new(usage.stored.in_bytes() + group.stored.in_bytes());
+            if group.retention.is_none() {
+                usage.no_retention_count += 1;
+            }
+        }
+        let mut prefixes = prefixes.into_values().collect::<Vec<_>>();
+        prefixes.sort_by_key(|u| Reverse(u.stored));
+
+        CwlGroupAnalytics {
+            total: groups.iter().map(|g| g.stored).sum(),
+            group_count: groups.len(),
+            no_retention_count: groups.iter().filter(|g| g.retention.is_none()).count(),
+            prefixes,
+            top: groups.into_iter().take(top_groups).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name: &str, stored: u128, retention: bool) -> CwlGroup {
+        let group = CwlGroup::for_test(name).stored(stored);
+        if retention {
+            group.retention_days(1)
+        } else {
+            group
+        }
+    }
+
+    #[test]
+    fn test_name_prefix() {
+        assert_eq!("/aws/lambda", name_prefix("/aws/lambda/foo", 2));
+        assert_eq!("/aws", name_prefix("/aws/lambda/foo", 1));
+        assert_eq!("/aws/lambda/foo", name_prefix("/aws/lambda/foo", 3));
+        assert_eq!("/aws/lambda/foo", name_prefix("/aws/lambda/foo", 5));
+        assert_eq!("app", name_prefix("app/web", 1));
+        assert_eq!("app", name_prefix("app", 2));
+        assert_eq!("/aws", name_prefix("/aws/lambda", 0));
+    }
+
+    #[test]
+    fn test_monthly_cost() {
+        assert_eq!(0.03, monthly_cost(Size::new(1 << 30), 0.03));
+        assert_eq!(1.5, monthly_cost(Size::new(50 << 30), 0.03));
+    }
+
+    #[test]
+    fn test_analytics() {
+        let groups = [
+            group("/aws/lambda/a", 100, true),
+            group("/aws/lambda/b", 300, false),
+            group("/ecs/web", 250, false),
+            group("app", 10, true),
+        ];
+        let analytics = CwlGroupAnalytics::new(groups.iter(), 2, 2);
+
+        assert_eq!(Size::new(660), analytics.total);
+        assert_eq!(4, analytics.group_count);
+        assert_eq!(2, analytics.no_retention_count);
+        assert_eq!(vec![&groups[1], &groups[2]], analytics.top);
+        assert_eq!(
+            vec![
+                PrefixUsage {
+                    prefix: "/aws/lambda".to_string(),
+                    group_count: 2,
+                    stored: Size::new(400),
+                    no_retention_count: 1,
+                },
+                PrefixUsage {
+                    prefix: "/ecs/web".to_string(),
+                    group_count: 1,
+                    stored: Size::new(250),
+                    no_retention_count: 1,
+                },
+                PrefixUsage {
+                    prefix: "app".to_string(),
+                    group_count: 1,
+                    stored: Size::new(10),
+                    no_retention_count: 0,
+                },
+            ],
+            analytics.prefixes
+        );
+    }
+}
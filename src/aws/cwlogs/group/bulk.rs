@@ -0,0 +1,271 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use futures::stream::{self, StreamExt};
+use thiserror::Error;
+
+use crate::aws::cwlogs::group::model::CwlGroup;
+use crate::aws::cwlogs::group::ops::{CwlGroupOps, CwlGroupOpsError};
+use crate::aws::cwlogs::group::retention::CwlRetention;
+
+/// An operation applied to every marked group.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CwlGroupBulkOp {
+    SetRetention(CwlRetention),
+    AddTags(BTreeMap<String, String>),
+    RemoveTags(Vec<String>),
+    Delete,
+}
+
+impl CwlGroupBulkOp {
+    async fn apply<O: CwlGroupOps + Sync>(
+        &self,
+        ops: &O,
+        group: &mut CwlGroup,
+    ) -> Result<(), CwlGroupOpsError> {
+        let name = group.group_name.as_str();
+        match self {
+            CwlGroupBulkOp::SetRetention(retention) => {
+                ops.set_retention(name, *retention).await?;
+                group.retention = retention.as_duration();
+            }
+            CwlGroupBulkOp::AddTags(tags) => ops.tag_group(name, tags.clone()).await?,
+            CwlGroupBulkOp::RemoveTags(keys) => ops.untag_group(name, keys.clone()).await?,
+            CwlGroupBulkOp::Delete => ops.delete_group(name).await?,
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CwlGroupBulkOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CwlGroupBulkOp::SetRetention(retention) => write!(f, "set retention to {}", retention),
+            CwlGroupBulkOp::AddTags(tags) => write!(f, "add {} tags", tags.len()),
+            CwlGroupBulkOp::RemoveTags(keys) => write!(f, "remove {} tags", keys.len()),
+            CwlGroupBulkOp::Delete => write!(f, "delete"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CwlGroupBulkOutcome {
+    /// The group after the operation.
+    pub group: CwlGroup,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CwlGroupBulkResult {
+    pub op: CwlGroupBulkOp,
+    pub outcomes: Vec<CwlGroupBulkOutcome>,
+}
+
+impl CwlGroupBulkResult {
+    pub fn succeeded(&self) -> impl Iterator<Item = &CwlGroup> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.error.is_none())
+            .map(|o| &o.group)
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = (&CwlGroup, &str)> {
+        self.outcomes
+            .iter()
+            .filter_map(|o| o.error.as_deref().map(|e| (&o.group, e)))
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {} succeeded, {} failed",
+            self.op,
+            self.succeeded().count(),
+            self.failed().count()
+        )
+    }
+}
+
+/// Applies the operation to the groups, running at most `concurrency` requests at once.
+pub async fn run_bulk<O: CwlGroupOps + Sync>(
+    ops: &O,
+    groups: Vec<CwlGroup>,
+    op: CwlGroupBulkOp,
+    concurrency: usize,
+) -> CwlGroupBulkResult {
+    let outcomes = stream::iter(groups)
+        .map(|mut group| {
+            let op = &op;
+            async move {
+                let error = op.apply(ops, &mut group).await.err().map(|e| e.to_string());
+                CwlGroupBulkOutcome { group, error }
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    CwlGroupBulkResult { op, outcomes }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseTagsError {
+    #[error("no tags given")]
+    Empty,
+
+    #[error("tag `{0}` must be in the form key=value")]
+    MissingValue(String),
+
+    #[error("tag key must not be empty")]
+    EmptyKey,
+}
+
+/// Parses comma separated `key=value` pairs.
+pub fn parse_tags(s: &str) -> Result<BTreeMap<String, String>, ParseTagsError> {
+    let mut tags = BTreeMap::new();
+    for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let mut it = pair.splitn(2, '=');
+        let key = it.next().unwrap_or("").trim();
+        let value = it
+            .next()
+            .ok_or_else(|| ParseTagsError::MissingValue(pair.to_string()))?;
+        if key.is_empty() {
+            return Err(ParseTagsError::EmptyKey);
+        }
+        tags.insert(key.to_string(), value.trim().to_string());
+    }
+
+    if tags.is_empty() {
+        Err(ParseTagsError::Empty)
+    } else {
+        Ok(tags)
+    }
+}
+
+/// Parses comma separated tag keys.
+pub fn parse_tag_keys(s: &str) -> Result<Vec<String>, ParseTagsError> {
+    let keys = s
+        .split(',')
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    if keys.is_empty() {
+        Err(ParseTagsError::Empty)
+    } else {
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use rusoto_core::RusotoError;
+
+    use super::*;
+
+    /// Fails for the group named `fail`, recording the peak number of running requests.
+    #[derive(Default)]
+    struct FakeOps {
+        running: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl FakeOps {
+        async fn call(&self, group_name: &str) -> Result<(), CwlGroupOpsError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::delay_for(Duration::from_millis(5)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            if group_name == "fail" {
+                Err(CwlGroupOpsError::DeleteGroup {
+                    source: RusotoError::Validation("failed".to_string()),
+                })
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CwlGroupOps for FakeOps {
+        async fn set_retention(&self, name: &str, _: CwlRetention) -> Result<(), CwlGroupOpsError> {
+            self.call(name).await
+        }
+
+        async fn delete_group(&self, name: &str) -> Result<(), CwlGroupOpsError> {
+            self.call(name).await
+        }
+
+        async fn delete_stream(&self, name: &str, _: &str) -> Result<(), CwlGroupOpsError> {
+            self.call(name).await
+        }
+
+        async fn tag_group(
+            &self,
+            name: &str,
+            _: BTreeMap<String, String>,
+        ) -> Result<(), CwlGroupOpsError> {
+            self.call(name).await
+        }
+
+        async fn untag_group(&self, name: &str, _: Vec<String>) -> Result<(), CwlGroupOpsError> {
+            self.call(name).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_bulk() {
+        let ops = FakeOps::default();
+        let groups = vec![
+            CwlGroup::for_test("a"),
+            CwlGroup::for_test("fail"),
+            CwlGroup::for_test("b"),
+            CwlGroup::for_test("c"),
+            CwlGroup::for_test("d"),
+        ];
+        let op = CwlGroupBulkOp::SetRetention(CwlRetention::Days(7));
+
+        let result = run_bulk(&ops, groups, op, 2).await;
+
+        assert_eq!(2, ops.peak.load(Ordering::SeqCst));
+        let succeeded = result
+            .succeeded()
+            .map(|g| g.group_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["a", "b", "c", "d"], succeeded);
+        assert!(result
+            .succeeded()
+            .all(|g| g.retention == CwlRetention::Days(7).as_duration()));
+
+        let failed = result.failed().map(|(g, _)| g).collect::<Vec<_>>();
+        assert_eq!(vec![&CwlGroup::for_test("fail")], failed);
+        assert_eq!(
+            "set retention to 7 days: 4 succeeded, 1 failed",
+            result.summary()
+        );
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let s = |s: &str| s.to_string();
+        let mut expected = BTreeMap::new();
+        expected.insert(s("env"), s("prd"));
+        expected.insert(s("team"), s("a=b"));
+        assert_eq!(Ok(expected), parse_tags(" env = prd, team=a=b,"));
+
+        assert_eq!(Err(ParseTagsError::Empty), parse_tags(" , "));
+        assert_eq!(
+            Err(ParseTagsError::MissingValue(s("env"))),
+            parse_tags("env")
+        );
+        assert_eq!(Err(ParseTagsError::EmptyKey), parse_tags("=prd"));
+
+        assert_eq!(Ok(vec![s("env"), s("team")]), parse_tag_keys("env, team"));
+        assert_eq!(Err(ParseTagsError::Empty), parse_tag_keys(""));
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use rusoto_core::Region;
+use rusoto_logs::DescribeLogGroupsRequest;
+
+use crate::aws::cwlogs::group::model::CwlGroup;
+use crate::aws::profile::ProfileName;
+
+const MAX_GROUPS_PER_PAGE: i64 = 50;
+
+/// Where a list of log groups comes from.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CwlGroupSource {
+    pub profile: ProfileName,
+    pub region: Region,
+    /// Server-side name prefix. `None` lists every group in the account.
+    pub prefix: Option<String>,
+}
+
+impl CwlGroupSource {
+    pub fn new(profile: ProfileName, region: Region) -> CwlGroupSource {
+        CwlGroupSource {
+            profile,
+            region,
+            prefix: None,
+        }
+    }
+
+    pub fn with_prefix(&self, prefix: String) -> CwlGroupSource {
+        CwlGroupSource {
+            prefix: Some(prefix),
+            ..self.clone()
+        }
+    }
+
+    pub fn without_prefix(&self) -> CwlGroupSource {
+        CwlGroupSource {
+            prefix: None,
+            ..self.clone()
+        }
+    }
+}
+
+impl From<&CwlGroupSource> for DescribeLogGroupsRequest {
+    fn from(source: &CwlGroupSource) -> Self {
+        DescribeLogGroupsRequest {
+            log_group_name_prefix: source.prefix.clone(),
+            limit: Some(MAX_GROUPS_PER_PAGE),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CwlGroupCacheState {
+    Loading,
+    Complete,
+    /// Stopped paging because the source has too many groups.
+    Truncated,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    groups: Vec<CwlGroup>,
+    state: CwlGroupCacheState,
+    /// Listing the pages belong to, so pages of a listing started before a refresh are dropped.
+    generation: u64,
+}
+
+/// Caches every fetched page of log groups per source, so switching between profiles or
+/// searching does not hit DescribeLogGroups again.
+#[derive(Debug, Default)]
+pub struct CwlGroupCache {
+    entries: HashMap<CwlGroupSource, CacheEntry>,
+    generation: u64,
+}
+
+impl CwlGroupCache {
+    pub fn state(&self, source: &CwlGroupSource) -> Option<CwlGroupCacheState> {
+        self.entries.get(source).map(|e| e.state)
+    }
+
+    pub fn groups(&self, source: &CwlGroupSource) -> &[CwlGroup] {
+        self.entries
+            .get(source)
+            .map(|e| e.groups.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Starts listing the source again. Returns the generation its pages must carry.
+    pub fn start_loading(&mut self, source: CwlGroupSource) -> u64 {
+        self.generation += 1;
+        self.entries.insert(
+            source,
+            CacheEntry {
+                groups: Vec::new(),
+                state: CwlGroupCacheState::Loading,
+                generation: self.generation,
+            },
+        );
+        self.generation
+    }
+
+    /// Returns whether the generation is the listing the source is loading.
+    pub fn is_loading(&self, source: &CwlGroupSource, generation: u64) -> bool {
+        matches!(self.entries.get(source), Some(e)
+            if e.state == CwlGroupCacheState::Loading && e.generation == generation)
+    }
+
+    /// Appends a page of groups, and returns whether it was appended. Pages for sources that are
+    /// not loading, or of an older listing of the source, are ignored.
+    pub fn append(
+        &mut self,
+        source: &CwlGroupSource,
+        generation: u64,
+        groups: Vec<CwlGroup>,
+        state: CwlGroupCacheState,
+    ) -> bool {
+        if !self.is_loading(source, generation) {
+            return false;
+        }
+        if let Some(entry) = self.entries.get_mut(source) {
+            entry.groups.extend(groups);
+            entry.state = state;
+        }
+        true
+    }
+
+    /// Replaces the group having the same ARN in every source.
+    pub fn replace(&mut self, group: &CwlGroup) {
+        for entry in self.entries.values_mut() {
+            for g in entry.groups.iter_mut().filter(|g| g.arn == group.arn) {
+                *g = group.clone();
+            }
+        }
+    }
+
+    /// Removes the group having the same ARN from every source.
+    pub fn remove(&mut self, group: &CwlGroup) {
+        for entry in self.entries.values_mut() {
+            entry.groups.retain(|g| g.arn != group.arn);
+        }
+    }
+
+    pub fn invalidate(&mut self, source: &CwlGroupSource) {
+        self.entries.remove(source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source() -> CwlGroupSource {
+        CwlGroupSource::new(ProfileName::from("default"), Region::ApNortheast1)
+    }
+
+    #[test]
+    fn test_append() {
+        use CwlGroupCacheState::*;
+
+        let mut cache = CwlGroupCache::default();
+        assert_eq!(None, cache.state(&source()));
+
+        let generation = cache.start_loading(source());
+        cache.append(
+            &source(),
+            generation,
+            vec![CwlGroup::for_test("a")],
+            Loading,
+        );
+        assert_eq!(Some(Loading), cache.state(&source()));
+
+        cache.append(
+            &source(),
+            generation,
+            vec![CwlGroup::for_test("b")],
+            Complete,
+        );
+        assert_eq!(Some(Complete), cache.state(&source()));
+        assert_eq!(
+            &[CwlGroup::for_test("a"), CwlGroup::for_test("b")],
+            cache.groups(&source())
+        );
+
+        // pages after completion are ignored.
+        assert!(!cache.append(
+            &source(),
+            generation,
+            vec![CwlGroup::for_test("c")],
+            Complete
+        ));
+        assert_eq!(2, cache.groups(&source()).len());
+
+        // sources are distinguished by their prefix.
+        let prefixed = source().with_prefix("/aws".to_string());
+        cache.append(
+            &prefixed,
+            generation,
+            vec![CwlGroup::for_test("/aws/d")],
+            Complete,
+        );
+        assert_eq!(None, cache.state(&prefixed));
+        assert_eq!(source(), prefixed.without_prefix());
+
+        let mut updated = CwlGroup::for_test("b");
+        updated.retention = None;
+        cache.replace(&updated);
+        assert_eq!(
+            &[CwlGroup::for_test("a"), updated.clone()],
+            cache.groups(&source())
+        );
+
+        cache.remove(&CwlGroup::for_test("a"));
+        assert_eq!(&[updated], cache.groups(&source()));
+
+        cache.invalidate(&source());
+        assert_eq!(None, cache.state(&source()));
+        assert!(cache.groups(&source()).is_empty());
+    }
+
+    #[test]
+    fn test_append_after_refresh() {
+        use CwlGroupCacheState::*;
+
+        let mut cache = CwlGroupCache::default();
+        let old = cache.start_loading(source());
+        cache.append(&source(), old, vec![CwlGroup::for_test("a")], Loading);
+
+        // a refresh starts another listing while pages of the old one are still coming.
+        cache.invalidate(&source());
+        let new = cache.start_loading(source());
+        assert!(cache.append(&source(), new, vec![CwlGroup::for_test("a")], Loading));
+        assert!(!cache.append(&source(), old, vec![CwlGroup::for_test("b")], Complete));
+        assert!(!cache.is_loading(&source(), old));
+        assert_eq!(Some(Loading), cache.state(&source()));
+
+        assert!(cache.append(&source(), new, vec![CwlGroup::for_test("b")], Complete));
+        assert_eq!(Some(Complete), cache.state(&source()));
+        assert_eq!(
+            &[CwlGroup::for_test("a"), CwlGroup::for_test("b")],
+            cache.groups(&source())
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use rusoto_core::RusotoError;
+use rusoto_logs::{
+    CloudWatchLogs, CloudWatchLogsClient, DescribeMetricFiltersError, DescribeMetricFiltersRequest,
+    DescribeSubscriptionFiltersError, DescribeSubscriptionFiltersRequest, ListTagsLogGroupError,
+    ListTagsLogGroupRequest, MetricFilter, SubscriptionFilter,
+};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CwlMetricTransformation {
+    pub namespace: String,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CwlMetricFilter {
+    pub name: String,
+    pub pattern: String,
+    pub transformations: Vec<CwlMetricTransformation>,
+}
+
+impl From<MetricFilter> for CwlMetricFilter {
+    fn from(filter: MetricFilter) -> Self {
+        CwlMetricFilter {
+            name: filter.filter_name.unwrap_or_default(),
+            pattern: filter.filter_pattern.unwrap_or_default(),
+            transformations: filter
+                .metric_transformations
+                .unwrap_or_default()
+                .into_iter()
+                .map(|t| CwlMetricTransformation {
+                    namespace: t.metric_namespace,
+                    name: t.metric_name,
+                    value: t.metric_value,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CwlSubscriptionFilter {
+    pub name: String,
+    pub pattern: String,
+    pub destination_arn: String,
+}
+
+impl From<SubscriptionFilter> for CwlSubscriptionFilter {
+    fn from(filter: SubscriptionFilter) -> Self {
+        CwlSubscriptionFilter {
+            name: filter.filter_name.unwrap_or_default(),
+            pattern: filter.filter_pattern.unwrap_or_default(),
+            destination_arn: filter.destination_arn.unwrap_or_default(),
+        }
+    }
+}
+
+/// Settings of a log group which are not included in DescribeLogGroups.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct CwlGroupDetail {
+    pub tags: BTreeMap<String, String>,
+    pub metric_filters: Vec<CwlMetricFilter>,
+    pub subscription_filters: Vec<CwlSubscriptionFilter>,
+}
+
+#[derive(Debug, Error)]
+pub enum CwlGroupDetailError {
+    #[error("could not list tags. cause:{source}")]
+    Tags {
+        #[from]
+        source: RusotoError<ListTagsLogGroupError>,
+    },
+
+    #[error("could not describe metric filters. cause:{source}")]
+    MetricFilters {
+        #[from]
+        source: RusotoError<DescribeMetricFiltersError>,
+    },
+
+    #[error("could not describe subscription filters. cause:{source}")]
+    SubscriptionFilters {
+        #[from]
+        source: RusotoError<DescribeSubscriptionFiltersError>,
+    },
+}
+
+#[async_trait]
+pub trait CwlGroupDetailLoader {
+    async fn load(&self, group_name: &str) -> Result<CwlGroupDetail, CwlGroupDetailError>;
+}
+
+#[derive(Clone)]
+pub struct RusotoCwlGroupDetailLoader {
+    client: CloudWatchLogsClient,
+}
+
+impl RusotoCwlGroupDetailLoader {
+    pub fn new(client: CloudWatchLogsClient) -> RusotoCwlGroupDetailLoader {
+        RusotoCwlGroupDetailLoader { client }
+    }
+
+    async fn tags(
+        &self,
+        group_name: &str,
+    ) -> Result<BTreeMap<String, String>, CwlGroupDetailError> {
+        let req = ListTagsLogGroupRequest {
+            log_group_name: group_name.to_string(),
+        };
+        let res = self.client.list_tags_log_group(req).await?;
+        Ok(res.tags.unwrap_or_default().into_iter().collect())
+    }
+
+    async fn metric_filters(
+        &self,
+        group_name: &str,
+    ) -> Result<Vec<CwlMetricFilter>, CwlGroupDetailError> {
+        let mut filters = Vec::new();
+        let mut req = DescribeMetricFiltersRequest {
+            log_group_name: Some(group_name.to_string()),
+            ..Default::default()
+        };
+        loop {
+            let res = self.client.describe_metric_filters(req.clone()).await?;
+            filters.extend(
+                res.metric_filters
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(CwlMetricFilter::from),
+            );
+            match res.next_token {
+                Some(token) => req.next_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(filters)
+    }
+
+    async fn subscription_filters(
+        &self,
+        group_name: &str,
+    ) -> Result<Vec<CwlSubscriptionFilter>, CwlGroupDetailError> {
+        let mut filters = Vec::new();
+        let mut req = DescribeSubscriptionFiltersRequest {
+            log_group_name: group_name.to_string(),
+            ..Default::default()
+        };
+        loop {
+            let res = self
+                .client
+                .describe_subscription_filters(req.clone())
+                .await?;
+            filters.extend(
+                res.subscription_filters
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(CwlSubscriptionFilter::from),
+            );
+            match res.next_token {
+                Some(token) => req.next_token = Some(token),
+                None => break,
+            }
+        }
+        Ok(filters)
+    }
+}
+
+#[async_trait]
+impl CwlGroupDetailLoader for RusotoCwlGroupDetailLoader {
+    async fn load(&self, group_name: &str) -> Result<CwlGroupDetail, CwlGroupDetailError> {
+        let (tags, metric_filters, subscription_filters) = futures::try_join!(
+            self.tags(group_name),
+            self.metric_filters(group_name),
+            self.subscription_filters(group_name),
+        )?;
+
+        Ok(CwlGroupDetail {
+            tags,
+            metric_filters,
+            subscription_filters,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusoto_logs::MetricTransformation;
+
+    use super::*;
+
+    #[test]
+    fn test_metric_filter_from() {
+        let filter = MetricFilter {
+            filter_name: Some("errors".to_string()),
+            filter_pattern: Some("ERROR".to_string()),
+            metric_transformations: Some(vec![MetricTransformation {
+                metric_namespace: "App".to_string(),
+                metric_name: "Errors".to_string(),
+                metric_value: "1".to_string(),
+                default_value: None,
+            }]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            CwlMetricFilter {
+                name: "errors".to_string(),
+                pattern: "ERROR".to_string(),
+                transformations: vec![CwlMetricTransformation {
+                    namespace: "App".to_string(),
+                    name: "Errors".to_string(),
+                    value: "1".to_string(),
+                }],
+            },
+            CwlMetricFilter::from(filter)
+        );
+    }
+}
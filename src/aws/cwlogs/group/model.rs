@@ -2,7 +2,7 @@ use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::time::Duration;
 
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Utc};
 use rusoto_logs::LogGroup;
 use thiserror::Error;
 
@@ -34,12 +34,8 @@ pub struct CwlGroup {
     pub group_name: String,
     pub retention: Option<Duration>,
     pub stored: Size,
-}
-
-impl CwlGroup {
-    pub fn creation_time_local(&self) -> DateTime<Local> {
-        self.creation_time.with_timezone(&Local)
-    }
+    pub metric_filter_count: usize,
+    pub kms_key_id: Option<String>,
 }
 
 impl PartialOrd for CwlGroup {
@@ -65,6 +61,7 @@ impl TryFrom<LogGroup> for CwlGroup {
             .retention_in_days
             .map(|days| DurationDays::from(days).into());
         let stored = Size::try_from(map_field(group.stored_bytes, "stored_bytes")?)?;
+        let metric_filter_count = group.metric_filter_count.unwrap_or(0) as usize;
 
         Ok(CwlGroup {
             arn,
@@ -72,10 +69,54 @@ impl TryFrom<LogGroup> for CwlGroup {
             group_name,
             retention,
             stored,
+            metric_filter_count,
+            kms_key_id: group.kms_key_id,
         })
     }
 }
 
+#[cfg(test)]
+impl CwlGroup {
+    /// A group of the name created at 2020-11-02T11:22:33Z, with no retention and nothing stored.
+    pub fn for_test(name: &str) -> CwlGroup {
+        use chrono::TimeZone;
+
+        CwlGroup {
+            arn: Arn::from(format!(
+                "arn:aws:logs:ap-northeast-1:000000000000:log-group:{}:*",
+                name
+            )),
+            creation_time: Utc.ymd(2020, 11, 2).and_hms(11, 22, 33),
+            group_name: name.to_string(),
+            retention: None,
+            stored: Size::new(0),
+            metric_filter_count: 0,
+            kms_key_id: None,
+        }
+    }
+
+    pub fn created_at(self, creation_time: DateTime<Utc>) -> CwlGroup {
+        CwlGroup {
+            creation_time,
+            ..self
+        }
+    }
+
+    pub fn retention_days(self, days: u64) -> CwlGroup {
+        CwlGroup {
+            retention: Some(Duration::from_secs(days * 24 * 60 * 60)),
+            ..self
+        }
+    }
+
+    pub fn stored(self, bytes: u128) -> CwlGroup {
+        CwlGroup {
+            stored: Size::new(bytes),
+            ..self
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,6 +132,7 @@ mod tests {
             creation_time: Some(1604316153000), // 2020-11-02T11:22:33
             retention_in_days: Some(3),
             stored_bytes: Some(256 * 1024),
+            metric_filter_count: Some(2),
             ..Default::default()
         }
     }
@@ -104,6 +146,8 @@ mod tests {
                 group_name: String::from("log_group_name"),
                 retention: Some(Duration::from_secs(3 * 24 * 60 * 60)),
                 stored: Size::new(256 * 1024),
+                metric_filter_count: 2,
+                kms_key_id: None,
             }),
             CwlGroup::try_from(log_group())
         );
@@ -0,0 +1,167 @@
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use rusoto_core::RusotoError;
+use rusoto_logs::{
+    CloudWatchLogs, CloudWatchLogsClient, DeleteLogGroupError, DeleteLogGroupRequest,
+    DeleteLogStreamError, DeleteLogStreamRequest, DeleteRetentionPolicyError,
+    DeleteRetentionPolicyRequest, PutRetentionPolicyError, PutRetentionPolicyRequest,
+    TagLogGroupError, TagLogGroupRequest, UntagLogGroupError, UntagLogGroupRequest,
+};
+use thiserror::Error;
+
+use crate::aws::cwlogs::group::retention::CwlRetention;
+
+#[derive(Debug, Error)]
+pub enum CwlGroupOpsError {
+    #[error("could not put retention policy. cause:{source}")]
+    PutRetention {
+        #[from]
+        source: RusotoError<PutRetentionPolicyError>,
+    },
+
+    #[error("could not delete retention policy. cause:{source}")]
+    DeleteRetention {
+        #[from]
+        source: RusotoError<DeleteRetentionPolicyError>,
+    },
+
+    #[error("could not delete log group. cause:{source}")]
+    DeleteGroup {
+        #[from]
+        source: RusotoError<DeleteLogGroupError>,
+    },
+
+    #[error("could not delete log stream. cause:{source}")]
+    DeleteStream {
+        #[from]
+        source: RusotoError<DeleteLogStreamError>,
+    },
+
+    #[error("could not tag log group. cause:{source}")]
+    Tag {
+        #[from]
+        source: RusotoError<TagLogGroupError>,
+    },
+
+    #[error("could not untag log group. cause:{source}")]
+    Untag {
+        #[from]
+        source: RusotoError<UntagLogGroupError>,
+    },
+}
+
+/// Operations changing log groups and their streams.
+#[async_trait]
+pub trait CwlGroupOps {
+    async fn set_retention(
+        &self,
+        group_name: &str,
+        retention: CwlRetention,
+    ) -> Result<(), CwlGroupOpsError>;
+
+    /// Deletes the group with all of its streams and events.
+    async fn delete_group(&self, group_name: &str) -> Result<(), CwlGroupOpsError>;
+
+    async fn delete_stream(
+        &self,
+        group_name: &str,
+        stream_name: &str,
+    ) -> Result<(), CwlGroupOpsError>;
+
+    /// Adds the tags, overwriting the values of existing keys.
+    async fn tag_group(
+        &self,
+        group_name: &str,
+        tags: BTreeMap<String, String>,
+    ) -> Result<(), CwlGroupOpsError>;
+
+    async fn untag_group(
+        &self,
+        group_name: &str,
+        keys: Vec<String>,
+    ) -> Result<(), CwlGroupOpsError>;
+}
+
+#[derive(Clone)]
+pub struct RusotoCwlGroupOps {
+    client: CloudWatchLogsClient,
+}
+
+impl RusotoCwlGroupOps {
+    pub fn new(client: CloudWatchLogsClient) -> RusotoCwlGroupOps {
+        RusotoCwlGroupOps { client }
+    }
+}
+
+#[async_trait]
+impl CwlGroupOps for RusotoCwlGroupOps {
+    async fn set_retention(
+        &self,
+        group_name: &str,
+        retention: CwlRetention,
+    ) -> Result<(), CwlGroupOpsError> {
+        let log_group_name = group_name.to_string();
+        match retention {
+            CwlRetention::Days(retention_in_days) => {
+                let req = PutRetentionPolicyRequest {
+                    log_group_name,
+                    retention_in_days,
+                };
+                self.client.put_retention_policy(req).await?;
+            }
+            CwlRetention::Never => {
+                let req = DeleteRetentionPolicyRequest { log_group_name };
+                self.client.delete_retention_policy(req).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete_group(&self, group_name: &str) -> Result<(), CwlGroupOpsError> {
+        let req = DeleteLogGroupRequest {
+            log_group_name: group_name.to_string(),
+        };
+        self.client.delete_log_group(req).await?;
+        Ok(())
+    }
+
+    async fn delete_stream(
+        &self,
+        group_name: &str,
+        stream_name: &str,
+    ) -> Result<(), CwlGroupOpsError> {
+        let req = DeleteLogStreamRequest {
+            log_group_name: group_name.to_string(),
+            log_stream_name: stream_name.to_string(),
+        };
+        self.client.delete_log_stream(req).await?;
+        Ok(())
+    }
+    async fn tag_group(
+        &self,
+        group_name: &str,
+        tags: BTreeMap<String, String>,
+    ) -> Result<(), CwlGroupOpsError> {
+        let req = TagLogGroupRequest {
+            log_group_name: group_name.to_string(),
+            tags: tags.into_iter().collect(),
+        };
+        self.client.tag_log_group(req).await?;
+        Ok(())
+    }
+
+    async fn untag_group(
+        &self,
+        group_name: &str,
+        keys: Vec<String>,
+    ) -> Result<(), CwlGroupOpsError> {
+        let req = UntagLogGroupRequest {
+            log_group_name: group_name.to_string(),
+            tags: keys,
+        };
+        self.client.untag_log_group(req).await?;
+        Ok(())
+    }
+}
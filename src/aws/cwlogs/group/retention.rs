@@ -0,0 +1,86 @@
+use std::fmt;
+use std::time::Duration;
+
+use crate::aws::cwlogs::mapper::DurationDays;
+
+/// Values accepted by PutRetentionPolicy.
+const RETENTION_DAYS: [i64; 22] = [
+    1, 3, 5, 7, 14, 30, 60, 90, 120, 150, 180, 365, 400, 545, 731, 1096, 1827, 2192, 2557, 2922,
+    3288, 3653,
+];
+
+const SECONDS_IN_DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CwlRetention {
+    Days(i64),
+    Never,
+}
+
+impl CwlRetention {
+    /// Returns every retention CloudWatch Logs accepts, from the shortest to never expire.
+    pub fn all() -> Vec<CwlRetention> {
+        RETENTION_DAYS
+            .iter()
+            .map(|&days| CwlRetention::Days(days))
+            .chain(std::iter::once(CwlRetention::Never))
+            .collect()
+    }
+
+    pub fn days(&self) -> Option<i64> {
+        match *self {
+            CwlRetention::Days(days) => Some(days),
+            CwlRetention::Never => None,
+        }
+    }
+
+    pub fn as_duration(&self) -> Option<Duration> {
+        self.days()
+            .map(|days| DurationDays::from(days).as_duration())
+    }
+}
+
+impl From<Option<Duration>> for CwlRetention {
+    fn from(retention: Option<Duration>) -> Self {
+        match retention {
+            Some(d) => CwlRetention::Days((d.as_secs() / SECONDS_IN_DAY) as i64),
+            None => CwlRetention::Never,
+        }
+    }
+}
+
+impl fmt::Display for CwlRetention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            CwlRetention::Days(1) => write!(f, "1 day"),
+            CwlRetention::Days(days) => write!(f, "{} days", days),
+            CwlRetention::Never => write!(f, "Never"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        let all = CwlRetention::all();
+        assert_eq!(23, all.len());
+        assert_eq!(CwlRetention::Days(1), all[0]);
+        assert_eq!(CwlRetention::Never, all[22]);
+    }
+
+    #[test]
+    fn test_duration() {
+        let thirty_days = Some(Duration::from_secs(30 * SECONDS_IN_DAY));
+        assert_eq!(CwlRetention::Days(30), CwlRetention::from(thirty_days));
+        assert_eq!(thirty_days, CwlRetention::Days(30).as_duration());
+        assert_eq!(CwlRetention::Never, CwlRetention::from(None));
+        assert_eq!(None, CwlRetention::Never.as_duration());
+
+        assert_eq!("1 day", CwlRetention::Days(1).to_string());
+        assert_eq!("30 days", CwlRetention::Days(30).to_string());
+        assert_eq!("Never", CwlRetention::Never.to_string());
+    }
+}
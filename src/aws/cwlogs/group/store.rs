@@ -1,59 +1,316 @@
+use std::cmp::Reverse;
+
+use chrono::Utc;
+
+use crate::aws::cwlogs::group::cache::CwlGroupCacheState;
 use crate::aws::cwlogs::group::model::CwlGroup;
-use crate::collection::store::Store;
-use crate::collection::Length;
+use crate::collection::{Length, SortOrder};
+use crate::matcher::{rank, Matched, MatchedIndex};
+use crate::query::group::GroupQuery;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GroupSortColumn {
+    /// Ranks groups by the score of the query, or by name if no query is set.
+    Relevance,
+    Name,
+    CreationTime,
+    Stored,
+}
+
+impl GroupSortColumn {
+    pub fn next(&self) -> GroupSortColumn {
+        use GroupSortColumn::*;
+        match *self {
+            Relevance => Name,
+            Name => CreationTime,
+            CreationTime => Stored,
+            Stored => Relevance,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct GroupSort {
+    pub column: GroupSortColumn,
+    pub order: SortOrder,
+}
+
+impl GroupSort {
+    pub fn new(column: GroupSortColumn, order: SortOrder) -> GroupSort {
+        GroupSort { column, order }
+    }
+}
+
+impl Default for GroupSort {
+    fn default() -> Self {
+        GroupSort::new(GroupSortColumn::Relevance, SortOrder::Asc)
+    }
+}
 
 #[derive(Debug)]
 pub struct CwlGroupStore {
-    groups: Store<CwlGroup>,
+    /// Groups ordered by name.
+    groups: Vec<CwlGroup>,
+    query: Option<GroupQuery>,
+    /// Groups matching the query, ranked by score.
+    matched: Vec<MatchedIndex>,
+    sort: GroupSort,
+    /// Positions in `matched` of the groups in the order of `sort`.
+    sorted: Vec<usize>,
+    state: Option<CwlGroupCacheState>,
+    label: String,
 }
 
 impl CwlGroupStore {
-    pub fn new(groups: Store<CwlGroup>) -> Self {
-        CwlGroupStore { groups }
+    pub fn new(groups: impl Iterator<Item = CwlGroup>) -> Self {
+        let mut store = CwlGroupStore {
+            groups: Vec::new(),
+            query: None,
+            matched: Vec::new(),
+            sort: GroupSort::default(),
+            sorted: Vec::new(),
+            state: None,
+            label: default_label().to_string(),
+        };
+        store.extend(groups);
+        store
     }
 
     pub fn clear(&mut self) {
         self.groups.clear();
+        self.matched.clear();
+        self.sorted.clear();
     }
 
     pub fn insert(&mut self, group: CwlGroup) {
-        self.groups.insert(group);
+        self.insert_sorted(group);
+        self.update_matched();
     }
 
     pub fn extend(&mut self, groups: impl Iterator<Item = CwlGroup>) {
-        self.groups.extend(groups);
+        groups.for_each(|group| self.insert_sorted(group));
+        self.update_matched();
     }
 
-    pub fn order_by_name_asc(&self) -> impl Iterator<Item = &CwlGroup> {
+    /// Removes the group having the same ARN.
+    pub fn remove(&mut self, group: &CwlGroup) {
+        if let Ok(i) = self.search(group) {
+            self.groups.remove(i);
+            self.update_matched();
+        }
+    }
+
+    /// Replaces the group having the same ARN.
+    pub fn replace(&mut self, group: CwlGroup) {
+        if let Ok(i) = self.search(&group) {
+            self.groups[i] = group;
+            self.update_matched();
+        }
+    }
+
+    /// Searches the group by name. The ARN is derived from the name, so equal names mean equal ARNs.
+    fn search(&self, group: &CwlGroup) -> Result<usize, usize> {
         self.groups
-            .order_by_asc_ref(|group| group.group_name.as_str())
+            .binary_search_by(|g| (&g.group_name, &g.arn).cmp(&(&group.group_name, &group.arn)))
+    }
+
+    fn insert_sorted(&mut self, group: CwlGroup) {
+        match self.search(&group) {
+            Ok(i) => self.groups[i] = group,
+            Err(i) => self.groups.insert(i, group),
+        }
+    }
+
+    /// Ranks the groups once, when the query or the groups change.
+    fn update_matched(&mut self) {
+        let matcher = self.query.as_ref().map(|q| q.matcher(Utc::now()));
+        self.matched = rank(self.groups.iter(), matcher.as_ref(), |group| group);
+        self.update_sorted();
+    }
+
+    /// Orders the matched groups once, when they or the sort change.
+    fn update_sorted(&mut self) {
+        use GroupSortColumn::*;
+        use SortOrder::*;
+
+        let matched = &self.matched;
+        let group = |i: &usize| &self.groups[matched[*i].index];
+        let mut sorted = (0..matched.len()).collect::<Vec<_>>();
+        match (self.sort.column, self.sort.order) {
+            (Relevance, Asc) => (),
+            (Relevance, Desc) => sorted.reverse(),
+            (Name, Asc) => sorted.sort_by_key(|i| matched[*i].index),
+            (Name, Desc) => sorted.sort_by_key(|i| Reverse(matched[*i].index)),
+            (CreationTime, Asc) => {
+                sorted.sort_by_key(|i| (group(i).creation_time, matched[*i].index))
+            }
+            (CreationTime, Desc) => {
+                sorted.sort_by_key(|i| Reverse((group(i).creation_time, matched[*i].index)))
+            }
+            (Stored, Asc) => sorted.sort_by_key(|i| (group(i).stored, matched[*i].index)),
+            (Stored, Desc) => sorted.sort_by_key(|i| Reverse((group(i).stored, matched[*i].index))),
+        }
+        self.sorted = sorted;
+    }
+
+    pub fn sort(&self) -> GroupSort {
+        self.sort
     }
 
-    pub fn order_by_creation_time_asc(&self) -> impl Iterator<Item = &CwlGroup> {
-        self.groups.order_by_asc(|group| group.creation_time)
+    pub fn set_sort(&mut self, sort: GroupSort) {
+        if self.sort != sort {
+            self.sort = sort;
+            self.update_sorted();
+        }
     }
 
-    pub fn order_by_creation_time_desc(&self) -> impl Iterator<Item = &CwlGroup> {
-        self.groups.order_by_desc(|group| group.creation_time)
+    pub fn set_query(&mut self, query: Option<GroupQuery>) {
+        self.query = query;
+        self.update_label();
+        self.update_matched();
     }
 
-    pub fn order_by_size_asc(&self) -> impl Iterator<Item = &CwlGroup> {
-        self.groups.order_by_asc(|group| group.stored)
+    pub fn query(&self) -> Option<&GroupQuery> {
+        self.query.as_ref()
     }
 
-    pub fn order_by_size_desc(&self) -> impl Iterator<Item = &CwlGroup> {
-        self.groups.order_by_desc(|group| group.stored)
+    pub fn set_state(&mut self, state: Option<CwlGroupCacheState>) {
+        self.state = state;
+        self.update_label();
+    }
+
+    fn update_label(&mut self) {
+        let mut label = default_label().to_string();
+        if let Some(q) = self.query.as_ref() {
+            label.push_str(&format!(" (\"{}\")", q.text()));
+        }
+        match self.state {
+            Some(CwlGroupCacheState::Loading) => label.push_str(" [loading]"),
+            Some(CwlGroupCacheState::Truncated) => label.push_str(" [truncated]"),
+            _ => (),
+        }
+        self.label = label;
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    /// Returns groups matching the query, ranked by score. Groups are ordered by name if no query is set.
+    pub fn matched(&self) -> Vec<Matched<'_, CwlGroup>> {
+        self.matched.iter().map(|m| m.of(&self.groups)).collect()
+    }
+
+    /// Returns groups matching the query in the order of the sort.
+    pub fn sorted(&self) -> Vec<Matched<'_, CwlGroup>> {
+        self.sorted
+            .iter()
+            .map(|i| self.matched[*i].of(&self.groups))
+            .collect()
+    }
+
+    /// The group at the position in the order of the sort.
+    pub fn get(&self, i: usize) -> Option<&CwlGroup> {
+        let m = &self.matched[*self.sorted.get(i)?];
+        Some(&self.groups[m.index])
+    }
+
+    pub fn order_by_name_asc(&self) -> impl Iterator<Item = &CwlGroup> {
+        self.groups.iter()
     }
 }
 
 impl Default for CwlGroupStore {
     fn default() -> Self {
-        CwlGroupStore::new(Store::default())
+        CwlGroupStore::new(std::iter::empty())
     }
 }
 
 impl Length for CwlGroupStore {
     fn len(&self) -> usize {
-        self.groups.len()
+        self.matched.len()
+    }
+}
+
+fn default_label() -> &'static str {
+    "Groups"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn group(name: &str, day: u32, stored: u128) -> CwlGroup {
+        CwlGroup::for_test(name)
+            .created_at(Utc.ymd(2020, 11, day).and_hms(0, 0, 0))
+            .stored(stored)
+    }
+
+    fn names(store: &mut CwlGroupStore, sort: GroupSort) -> Vec<&str> {
+        store.set_sort(sort);
+        store
+            .sorted()
+            .into_iter()
+            .map(|m| m.item.group_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_sorted() {
+        use GroupSortColumn::*;
+        use SortOrder::*;
+
+        let mut store = CwlGroupStore::default();
+        store.extend(
+            vec![
+                group("api", 2, 10),
+                group("rapid", 1, 30),
+                group("my-api", 3, 20),
+            ]
+            .into_iter(),
+        );
+
+        assert_eq!(
+            vec!["api", "my-api", "rapid"],
+            names(&mut store, GroupSort::default())
+        );
+        assert_eq!(
+            vec!["rapid", "my-api", "api"],
+            names(&mut store, GroupSort::new(Name, Desc))
+        );
+        assert_eq!(
+            vec!["rapid", "api", "my-api"],
+            names(&mut store, GroupSort::new(CreationTime, Asc))
+        );
+        assert_eq!(
+            vec!["rapid", "my-api", "api"],
+            names(&mut store, GroupSort::new(Stored, Desc))
+        );
+
+        store.set_query(Some(GroupQuery::from_str("api").unwrap()));
+        assert_eq!(
+            vec!["api", "my-api", "rapid"],
+            names(&mut store, GroupSort::default())
+        );
+        assert_eq!(
+            vec!["rapid", "my-api", "api"],
+            names(&mut store, GroupSort::new(Relevance, Desc))
+        );
+        assert_eq!(
+            vec!["api", "my-api", "rapid"],
+            names(&mut store, GroupSort::new(Stored, Asc))
+        );
+
+        // the order is kept as groups change.
+        store.insert(group("api-v2", 4, 5));
+        assert_eq!("api-v2", store.get(0).unwrap().group_name);
+        assert_eq!(
+            vec!["api-v2", "api", "my-api", "rapid"],
+            names(&mut store, GroupSort::new(Stored, Asc))
+        );
     }
 }
@@ -0,0 +1,180 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::aws::cwlogs::group::analytics::name_prefix;
+use crate::aws::cwlogs::group::model::CwlGroup;
+use crate::size::Size;
+
+/// A name prefix of log groups, e.g. `/aws/lambda`, with the totals of the groups under it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CwlGroupTreeNode<'a> {
+    /// The name prefix up to this node.
+    pub path: String,
+    /// The last segment of the path.
+    pub segment: String,
+    pub group_count: usize,
+    pub stored: Size,
+    /// The group named exactly the path, if any.
+    pub group: Option<&'a CwlGroup>,
+    children: BTreeMap<String, CwlGroupTreeNode<'a>>,
+}
+
+impl<'a> CwlGroupTreeNode<'a> {
+    fn new(path: &str, parent: &str) -> Self {
+        let segment = path[parent.len()..].trim_start_matches('/');
+        CwlGroupTreeNode {
+            path: path.to_string(),
+            segment: if parent.is_empty() { path } else { segment }.to_string(),
+            group_count: 0,
+            stored: Size::new(0),
+            group: None,
+            children: BTreeMap::new(),
+        }
+    }
+
+    fn add(&mut self, group: &'a CwlGroup) {
+        self.group_count += 1;
+        self.stored = Size::new(self.stored.in_bytes() + group.stored.in_bytes());
+    }
+
+    pub fn has_children(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// A visible line of the tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CwlGroupTreeRow<'t, 'a> {
+    pub depth: usize,
+    pub expanded: bool,
+    pub node: &'t CwlGroupTreeNode<'a>,
+}
+
+/// Log groups split on `/` into nested name prefixes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CwlGroupTree<'a> {
+    roots: BTreeMap<String, CwlGroupTreeNode<'a>>,
+}
+
+impl<'a> CwlGroupTree<'a> {
+    pub fn new(groups: impl IntoIterator<Item = &'a CwlGroup>) -> Self {
+        let mut tree = CwlGroupTree::default();
+        for group in groups {
+            tree.insert(group);
+        }
+        tree
+    }
+
+    fn insert(&mut self, group: &'a CwlGroup) {
+        let name = group.group_name.as_str();
+        let (mut children, mut parent) = (&mut self.roots, "");
+        let mut depth = 1;
+        loop {
+            let path = name_prefix(name, depth);
+            let node = children
+                .entry(path.to_string())
+                .or_insert_with(|| CwlGroupTreeNode::new(path, parent));
+            node.add(group);
+            if path == name {
+                node.group = Some(group);
+                return;
+            }
+            children = &mut node.children;
+            parent = path;
+            depth += 1;
+        }
+    }
+
+    /// Lists the nodes in name order, descending only into the expanded paths.
+    pub fn rows(&self, expanded: &BTreeSet<String>) -> Vec<CwlGroupTreeRow<'_, 'a>> {
+        let mut rows = Vec::new();
+        let mut stack = self
+            .roots
+            .values()
+            .rev()
+            .map(|n| (0, n))
+            .collect::<Vec<_>>();
+        while let Some((depth, node)) = stack.pop() {
+            let is_expanded = expanded.contains(&node.path);
+            rows.push(CwlGroupTreeRow {
+                depth,
+                expanded: is_expanded,
+                node,
+            });
+            if is_expanded {
+                stack.extend(node.children.values().rev().map(|n| (depth + 1, n)));
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name: &str, stored: u128) -> CwlGroup {
+        CwlGroup::for_test(name).stored(stored)
+    }
+
+    fn lines(tree: &CwlGroupTree, expanded: &[&str]) -> Vec<(usize, String, usize, u128, bool)> {
+        let expanded = expanded.iter().map(|s| s.to_string()).collect();
+        tree.rows(&expanded)
+            .iter()
+            .map(|r| {
+                (
+                    r.depth,
+                    r.node.segment.clone(),
+                    r.node.group_count,
+                    r.node.stored.in_bytes(),
+                    r.node.group.is_some(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tree() {
+        let groups = [
+            group("/aws/lambda/b", 20),
+            group("/aws/lambda/a", 10),
+            group("/aws/lambda", 5),
+            group("/ecs/web", 100),
+            group("app", 1),
+        ];
+        let tree = CwlGroupTree::new(groups.iter());
+
+        assert_eq!(
+            vec![
+                (0, "/aws".to_string(), 3, 35, false),
+                (0, "/ecs".to_string(), 1, 100, false),
+                (0, "app".to_string(), 1, 1, true),
+            ],
+            lines(&tree, &[])
+        );
+        assert_eq!(
+            vec![
+                (0, "/aws".to_string(), 3, 35, false),
+                (1, "lambda".to_string(), 3, 35, true),
+                (2, "a".to_string(), 1, 10, true),
+                (2, "b".to_string(), 1, 20, true),
+                (0, "/ecs".to_string(), 1, 100, false),
+                (0, "app".to_string(), 1, 1, true),
+            ],
+            lines(&tree, &["/aws", "/aws/lambda", "/ecs/web"])
+        );
+    }
+
+    #[test]
+    fn test_collapsed_parent_hides_expanded_children() {
+        let groups = [group("/aws/lambda/a", 10)];
+        let tree = CwlGroupTree::new(groups.iter());
+
+        assert_eq!(
+            vec![(0, "/aws".to_string(), 1, 10, false)],
+            lines(&tree, &["/aws/lambda"])
+        );
+        let rows = tree.rows(&["/aws".to_string()].iter().cloned().collect());
+        assert_eq!("/aws/lambda", rows[1].node.path);
+        assert!(rows[1].node.has_children());
+    }
+}
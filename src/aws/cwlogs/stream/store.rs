@@ -1,14 +1,23 @@
 use crate::aws::cwlogs::stream::model::CwlStream;
 use crate::collection::store::Store;
+use crate::collection::Length;
 
 #[derive(Debug)]
 pub struct CwlStreamStore {
     streams: Store<CwlStream>,
+    loading: bool,
 }
 
 impl CwlStreamStore {
     pub fn new(streams: Store<CwlStream>) -> Self {
-        CwlStreamStore { streams }
+        CwlStreamStore {
+            streams,
+            loading: false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.streams.clear();
     }
 
     pub fn insert(&mut self, stream: CwlStream) {
@@ -19,6 +28,19 @@ impl CwlStreamStore {
         self.streams.extend(streams);
     }
 
+    /// Removes the stream having the same ARN.
+    pub fn remove(&mut self, stream: &CwlStream) {
+        self.streams.remove(stream);
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    pub fn set_loading(&mut self, loading: bool) {
+        self.loading = loading;
+    }
+
     pub fn order_by_creation_time_asc(&self) -> impl Iterator<Item = &CwlStream> {
         self.streams.order_by_asc(|stream| stream.creation_time)
     }
@@ -26,6 +48,10 @@ impl CwlStreamStore {
     pub fn order_by_creation_time_desc(&self) -> impl Iterator<Item = &CwlStream> {
         self.streams.order_by_desc(|stream| stream.creation_time)
     }
+
+    pub fn order_by_last_event_time_desc(&self) -> impl Iterator<Item = &CwlStream> {
+        self.streams.order_by_desc(|stream| stream.last_event_time)
+    }
 }
 
 impl Default for CwlStreamStore {
@@ -33,3 +59,9 @@ impl Default for CwlStreamStore {
         CwlStreamStore::new(Store::default())
     }
 }
+
+impl Length for CwlStreamStore {
+    fn len(&self) -> usize {
+        self.streams.len()
+    }
+}
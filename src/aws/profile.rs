@@ -6,8 +6,8 @@ use std::path::PathBuf;
 use dirs_next::home_dir;
 use thiserror::Error;
 
-use crate::collection::store::Store;
 use crate::collection::{AsStr, Length};
+use crate::matcher::{rank, Matched, MatchedIndex};
 use crate::query::Query;
 
 #[derive(Debug, Error)]
@@ -19,7 +19,7 @@ pub enum ProfileStoreError {
     NoHomeDirectory,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ProfileName(String);
 
 impl AsStr for ProfileName {
@@ -43,8 +43,9 @@ impl<T: Into<String>> From<T> for ProfileName {
 
 #[derive(Debug)]
 pub struct ProfileStore {
-    profile_names: Store<(usize, ProfileName)>,
+    profile_names: Vec<ProfileName>,
     query: Option<Query>,
+    matched: Vec<MatchedIndex>,
     label: String,
 }
 
@@ -83,14 +84,13 @@ impl ProfileStore {
     }
 
     pub fn insert(&mut self, profile: ProfileName) {
-        self.profile_names
-            .insert((self.profile_names.len() + 1, profile));
+        self.profile_names.push(profile);
+        self.update_matched();
     }
 
     pub fn extend(&mut self, profiles: impl Iterator<Item = ProfileName>) {
-        let offset = self.profile_names.len() + 1;
-        self.profile_names
-            .extend(profiles.enumerate().map(|(i, p)| (i + offset, p)));
+        self.profile_names.extend(profiles);
+        self.update_matched();
     }
 
     pub fn set_query(&mut self, query: Option<Query>) {
@@ -100,6 +100,13 @@ impl ProfileStore {
         } else {
             default_label().to_string()
         };
+        self.update_matched();
+    }
+
+    /// Ranks the profiles once, when the query or the profiles change.
+    fn update_matched(&mut self) {
+        let matcher = self.query.as_ref().map(Query::matcher);
+        self.matched = rank(self.profile_names.iter(), matcher.as_ref(), |p| p.as_str());
     }
 
     pub fn label(&self) -> &str {
@@ -107,19 +114,35 @@ impl ProfileStore {
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &ProfileName> {
-        let q = self.query.clone();
-        self.profile_names
+        self.matched
             .iter()
-            .filter(move |(_, p)| profile_matches(p, q.as_ref()))
-            .map(|(_, p)| p)
+            .map(move |m| &self.profile_names[m.index])
+    }
+
+    /// The profile at the position in the ranked profiles.
+    pub fn get(&self, i: usize) -> Option<&ProfileName> {
+        self.matched.get(i).map(|m| &self.profile_names[m.index])
+    }
+
+    /// Every profile, regardless of the query.
+    pub fn all(&self) -> impl Iterator<Item = &ProfileName> {
+        self.profile_names.iter()
+    }
+
+    pub fn matched(&self) -> Vec<Matched<'_, ProfileName>> {
+        self.matched
+            .iter()
+            .map(|m| m.of(&self.profile_names))
+            .collect()
     }
 }
 
 impl Default for ProfileStore {
     fn default() -> Self {
         ProfileStore {
-            profile_names: Store::default(),
+            profile_names: Vec::new(),
             query: None,
+            matched: Vec::new(),
             label: default_label().to_string(),
         }
     }
@@ -127,7 +150,7 @@ impl Default for ProfileStore {
 
 impl Length for ProfileStore {
     fn len(&self) -> usize {
-        self.iter().count()
+        self.matched.len()
     }
 }
 
@@ -135,22 +158,17 @@ fn default_label() -> &'static str {
     "Profiles"
 }
 
-fn default_config_path() -> Option<PathBuf> {
+pub fn default_config_path() -> Option<PathBuf> {
     home_dir().map(|p| p.join(".aws").join("config"))
 }
 
-fn profile_matches(profile: &ProfileName, query: Option<&Query>) -> bool {
-    query
-        .map(|q| profile.as_str().contains(q.word()))
-        .unwrap_or(true)
-}
-
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
     use std::str::FromStr;
 
     use crate::aws::profile::{ProfileName, ProfileStore};
+    use crate::query::query_from;
 
     const PROJECT_ROOT: &'static str = env!("CARGO_MANIFEST_DIR");
 
@@ -182,4 +200,22 @@ mod tests {
         assert_eq!(Some(&profile_name("profile-outer-spaces")), iter.next());
         assert_eq!(None, iter.next());
     }
+
+    #[test]
+    fn test_fuzzy_query() {
+        let path = fixture_path("aws_config");
+        let mut store = ProfileStore::from_file(path).unwrap();
+        store.set_query(query_from("prfinner".to_string()));
+
+        assert_eq!(
+            vec![&profile_name("profile-inner-spaces")],
+            store.iter().collect::<Vec<_>>()
+        );
+
+        store.set_query(query_from("spaces".to_string()));
+        let matched = store.matched();
+        assert_eq!(4, matched.len());
+        assert_eq!(&profile_name("inner-spaces"), matched[0].item);
+        assert_eq!(&[6, 7, 8, 9, 10, 11], matched[0].indices());
+    }
 }
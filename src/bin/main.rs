@@ -11,9 +11,12 @@ use tokio::sync::Mutex;
 use razy_cwlogs::app::config::AppConfig;
 use razy_cwlogs::app::data::AppData;
 use razy_cwlogs::app::handler::{handle_action, handle_key_input};
+use razy_cwlogs::app::shell::handler::launch;
 use razy_cwlogs::app::state::AppSharedState;
 use razy_cwlogs::app::App;
+use razy_cwlogs::bookmark::BookmarkStore;
 use razy_cwlogs::event::{crossterm_key_stream, tick_stream, Action, Event};
+use razy_cwlogs::launch::{parse_args, LaunchCommand, USAGE};
 use razy_cwlogs::terminal::CrossTermTerminal;
 use razy_cwlogs::ui::widget::debug::DebugData;
 
@@ -47,10 +50,11 @@ fn app_data() -> AppData {
     AppData::default()
 }
 
-fn app(action_sender: Sender<Action>) -> App {
-    let config = AppConfig::default();
-    let data = app_data();
-    App::new(config, data, action_sender)
+fn app(action_sender: Sender<Action>) -> anyhow::Result<App> {
+    let config = AppConfig::load()?;
+    let mut data = app_data();
+    data.bookmarks = BookmarkStore::load()?;
+    Ok(App::new(config, data, action_sender))
 }
 
 fn terminal() -> anyhow::Result<CrossTermTerminal> {
@@ -138,13 +142,21 @@ fn event_receiver(app: &App, action_receiver: Receiver<Action>) -> Receiver<Even
     receiver
 }
 
+fn draw(app: &mut App, terminal: &mut CrossTermTerminal) -> anyhow::Result<()> {
+    terminal.draw(|frame| frame.render_widget(app.widgets(), frame.size()))?;
+    if let Some((area, text, url)) = app.status_link() {
+        terminal.draw_hyperlink(area, text, url)?;
+    }
+    Ok(())
+}
+
 async fn run(
     mut app: App,
     mut terminal: CrossTermTerminal,
     mut receiver: Receiver<Event>,
 ) -> anyhow::Result<()> {
     terminal.clear()?;
-    terminal.draw(|frame| frame.render_widget(app.widgets(), frame.size()))?;
+    draw(&mut app, &mut terminal)?;
 
     loop {
         if !app_is_running(&app.shared_state()) {
@@ -156,9 +168,7 @@ async fn run(
                 Event::Input(key) => {
                     handle_key_input(&mut app, key).await?;
                 }
-                Event::Tick => {
-                    terminal.draw(|frame| frame.render_widget(app.widgets(), frame.size()))?;
-                }
+                Event::Tick => draw(&mut app, &mut terminal)?,
                 Event::Action(action) => {
                     handle_action(&mut app, action).await?;
                 }
@@ -177,9 +187,28 @@ async fn main() -> Result<(), Box<dyn StdError>> {
         panic_hook(info);
     }));
 
+    let target = match parse_args(std::env::args().skip(1)) {
+        Ok(LaunchCommand::Open(target)) => target,
+        Ok(LaunchCommand::Help) => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        Ok(LaunchCommand::Version) => {
+            println!("razy-cwlogs {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Err(e) => {
+            eprintln!("razy-cwlogs: {}", e);
+            std::process::exit(2);
+        }
+    };
+
     let (action_sender, action_receiver) = channel(MAX_ACTIONS);
-    let app = app(action_sender);
+    let mut app = app(action_sender)?;
     app.state().lock().await.start_running();
+    if !target.is_empty() {
+        launch(&mut app, target).await;
+    }
 
     let terminal = terminal()?;
     let receiver = event_receiver(&app, action_receiver);
@@ -0,0 +1,265 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::app::config::default_config_dir;
+use crate::aws::cwlogs::event::{CwlEvent, EventId};
+use crate::aws::cwlogs::group::CwlGroupSource;
+use crate::collection::AsStr;
+
+#[derive(Debug, Error)]
+pub enum BookmarkError {
+    #[error("could not access {}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("invalid bookmarks file {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("could not write bookmarks: {source}")]
+    Serialize {
+        #[from]
+        source: toml::ser::Error,
+    },
+}
+
+/// An event kept to come back to, with the account it was read from.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub profile: String,
+    pub region: String,
+    pub group_name: String,
+    pub stream_name: String,
+    pub event_id: String,
+    /// Time of the event in milliseconds, to read the lines around it.
+    pub event_time: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl Bookmark {
+    pub fn new(source: &CwlGroupSource, group_name: &str, event: &CwlEvent) -> Self {
+        Bookmark {
+            profile: source.profile.to_string(),
+            region: source.region.name().to_string(),
+            group_name: group_name.to_string(),
+            stream_name: event.stream_name.clone(),
+            event_id: event.event_id.as_str().to_string(),
+            event_time: event.event_time.timestamp_millis(),
+            message: event.message.clone(),
+            note: None,
+        }
+    }
+
+    pub fn event_time(&self) -> DateTime<Utc> {
+        Utc.timestamp_millis(self.event_time)
+    }
+
+    /// The event, without the times and level which are not kept.
+    pub fn event(&self) -> CwlEvent {
+        CwlEvent {
+            event_id: EventId::from(self.event_id.as_str()),
+            ingestion_time: self.event_time(),
+            stream_name: self.stream_name.clone(),
+            message: self.message.clone(),
+            event_time: self.event_time(),
+            level: None,
+            group_name: Some(self.group_name.clone()),
+        }
+    }
+
+    /// Whether the bookmark is of the event, by its group, stream and id.
+    pub fn is_of(&self, group_name: &str, event: &CwlEvent) -> bool {
+        self.group_name == group_name
+            && self.stream_name == event.stream_name
+            && self.event_id == event.event_id.as_str()
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BookmarksFile {
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+}
+
+/// Bookmarks of every session, saved to `bookmarks.toml` in the config directory.
+#[derive(Debug, Default)]
+pub struct BookmarkStore {
+    bookmarks: Vec<Bookmark>,
+    path: Option<PathBuf>,
+}
+
+impl BookmarkStore {
+    /// Loads `bookmarks.toml` in the config directory, or no bookmarks if it does not exist.
+    pub fn load() -> Result<BookmarkStore, BookmarkError> {
+        match default_config_dir().map(|dir| dir.join("bookmarks.toml")) {
+            Some(path) if path.exists() => BookmarkStore::from_file(&path),
+            path => Ok(BookmarkStore {
+                bookmarks: Vec::new(),
+                path,
+            }),
+        }
+    }
+
+    pub fn from_file(path: &Path) -> Result<BookmarkStore, BookmarkError> {
+        let s = std::fs::read_to_string(path).map_err(|source| BookmarkError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let file: BookmarksFile = toml::from_str(&s).map_err(|source| BookmarkError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Ok(BookmarkStore {
+            bookmarks: file.bookmarks,
+            path: Some(path.to_path_buf()),
+        })
+    }
+
+    /// Writes the bookmarks to the file they were loaded from, if any.
+    pub fn save(&self) -> Result<(), BookmarkError> {
+        let path = match self.path.as_ref() {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+        let file = BookmarksFile {
+            bookmarks: self.bookmarks.clone(),
+        };
+        let s = toml::to_string(&file)?;
+        let io_error = |source| BookmarkError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        std::fs::write(path, s).map_err(io_error)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Bookmark> {
+        self.bookmarks.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bookmarks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bookmarks.is_empty()
+    }
+
+    pub fn get(&self, i: usize) -> Option<&Bookmark> {
+        self.bookmarks.get(i)
+    }
+
+    /// Adds a bookmark of the event, or removes it if the event is bookmarked already.
+    /// Returns whether the event is bookmarked.
+    pub fn toggle(&mut self, bookmark: Bookmark) -> bool {
+        let same = |b: &Bookmark| {
+            b.group_name == bookmark.group_name
+                && b.stream_name == bookmark.stream_name
+                && b.event_id == bookmark.event_id
+        };
+        match self.bookmarks.iter().position(same) {
+            Some(i) => {
+                self.bookmarks.remove(i);
+                false
+            }
+            None => {
+                self.bookmarks.push(bookmark);
+                self.bookmarks.sort_by_key(|b| b.event_time);
+                true
+            }
+        }
+    }
+
+    pub fn remove(&mut self, i: usize) -> Option<Bookmark> {
+        if i < self.bookmarks.len() {
+            Some(self.bookmarks.remove(i))
+        } else {
+            None
+        }
+    }
+
+    /// Sets the note of the bookmark, an empty note removes it.
+    pub fn set_note(&mut self, i: usize, note: &str) {
+        if let Some(bookmark) = self.bookmarks.get_mut(i) {
+            let note = note.trim();
+            bookmark.note = if note.is_empty() {
+                None
+            } else {
+                Some(note.to_string())
+            };
+        }
+    }
+
+    pub fn is_bookmarked(&self, group_name: &str, event: &CwlEvent) -> bool {
+        self.bookmarks.iter().any(|b| b.is_of(group_name, event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rusoto_core::Region;
+
+    use crate::aws::profile::ProfileName;
+
+    use super::*;
+
+    fn event(id: &str, ms: i64) -> CwlEvent {
+        CwlEvent::for_test(ms).id(id)
+    }
+
+    fn bookmark(id: &str, ms: i64) -> Bookmark {
+        let source = CwlGroupSource::new(ProfileName::from("default"), Region::ApNortheast1);
+        Bookmark::new(&source, "/app/api", &event(id, ms))
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut store = BookmarkStore::default();
+        assert!(store.toggle(bookmark("b", 2000)));
+        assert!(store.toggle(bookmark("a", 1000)));
+        assert_eq!(
+            vec!["a", "b"],
+            store
+                .iter()
+                .map(|b| b.event_id.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(store.is_bookmarked("/app/api", &event("b", 2000)));
+        assert!(!store.is_bookmarked("/app/db", &event("b", 2000)));
+
+        assert!(!store.toggle(bookmark("b", 2000)));
+        assert_eq!(1, store.len());
+
+        store.set_note(0, "  cause of the outage ");
+        assert_eq!(
+            Some("cause of the outage"),
+            store.get(0).unwrap().note.as_deref()
+        );
+        store.set_note(0, "");
+        assert_eq!(None, store.get(0).unwrap().note);
+    }
+
+    #[test]
+    fn test_file() {
+        let mut b = bookmark("a", 1000);
+        b.note = Some("a note".to_string());
+        let file = BookmarksFile {
+            bookmarks: vec![b.clone(), bookmark("b", 2000)],
+        };
+
+        let s = toml::to_string(&file).unwrap();
+        let parsed: BookmarksFile = toml::from_str(&s).unwrap();
+        assert_eq!(file.bookmarks, parsed.bookmarks);
+        assert_eq!("ap-northeast-1", parsed.bookmarks[0].region);
+        let restored = b.event();
+        assert!(b.is_of("/app/api", &restored));
+        assert_eq!(Some("/app/api"), restored.group_name.as_deref());
+    }
+}
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::io::{stdout, Write};
+
+use crossterm::{execute, Command};
+use thiserror::Error;
+
+/// Text copied at most, terminals drop OSC 52 sequences of around 100KB and more.
+pub const MAX_CLIPBOARD_BYTES: usize = 74_000;
+
+#[derive(Debug, Error)]
+pub enum ClipboardError {
+    #[error("nothing to copy")]
+    Empty,
+
+    #[error("{size} bytes are too large to copy, the limit is {limit} bytes")]
+    TooLarge { size: usize, limit: usize },
+
+    #[error("could not write to the terminal")]
+    CrossTerm(#[from] crossterm::ErrorKind),
+}
+
+/// Sets the clipboard of the terminal by the OSC 52 escape sequence.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SetClipboard(String);
+
+impl SetClipboard {
+    pub fn new(text: &str) -> Result<SetClipboard, ClipboardError> {
+        match text.len() {
+            0 => Err(ClipboardError::Empty),
+            size if size > MAX_CLIPBOARD_BYTES => Err(ClipboardError::TooLarge {
+                size,
+                limit: MAX_CLIPBOARD_BYTES,
+            }),
+            _ => Ok(SetClipboard(base64::encode(text))),
+        }
+    }
+}
+
+impl fmt::Display for SetClipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\x1b]52;c;{}\x07", self.0)
+    }
+}
+
+impl Command for SetClipboard {
+    type AnsiType = String;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        self.to_string()
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(
+        &self,
+        _writer: impl FnMut() -> crossterm::Result<()>,
+    ) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
+/// Copies the text to the clipboard of the terminal, returns the copied bytes.
+pub fn copy(text: &str) -> Result<usize, ClipboardError> {
+    let command = SetClipboard::new(text)?;
+    execute!(stdout(), command)?;
+    Ok(text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence() {
+        let command = SetClipboard::new("/aws/lambda/api").unwrap();
+        assert_eq!("\x1b]52;c;L2F3cy9sYW1iZGEvYXBp\x07", command.ansi_code());
+
+        assert!(matches!(SetClipboard::new(""), Err(ClipboardError::Empty)));
+        let large = "a".repeat(MAX_CLIPBOARD_BYTES + 1);
+        assert!(matches!(
+            SetClipboard::new(&large),
+            Err(ClipboardError::TooLarge { .. })
+        ));
+    }
+}
@@ -2,6 +2,27 @@ use std::collections::VecDeque;
 
 pub mod store;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl Default for SortOrder {
+    fn default() -> Self {
+        SortOrder::Asc
+    }
+}
+
+impl SortOrder {
+    pub fn reversed(&self) -> SortOrder {
+        match *self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+}
+
 pub trait Length {
     fn len(&self) -> usize;
 }
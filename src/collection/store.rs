@@ -47,6 +47,10 @@ impl<T: Ord + Debug> Store<T> {
         self.items.extend(items);
     }
 
+    pub fn remove(&mut self, item: &T) {
+        self.items.remove(item);
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &T> {
         self.items.iter()
     }
@@ -0,0 +1,432 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use rusoto_core::Region;
+use thiserror::Error;
+
+use crate::aws::profile::ProfileName;
+use crate::preset::PresetName;
+use crate::time::{ParseTimeRangeError, TimeRangeSpec, TimeZoneChoice};
+use crate::ui::theme::ThemeName;
+
+/// Commands kept in the history at most.
+const MAX_HISTORY: usize = 100;
+
+/// Regions offered by completion, any region rusoto knows is accepted.
+pub const REGIONS: [&str; 22] = [
+    "af-south-1",
+    "ap-east-1",
+    "ap-northeast-1",
+    "ap-northeast-2",
+    "ap-northeast-3",
+    "ap-south-1",
+    "ap-southeast-1",
+    "ap-southeast-2",
+    "ca-central-1",
+    "eu-central-1",
+    "eu-north-1",
+    "eu-south-1",
+    "eu-west-1",
+    "eu-west-2",
+    "eu-west-3",
+    "me-south-1",
+    "sa-east-1",
+    "us-east-1",
+    "us-east-2",
+    "us-gov-west-1",
+    "us-west-1",
+    "us-west-2",
+];
+
+/// A command of the command line, with what its argument is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct CommandSpec {
+    pub name: &'static str,
+    pub argument: Option<&'static str>,
+    pub description: &'static str,
+}
+
+pub const COMMANDS: [CommandSpec; 8] = [
+    CommandSpec {
+        name: "profile",
+        argument: Some("<profile>"),
+        description: "list log groups of the profile",
+    },
+    CommandSpec {
+        name: "preset",
+        argument: Some("<preset>"),
+        description: "list log groups of the preset in the current profile",
+    },
+    CommandSpec {
+        name: "region",
+        argument: Some("<region>"),
+        description: "use the region over the one of each profile",
+    },
+    CommandSpec {
+        name: "group",
+        argument: Some("<group>"),
+        description: "open a listed log group",
+    },
+    CommandSpec {
+        name: "since",
+        argument: Some("<range>"),
+        description: "set the time range of the session, like 2h or today 09:00..10:30",
+    },
+    CommandSpec {
+        name: "export",
+        argument: Some("csv"),
+        description: "export the marked groups, or every listed group",
+    },
+    CommandSpec {
+        name: "theme",
+        argument: Some("dark|light"),
+        description: "switch the colours",
+    },
+    CommandSpec {
+        name: "quit",
+        argument: None,
+        description: "quit the app",
+    },
+];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Command {
+    Profile(ProfileName),
+    Preset(PresetName),
+    Region(Region),
+    Group(String),
+    Since(TimeRangeSpec),
+    Export(ExportFormat),
+    Theme(ThemeName),
+    Quit,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum CommandError {
+    #[error("type a command, like :profile <profile>")]
+    Empty,
+
+    #[error("unknown command `{0}`")]
+    Unknown(String),
+
+    #[error(":{} needs {}", .0.name, .0.argument.unwrap_or_default())]
+    MissingArgument(CommandSpec),
+
+    #[error(":{} takes no argument", .0.name)]
+    UnexpectedArgument(CommandSpec),
+
+    #[error("unknown region `{0}`")]
+    Region(String),
+
+    #[error("{0}")]
+    TimeRange(#[from] ParseTimeRangeError),
+
+    #[error("unknown format `{0}`, expected csv")]
+    ExportFormat(String),
+
+    #[error("unknown theme `{0}`, expected dark or light")]
+    Theme(String),
+}
+
+impl Command {
+    /// Parses a command line like `profile prd` or `:since 2h`, reading times in the zone.
+    pub fn parse(
+        s: &str,
+        zone: &TimeZoneChoice,
+        now: &DateTime<Utc>,
+    ) -> Result<Command, CommandError> {
+        let s = s.trim();
+        let s = s.strip_prefix(':').unwrap_or(s).trim_start();
+        if s.is_empty() {
+            return Err(CommandError::Empty);
+        }
+        let (name, arg) = match s.find(char::is_whitespace) {
+            Some(i) => (&s[..i], s[i..].trim()),
+            None => (s, ""),
+        };
+        let name = if name == "q" { "quit" } else { name };
+        let spec = COMMANDS
+            .iter()
+            .find(|c| c.name == name)
+            .copied()
+            .ok_or_else(|| CommandError::Unknown(name.to_string()))?;
+        match (spec.argument, arg.is_empty()) {
+            (Some(_), true) => return Err(CommandError::MissingArgument(spec)),
+            (None, false) => return Err(CommandError::UnexpectedArgument(spec)),
+            _ => (),
+        }
+
+        let command = match spec.name {
+            "profile" => Command::Profile(ProfileName::from(arg)),
+            "preset" => Command::Preset(PresetName::from(arg)),
+            "region" => Command::Region(
+                Region::from_str(arg).map_err(|_| CommandError::Region(arg.to_string()))?,
+            ),
+            "group" => Command::Group(arg.to_string()),
+            "since" => Command::Since(TimeRangeSpec::parse(arg, zone, now)?),
+            "export" => match arg {
+                "csv" => Command::Export(ExportFormat::Csv),
+                _ => return Err(CommandError::ExportFormat(arg.to_string())),
+            },
+            "theme" => match ThemeName::ALL.iter().find(|t| t.as_str() == arg) {
+                Some(theme) => Command::Theme(*theme),
+                None => return Err(CommandError::Theme(arg.to_string())),
+            },
+            "quit" => Command::Quit,
+            // a command listed without being parsed is an error of COMMANDS, not of the input.
+            name => unreachable!("command `{}` is listed but not parsed", name),
+        };
+        Ok(command)
+    }
+}
+
+/// The command line after completing the word being typed, with the words it could be.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Completion {
+    pub input: String,
+    pub candidates: Vec<String>,
+}
+
+/// The longest prefix of every word, cut at a character boundary.
+fn common_prefix<'a>(words: &[&'a str]) -> &'a str {
+    let first = words.first().copied().unwrap_or_default();
+    // byte offset in `first` where the words stop agreeing.
+    let end = words.iter().skip(1).fold(first.len(), |end, word| {
+        first[..end]
+            .char_indices()
+            .zip(word.chars())
+            .find(|((_, a), b)| a != b)
+            .map(|((i, _), _)| i)
+            .unwrap_or_else(|| end.min(word.len()))
+    });
+    &first[..end]
+}
+
+/// Completes the command name, or its argument from the candidates of the command.
+/// Arguments are matched by prefix, or anywhere in them when no argument starts with it.
+pub fn complete<F>(input: &str, arguments: F) -> Option<Completion>
+where
+    F: FnOnce(&str) -> Vec<String>,
+{
+    let input = input.trim_start();
+    let input = input.strip_prefix(':').unwrap_or(input);
+    let (head, word, candidates) = match input.find(char::is_whitespace) {
+        None => {
+            let names = COMMANDS.iter().map(|c| c.name.to_string()).collect();
+            ("", input, names)
+        }
+        Some(i) => {
+            let name = &input[..i];
+            let word = input[i..].trim_start();
+            (&input[..input.len() - word.len()], word, arguments(name))
+        }
+    };
+
+    let mut matched = candidates
+        .iter()
+        .filter(|c| c.starts_with(word))
+        .map(String::as_str)
+        .collect::<Vec<_>>();
+    if matched.is_empty() {
+        matched = candidates
+            .iter()
+            .filter(|c| c.contains(word))
+            .map(String::as_str)
+            .collect();
+    }
+
+    let completed = match matched.as_slice() {
+        [] => return None,
+        [only] if head.is_empty() => format!("{} ", only),
+        [only] => only.to_string(),
+        words => {
+            let prefix = common_prefix(words);
+            if prefix.starts_with(word) {
+                prefix.to_string()
+            } else {
+                word.to_string()
+            }
+        }
+    };
+    Some(Completion {
+        input: format!("{}{}", head, completed),
+        candidates: matched.into_iter().map(str::to_string).collect(),
+    })
+}
+
+/// Command lines run before, browsed from the latest one.
+#[derive(Debug, Clone, Default)]
+pub struct CommandHistory {
+    entries: VecDeque<String>,
+    /// Position from the latest entry while browsing.
+    pos: Option<usize>,
+}
+
+impl CommandHistory {
+    pub fn push(&mut self, line: &str) {
+        self.pos = None;
+        let line = line.trim();
+        if line.is_empty() || self.entries.front().map(String::as_str) == Some(line) {
+            return;
+        }
+        self.entries.push_front(line.to_string());
+        self.entries.truncate(MAX_HISTORY);
+    }
+
+    /// An older line than the one shown.
+    pub fn older(&mut self) -> Option<&str> {
+        let pos = self.pos.map(|p| p + 1).unwrap_or(0);
+        if pos < self.entries.len() {
+            self.pos = Some(pos);
+        }
+        self.current()
+    }
+
+    /// A newer line than the one shown, `None` past the latest one.
+    pub fn newer(&mut self) -> Option<&str> {
+        self.pos = self.pos.and_then(|p| p.checked_sub(1));
+        self.current()
+    }
+
+    fn current(&self) -> Option<&str> {
+        let entries = &self.entries;
+        self.pos.and_then(|p| entries.get(p)).map(String::as_str)
+    }
+
+    pub fn reset(&mut self) {
+        self.pos = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn parse(s: &str) -> Result<Command, CommandError> {
+        let now = Utc.ymd(2021, 1, 2).and_hms(12, 0, 0);
+        Command::parse(s, &TimeZoneChoice::default(), &now)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Ok(Command::Profile(ProfileName::from("prd"))),
+            parse(":profile prd")
+        );
+        assert_eq!(
+            Ok(Command::Region(Region::UsEast1)),
+            parse("region  us-east-1 ")
+        );
+        assert_eq!(
+            Ok(Command::Group("/aws/lambda/foo".to_string())),
+            parse("group /aws/lambda/foo")
+        );
+        assert!(matches!(parse("since 2h"), Ok(Command::Since(_))));
+        assert_eq!(Ok(Command::Export(ExportFormat::Csv)), parse("export csv"));
+        assert_eq!(Ok(Command::Theme(ThemeName::Light)), parse("theme light"));
+        assert_eq!(Ok(Command::Quit), parse(":q"));
+
+        assert_eq!(Err(CommandError::Empty), parse(":"));
+        assert_eq!(
+            Err(CommandError::Unknown("prof".to_string())),
+            parse("prof prd")
+        );
+        assert_eq!(
+            ":profile needs <profile>",
+            parse("profile").unwrap_err().to_string()
+        );
+        assert!(matches!(
+            parse("quit now"),
+            Err(CommandError::UnexpectedArgument(_))
+        ));
+        assert_eq!(
+            Err(CommandError::Region("mars-1".to_string())),
+            parse("region mars-1")
+        );
+        assert!(matches!(parse("since 2y"), Err(CommandError::TimeRange(_))));
+        assert_eq!(
+            Err(CommandError::ExportFormat("xml".to_string())),
+            parse("export xml")
+        );
+        assert_eq!(
+            Err(CommandError::Theme("blue".to_string())),
+            parse("theme blue")
+        );
+    }
+
+    #[test]
+    fn test_regions() {
+        for region in REGIONS.iter() {
+            assert_eq!(*region, Region::from_str(region).unwrap().name());
+        }
+    }
+
+    #[test]
+    fn test_complete() {
+        let groups = |name: &str| match name {
+            "group" => vec![
+                "/aws/lambda/api".to_string(),
+                "/aws/lambda/auth".to_string(),
+                "/ecs/web".to_string(),
+            ],
+            _ => Vec::new(),
+        };
+        let complete = |input| complete(input, groups).map(|c| (c.input, c.candidates.len()));
+
+        assert_eq!(Some(("profile ".to_string(), 1)), complete("pro"));
+        assert_eq!(Some(("pr".to_string(), 2)), complete(":p"));
+        assert_eq!(
+            Some(("group /aws/lambda/a".to_string(), 2)),
+            complete("group /aws")
+        );
+        assert_eq!(
+            Some(("group /ecs/web".to_string(), 1)),
+            complete("group /e")
+        );
+        // matched anywhere, keeping what is typed.
+        assert_eq!(
+            Some(("group /aws/lambda/api".to_string(), 1)),
+            complete("group api")
+        );
+        assert_eq!(
+            Some(("group lambda".to_string(), 2)),
+            complete("group lambda")
+        );
+        assert_eq!(None, complete("group nothing"));
+        assert_eq!(None, complete("zzz"));
+    }
+
+    #[test]
+    fn test_common_prefix() {
+        assert_eq!("pr", common_prefix(&["prd", "prod", "pre"]));
+        assert_eq!("prd", common_prefix(&["prd", "prd-api"]));
+        assert_eq!("", common_prefix(&["prd", "stg"]));
+        assert_eq!("/ログ/", common_prefix(&["/ログ/api", "/ログ/db"]));
+        assert_eq!("/ロ", common_prefix(&["/ログ", "/ロゴ"]));
+        assert_eq!("", common_prefix(&[]));
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = CommandHistory::default();
+        history.push("profile prd");
+        history.push("since 2h");
+        history.push("since 2h");
+
+        assert_eq!(Some("since 2h"), history.older());
+        assert_eq!(Some("profile prd"), history.older());
+        assert_eq!(Some("profile prd"), history.older());
+        assert_eq!(Some("since 2h"), history.newer());
+        assert_eq!(None, history.newer());
+        assert_eq!(None, history.newer());
+        assert_eq!(Some("since 2h"), history.older());
+    }
+}
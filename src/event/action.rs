@@ -1,11 +1,54 @@
-use crate::aws::cwlogs::group::CwlGroup;
+use crate::aws::cwlogs::event::{ContextDirection, CwlEvent, CwlEventPage};
+use crate::aws::cwlogs::group::{
+    CwlGroup, CwlGroupBulkOp, CwlGroupBulkResult, CwlGroupCacheState, CwlGroupDetail,
+    CwlGroupSource, CwlRetention,
+};
+use crate::aws::cwlogs::stream::CwlStream;
 use crate::aws::profile::ProfileName;
+use crate::aws::Arn;
 use crate::preset::Preset;
+use crate::session::SessionId;
 
 #[derive(Debug, Clone)]
 pub enum Action {
     Search(String),
     RequestLogGroups(ProfileName, Option<Preset>),
-    ReceiveLogGroups(Vec<CwlGroup>),
+    ReceiveLogGroups(CwlGroupSource, u64, Vec<CwlGroup>, CwlGroupCacheState),
+    FailLogGroups(CwlGroupSource, u64, String),
+    RequestLogGroupDetail(CwlGroupSource, Box<CwlGroup>),
+    ReceiveLogGroupDetail(Arn, CwlGroupDetail),
+    FailLogGroupDetail(Arn, String),
+    SetLogGroupRetention(CwlGroupSource, Box<CwlGroup>, CwlRetention),
+    ReceiveLogGroupRetention(Box<CwlGroup>),
+    DeleteLogGroup(CwlGroupSource, Box<CwlGroup>),
+    RemoveLogGroup(Box<CwlGroup>),
+    RunBulkOperation(CwlGroupSource, Vec<CwlGroup>, CwlGroupBulkOp),
+    ReceiveBulkResult(Box<CwlGroupBulkResult>),
+    OpenSession(CwlGroupSource, Box<CwlGroup>),
+    ReceiveLogStreams(SessionId, u64, Vec<CwlStream>, bool),
+    FailLogStreams(SessionId, u64, String),
+    DeleteLogStream(SessionId, Box<CwlStream>),
+    RemoveLogStream(SessionId, Box<CwlStream>),
+    ReceiveLogEvents(SessionId, u64, Vec<CwlEvent>, bool),
+    FailLogEvents(SessionId, u64, String),
+    ReceiveEventContext(SessionId, u64, ContextDirection, Box<CwlEventPage>),
+    FailEventContext(SessionId, u64, ContextDirection, String),
     Error(String),
 }
+
+impl Action {
+    /// Returns the session the action belongs to.
+    pub fn session_id(&self) -> Option<SessionId> {
+        match self {
+            Action::ReceiveLogStreams(id, _, _, _)
+            | Action::FailLogStreams(id, _, _)
+            | Action::DeleteLogStream(id, _)
+            | Action::RemoveLogStream(id, _)
+            | Action::ReceiveLogEvents(id, _, _, _)
+            | Action::FailLogEvents(id, _, _)
+            | Action::ReceiveEventContext(id, _, _, _)
+            | Action::FailEventContext(id, _, _, _) => Some(*id),
+            _ => None,
+        }
+    }
+}
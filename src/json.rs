@@ -0,0 +1,82 @@
+mod field;
+mod path;
+mod pretty;
+
+pub use field::{flatten, format_value, JsonField};
+pub use path::{JsonPath, ParseJsonPathError, Segment};
+pub use pretty::{pretty_lines, JsonLine, JsonToken, JsonTokenKind};
+
+use serde_json::Value;
+
+/// Parses the first JSON object or array in the message, which may follow a plain text prefix
+/// like `[INFO]` and the timestamp and request id written by Lambda, and be followed by text.
+pub fn parse_message(message: &str) -> Option<Value> {
+    message.match_indices(['{', '[']).find_map(|(start, _)| {
+        serde_json::Deserializer::from_str(&message[start..])
+            .into_iter::<Value>()
+            .next()
+            .and_then(Result::ok)
+    })
+}
+
+/// Whether the whole message is a JSON object or array, which JSON filter patterns of CloudWatch
+/// Logs can only match.
+pub fn is_json_message(message: &str) -> bool {
+    matches!(
+        serde_json::from_str::<Value>(message.trim()),
+        Ok(v) if v.is_object() || v.is_array()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let expected = json!({"level": "info", "id": 1});
+        assert_eq!(
+            Some(expected.clone()),
+            parse_message(r#" {"level": "info", "id": 1} "#)
+        );
+        assert_eq!(
+            Some(expected),
+            parse_message(
+                "2021-01-02T03:04:05.678Z\tabc\tINFO\t{\"level\": \"info\", \"id\": 1}\n"
+            )
+        );
+        assert_eq!(Some(json!([1, 2])), parse_message("[1, 2]"));
+        assert_eq!(None, parse_message("plain text"));
+        assert_eq!(None, parse_message("broken {\"id\": "));
+    }
+
+    #[test]
+    fn test_parse_message_after_brackets() {
+        let expected = json!({"level": "info", "id": 1});
+        assert_eq!(
+            Some(expected.clone()),
+            parse_message(
+                "[INFO]\t2021-01-02T03:04:05.678Z\tabc\t{\"level\": \"info\", \"id\": 1}\n"
+            )
+        );
+        assert_eq!(
+            Some(expected.clone()),
+            parse_message(r#"[ERROR] {"level": "info", "id": 1}"#)
+        );
+        assert_eq!(
+            Some(expected),
+            parse_message(r#"{"level": "info", "id": 1} (took 3 ms)"#)
+        );
+        assert_eq!(None, parse_message("[INFO] started"));
+    }
+
+    #[test]
+    fn test_is_json_message() {
+        assert!(is_json_message(r#" {"id": 1} "#));
+        assert!(!is_json_message(r#"[INFO] {"id": 1}"#));
+        assert!(!is_json_message(r#"{"id": 1} trailing"#));
+        assert!(!is_json_message("1"));
+    }
+}
@@ -0,0 +1,112 @@
+use serde_json::Value;
+
+use crate::json::path::JsonPath;
+
+/// A leaf value of a JSON document with its path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonField {
+    pub path: JsonPath,
+    pub value: Value,
+}
+
+impl JsonField {
+    /// Condition of a CloudWatch Logs filter pattern matching the value, like `$.id = "abc"`.
+    pub fn filter_condition(&self) -> Option<String> {
+        let path = &self.path;
+        match &self.value {
+            Value::String(_) | Value::Number(_) => Some(format!("{} = {}", path, self.value)),
+            Value::Bool(true) => Some(format!("{} IS TRUE", path)),
+            Value::Bool(false) => Some(format!("{} IS FALSE", path)),
+            Value::Null => Some(format!("{} IS NULL", path)),
+            Value::Array(_) | Value::Object(_) => None,
+        }
+    }
+}
+
+/// Lists every leaf of the document in order, empty objects and arrays included.
+pub fn flatten(value: &Value) -> Vec<JsonField> {
+    let mut fields = Vec::new();
+    flatten_into(&mut fields, JsonPath::root(), value);
+    fields
+}
+
+fn flatten_into(fields: &mut Vec<JsonField>, path: JsonPath, value: &Value) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, v) in map.iter() {
+                flatten_into(fields, path.key(key), v);
+            }
+        }
+        Value::Array(values) if !values.is_empty() => {
+            for (i, v) in values.iter().enumerate() {
+                flatten_into(fields, path.index(i), v);
+            }
+        }
+        _ => fields.push(JsonField {
+            path,
+            value: value.clone(),
+        }),
+    }
+}
+
+/// Formats the value for a table cell, strings without quotes.
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let value = json!({"a": {"b": 1, "c": [true, null]}, "d": "x", "e": {}});
+        let fields = flatten(&value)
+            .into_iter()
+            .map(|f| (f.path.to_string(), f.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("$.a.b".to_string(), json!(1)),
+                ("$.a.c[0]".to_string(), json!(true)),
+                ("$.a.c[1]".to_string(), json!(null)),
+                ("$.d".to_string(), json!("x")),
+                ("$.e".to_string(), json!({})),
+            ],
+            fields
+        );
+    }
+
+    #[test]
+    fn test_filter_condition() {
+        let field = |value| JsonField {
+            path: JsonPath::root().key("id"),
+            value,
+        };
+        assert_eq!(
+            Some(r#"$.id = "a\"b""#.to_string()),
+            field(json!("a\"b")).filter_condition()
+        );
+        assert_eq!(
+            Some("$.id = 200".to_string()),
+            field(json!(200)).filter_condition()
+        );
+        assert_eq!(
+            Some("$.id IS FALSE".to_string()),
+            field(json!(false)).filter_condition()
+        );
+        assert_eq!(None, field(json!({})).filter_condition());
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!("abc", format_value(&json!("abc")));
+        assert_eq!("1.5", format_value(&json!(1.5)));
+        assert_eq!("[1,2]", format_value(&json!([1, 2])));
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[error("invalid json path `{0}`, expected a path like $.request.id or $.items[0]")]
+pub struct ParseJsonPathError(String);
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Path to a value in a JSON document, written like `$.request.id`.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct JsonPath(Vec<Segment>);
+
+impl JsonPath {
+    pub fn root() -> Self {
+        JsonPath::default()
+    }
+
+    pub fn key(&self, key: &str) -> JsonPath {
+        self.child(Segment::Key(key.to_string()))
+    }
+
+    pub fn index(&self, index: usize) -> JsonPath {
+        self.child(Segment::Index(index))
+    }
+
+    fn child(&self, segment: Segment) -> JsonPath {
+        let mut segments = self.0.clone();
+        segments.push(segment);
+        JsonPath(segments)
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    pub fn lookup<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        self.0.iter().try_fold(value, |v, segment| match segment {
+            Segment::Key(key) => v.get(key.as_str()),
+            Segment::Index(i) => v.get(*i),
+        })
+    }
+}
+
+fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$")?;
+        for segment in self.0.iter() {
+            match segment {
+                Segment::Key(key) if is_identifier(key) => write!(f, ".{}", key)?,
+                Segment::Key(key) => write!(f, "[{}]", Value::String(key.clone()))?,
+                Segment::Index(i) => write!(f, "[{}]", i)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = ParseJsonPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseJsonPathError(s.to_string());
+        let mut rest = s.trim().strip_prefix('$').ok_or_else(error)?;
+        let mut segments = Vec::new();
+        while !rest.is_empty() {
+            if let Some(r) = rest.strip_prefix('.') {
+                let end = r.find(['.', '[']).unwrap_or(r.len());
+                if !is_identifier(&r[..end]) {
+                    return Err(error());
+                }
+                segments.push(Segment::Key(r[..end].to_string()));
+                rest = &r[end..];
+            } else if let Some(r) = rest.strip_prefix("[\"") {
+                // the key is a JSON string, find its closing quote skipping escaped ones.
+                let mut escaped = false;
+                let end = r
+                    .char_indices()
+                    .find(|&(_, c)| {
+                        let close = c == '"' && !escaped;
+                        escaped = c == '\\' && !escaped;
+                        close
+                    })
+                    .map(|(i, _)| i)
+                    .ok_or_else(error)?;
+                let key = serde_json::from_str::<String>(&rest[1..end + 3]).map_err(|_| error())?;
+                segments.push(Segment::Key(key));
+                rest = r[end + 1..].strip_prefix(']').ok_or_else(error)?;
+            } else if let Some(r) = rest.strip_prefix('[') {
+                let end = r.find(']').ok_or_else(error)?;
+                let index = r[..end].parse::<usize>().map_err(|_| error())?;
+                segments.push(Segment::Index(index));
+                rest = &r[end + 1..];
+            } else {
+                return Err(error());
+            }
+        }
+        Ok(JsonPath(segments))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let path = JsonPath::root().key("request").key("id");
+        assert_eq!("$.request.id", path.to_string());
+        let path = JsonPath::root().key("items").index(0).key("a b");
+        assert_eq!(r#"$.items[0]["a b"]"#, path.to_string());
+        assert_eq!("$", JsonPath::root().to_string());
+    }
+
+    #[test]
+    fn test_from_str() {
+        for s in &["$", "$.request.id", r#"$.items[0]["a \"b\""].c"#, "$[1][2]"] {
+            assert_eq!(*s, JsonPath::from_str(s).unwrap().to_string());
+        }
+        assert!(JsonPath::from_str("request.id").is_err());
+        assert!(JsonPath::from_str("$.").is_err());
+        assert!(JsonPath::from_str("$[x]").is_err());
+        assert!(JsonPath::from_str(r#"$["a"#).is_err());
+    }
+
+    #[test]
+    fn test_lookup() {
+        let value = json!({"request": {"id": "abc", "tags": ["x", "y"]}});
+        let path = JsonPath::from_str("$.request.tags[1]").unwrap();
+        assert_eq!(Some(&json!("y")), path.lookup(&value));
+        let path = JsonPath::from_str("$.request.name").unwrap();
+        assert_eq!(None, path.lookup(&value));
+        assert_eq!(Some(&value), JsonPath::root().lookup(&value));
+    }
+}
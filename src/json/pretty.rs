@@ -0,0 +1,196 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+
+use crate::json::path::JsonPath;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum JsonTokenKind {
+    Key,
+    String,
+    Number,
+    Literal,
+    Punct,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct JsonToken {
+    pub kind: JsonTokenKind,
+    pub text: String,
+}
+
+impl JsonToken {
+    fn new<S: Into<String>>(kind: JsonTokenKind, text: S) -> Self {
+        JsonToken {
+            kind,
+            text: text.into(),
+        }
+    }
+}
+
+/// A line of pretty-printed JSON.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct JsonLine {
+    pub depth: usize,
+    pub tokens: Vec<JsonToken>,
+    /// Path of the object or array opened on the line, which can be collapsed.
+    pub container: Option<JsonPath>,
+    pub collapsed: bool,
+}
+
+impl JsonLine {
+    pub fn text(&self) -> String {
+        self.tokens.iter().map(|t| t.text.as_str()).collect()
+    }
+}
+
+/// Pretty-prints the value in lines, showing the collapsed objects and arrays on a single line.
+pub fn pretty_lines(value: &Value, collapsed: &BTreeSet<JsonPath>) -> Vec<JsonLine> {
+    let mut printer = Printer {
+        lines: Vec::new(),
+        collapsed,
+    };
+    printer.push(&JsonPath::root(), None, value, 0, false);
+    printer.lines
+}
+
+struct Printer<'c> {
+    lines: Vec<JsonLine>,
+    collapsed: &'c BTreeSet<JsonPath>,
+}
+
+impl Printer<'_> {
+    fn push(
+        &mut self,
+        path: &JsonPath,
+        key: Option<&str>,
+        value: &Value,
+        depth: usize,
+        comma: bool,
+    ) {
+        let mut tokens = Vec::new();
+        if let Some(key) = key {
+            tokens.push(JsonToken::new(JsonTokenKind::Key, quote(key)));
+            tokens.push(JsonToken::new(JsonTokenKind::Punct, ": "));
+        }
+        let comma_token = || JsonToken::new(JsonTokenKind::Punct, if comma { "," } else { "" });
+
+        let (open, close, len) = match value {
+            Value::Object(map) if !map.is_empty() => ("{", "}", map.len()),
+            Value::Array(values) if !values.is_empty() => ("[", "]", values.len()),
+            _ => {
+                tokens.push(scalar_token(value));
+                tokens.push(comma_token());
+                return self.lines.push(JsonLine {
+                    depth,
+                    tokens,
+                    container: None,
+                    collapsed: false,
+                });
+            }
+        };
+
+        let collapsed = self.collapsed.contains(path);
+        if collapsed {
+            let unit = if value.is_object() { "keys" } else { "items" };
+            let summary = format!("{} … {} {} {}", open, len, unit, close);
+            tokens.push(JsonToken::new(JsonTokenKind::Punct, summary));
+            tokens.push(comma_token());
+        } else {
+            tokens.push(JsonToken::new(JsonTokenKind::Punct, open));
+        }
+        self.lines.push(JsonLine {
+            depth,
+            tokens,
+            container: Some(path.clone()),
+            collapsed,
+        });
+        if collapsed {
+            return;
+        }
+
+        match value {
+            Value::Object(map) => {
+                for (i, (k, v)) in map.iter().enumerate() {
+                    self.push(&path.key(k), Some(k), v, depth + 1, i + 1 < len);
+                }
+            }
+            Value::Array(values) => {
+                for (i, v) in values.iter().enumerate() {
+                    self.push(&path.index(i), None, v, depth + 1, i + 1 < len);
+                }
+            }
+            _ => (),
+        }
+        self.lines.push(JsonLine {
+            depth,
+            tokens: vec![JsonToken::new(JsonTokenKind::Punct, close), comma_token()],
+            container: None,
+            collapsed: false,
+        });
+    }
+}
+
+fn quote(s: &str) -> String {
+    Value::String(s.to_string()).to_string()
+}
+
+fn scalar_token(value: &Value) -> JsonToken {
+    let kind = match value {
+        Value::String(_) => JsonTokenKind::String,
+        Value::Number(_) => JsonTokenKind::Number,
+        Value::Bool(_) | Value::Null => JsonTokenKind::Literal,
+        Value::Object(_) | Value::Array(_) => JsonTokenKind::Punct,
+    };
+    JsonToken::new(kind, value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn texts(lines: &[JsonLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| format!("{}{}", "  ".repeat(l.depth), l.text()))
+            .collect()
+    }
+
+    #[test]
+    fn test_pretty_lines() {
+        let value = json!({"a": {"b": 1, "c": [true, null]}, "d": "x", "e": {}});
+        let lines = pretty_lines(&value, &BTreeSet::new());
+        assert_eq!(
+            vec![
+                "{",
+                "  \"a\": {",
+                "    \"b\": 1,",
+                "    \"c\": [",
+                "      true,",
+                "      null",
+                "    ]",
+                "  },",
+                "  \"d\": \"x\",",
+                "  \"e\": {}",
+                "}",
+            ],
+            texts(&lines)
+        );
+        assert_eq!(Some(JsonPath::root().key("a")), lines[1].container);
+        assert_eq!(None, lines[2].container);
+    }
+
+    #[test]
+    fn test_collapsed() {
+        let value = json!({"a": {"b": 1, "c": [true, null]}, "d": "x"});
+        let collapsed = [JsonPath::root().key("a")].iter().cloned().collect();
+        let lines = pretty_lines(&value, &collapsed);
+        assert_eq!(
+            vec!["{", "  \"a\": { … 2 keys },", "  \"d\": \"x\"", "}"],
+            texts(&lines)
+        );
+        assert!(lines[1].collapsed);
+    }
+}
@@ -0,0 +1,355 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use rusoto_core::Region;
+use thiserror::Error;
+
+use crate::aws::cwlogs::console::encode_component;
+use crate::aws::cwlogs::event::CwlEvent;
+use crate::aws::profile::ProfileName;
+use crate::session::Session;
+use crate::time::{ParseTimeRangeError, TimeRangeSpec, TimeZoneChoice};
+
+/// Prefix of links to a view, like `razy-cwlogs://open?group=%2Faws%2Flambda%2Ffoo&since=1h`.
+pub const URI_PREFIX: &str = "razy-cwlogs://open";
+
+/// Options taking a value, the same as the parameters of a link.
+const OPTIONS: [&str; 6] = ["profile", "region", "group", "stream", "since", "filter"];
+
+pub const USAGE: &str = "\
+Usage: razy-cwlogs [OPTIONS] [razy-cwlogs://open?...]
+
+Options:
+    --profile <NAME>     profile of the shared AWS config
+    --region <REGION>    region, over the one of the profile
+    --group <NAME>       log group to open
+    --stream <NAME>      log stream whose events are listed
+    --since <RANGE>      time range of the events, like 1h or 2021-01-02T09:00..10:30
+    --filter <PATTERN>   filter pattern of the events, like ERROR
+    -h, --help           print this help
+    -V, --version        print the version";
+
+#[derive(Debug, Error)]
+pub enum LaunchError {
+    #[error("unknown argument `{0}`, see --help")]
+    UnknownArgument(String),
+
+    #[error("`{0}` needs a value")]
+    MissingValue(String),
+
+    #[error("invalid region `{0}`")]
+    InvalidRegion(String),
+
+    #[error("invalid link `{0}`, links start with {}", URI_PREFIX)]
+    InvalidUri(String),
+
+    #[error("invalid time range `{range}`: {source}")]
+    TimeRange {
+        range: String,
+        source: ParseTimeRangeError,
+    },
+
+    #[error("`{0}` needs a group")]
+    NoGroup(&'static str),
+}
+
+/// What to do on startup, told by the arguments.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum LaunchCommand {
+    Help,
+    Version,
+    Open(LaunchTarget),
+}
+
+/// A view to open on startup, skipping the presets and profiles.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct LaunchTarget {
+    pub profile: Option<ProfileName>,
+    pub region: Option<Region>,
+    pub group_name: Option<String>,
+    pub stream_name: Option<String>,
+    /// Time range of the events as typed, read in the zone of the session.
+    pub since: Option<String>,
+    pub filter: Option<String>,
+    /// Event to show the lines around once the events are open, like a bookmarked one.
+    /// Links do not carry it.
+    pub context: Option<Box<CwlEvent>>,
+}
+
+impl LaunchTarget {
+    /// The view of the session, to be opened again from its link.
+    pub fn of_session(session: &Session) -> Self {
+        LaunchTarget {
+            profile: Some(session.source.profile.clone()),
+            region: Some(session.source.region.clone()),
+            group_name: Some(session.group.group_name.clone()),
+            stream_name: session.events_stream.clone(),
+            since: Some(session.time_range.expression()),
+            filter: session.filter_pattern(),
+            context: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == LaunchTarget::default()
+    }
+
+    /// Profile of the target, `AWS_PROFILE` or `default` if not given.
+    pub fn profile_or_default(&self) -> ProfileName {
+        self.profile.clone().unwrap_or_else(|| {
+            ProfileName::from(std::env::var("AWS_PROFILE").unwrap_or_else(|_| "default".into()))
+        })
+    }
+
+    fn set(&mut self, key: &str, value: String) -> Result<(), LaunchError> {
+        match key {
+            "profile" => self.profile = Some(ProfileName::from(value)),
+            "region" => {
+                let region =
+                    Region::from_str(&value).map_err(|_| LaunchError::InvalidRegion(value))?;
+                self.region = Some(region);
+            }
+            "group" => self.group_name = Some(value),
+            "stream" => self.stream_name = Some(value),
+            "since" => {
+                let zone = TimeZoneChoice::default();
+                if let Err(source) = TimeRangeSpec::parse(&value, &zone, &Utc::now()) {
+                    return Err(LaunchError::TimeRange {
+                        range: value,
+                        source,
+                    });
+                }
+                self.since = Some(value);
+            }
+            "filter" => self.filter = Some(value),
+            _ => return Err(LaunchError::UnknownArgument(key.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Options of a view need its group, the profile and region alone only list groups.
+    fn validate(self) -> Result<Self, LaunchError> {
+        if self.group_name.is_none() {
+            let option = match &self {
+                t if t.stream_name.is_some() => Some("stream"),
+                t if t.since.is_some() => Some("since"),
+                t if t.filter.is_some() => Some("filter"),
+                _ => None,
+            };
+            if let Some(option) = option {
+                return Err(LaunchError::NoGroup(option));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Reads a link like `razy-cwlogs://open?profile=prd&group=%2Faws%2Flambda%2Ffoo&since=1h`.
+    pub fn from_uri(uri: &str) -> Result<Self, LaunchError> {
+        let invalid = || LaunchError::InvalidUri(uri.to_string());
+        let query = match uri.strip_prefix(URI_PREFIX) {
+            Some("") => "",
+            Some(rest) => rest.strip_prefix('?').ok_or_else(invalid)?,
+            None => return Err(invalid()),
+        };
+
+        let mut target = LaunchTarget::default();
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (key, value) = match pair.find('=') {
+                Some(i) => (&pair[..i], &pair[i + 1..]),
+                None => return Err(invalid()),
+            };
+            let value = decode_component(value).ok_or_else(invalid)?;
+            target.set(key, value)?;
+        }
+        target.validate()
+    }
+
+    /// A link to the target, in the order of the options.
+    pub fn to_uri(&self) -> String {
+        let params = [
+            ("profile", self.profile.as_ref().map(ToString::to_string)),
+            ("region", self.region.as_ref().map(|r| r.name().to_string())),
+            ("group", self.group_name.clone()),
+            ("stream", self.stream_name.clone()),
+            ("since", self.since.clone()),
+            ("filter", self.filter.clone()),
+        ];
+        let query = params
+            .iter()
+            .filter_map(|(key, value)| {
+                value
+                    .as_ref()
+                    .map(|v| format!("{}={}", key, encode_component(v, '%')))
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        if query.is_empty() {
+            URI_PREFIX.to_string()
+        } else {
+            format!("{}?{}", URI_PREFIX, query)
+        }
+    }
+}
+
+/// Decodes `%XX` and `+` of a query value, `None` if it is not valid UTF-8.
+fn decode_component(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = s.get(i + 1..i + 3)?;
+                decoded.push(u8::from_str_radix(hex, 16).ok()?);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            b => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Reads the arguments after the program name, like `--group /aws/lambda/foo --since 1h`
+/// or a link to a view.
+pub fn parse_args<I>(args: I) -> Result<LaunchCommand, LaunchError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut target = LaunchTarget::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(LaunchCommand::Help),
+            "-V" | "--version" => return Ok(LaunchCommand::Version),
+            uri if uri.starts_with("razy-cwlogs:") => {
+                let from_uri = LaunchTarget::from_uri(uri)?;
+                target = LaunchTarget {
+                    profile: target.profile.or(from_uri.profile),
+                    region: target.region.or(from_uri.region),
+                    group_name: target.group_name.or(from_uri.group_name),
+                    stream_name: target.stream_name.or(from_uri.stream_name),
+                    since: target.since.or(from_uri.since),
+                    filter: target.filter.or(from_uri.filter),
+                    context: None,
+                };
+            }
+            option if option.starts_with("--") => {
+                let (key, value) = match option.find('=') {
+                    Some(i) => (&option[2..i], Some(option[i + 1..].to_string())),
+                    None => (&option[2..], None),
+                };
+                if !OPTIONS.contains(&key) {
+                    return Err(LaunchError::UnknownArgument(arg));
+                }
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| LaunchError::MissingValue(arg.clone()))?;
+                target.set(key, value)?;
+            }
+            _ => return Err(LaunchError::UnknownArgument(arg)),
+        }
+    }
+    Ok(LaunchCommand::Open(target.validate()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<LaunchCommand, LaunchError> {
+        parse_args(s.split_whitespace().map(str::to_string))
+    }
+
+    fn target() -> LaunchTarget {
+        LaunchTarget {
+            profile: Some(ProfileName::from("prd")),
+            region: Some(Region::ApNortheast1),
+            group_name: Some("/aws/lambda/foo".to_string()),
+            stream_name: None,
+            since: Some("1h".to_string()),
+            filter: Some("ERROR".to_string()),
+            context: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            LaunchCommand::Open(target()),
+            args(
+                "--profile prd --region ap-northeast-1 --group /aws/lambda/foo \
+                 --since 1h --filter=ERROR"
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            LaunchCommand::Open(LaunchTarget::default()),
+            args("").unwrap()
+        );
+        assert_eq!(LaunchCommand::Help, args("--group a -h").unwrap());
+
+        assert!(matches!(args("--group"), Err(LaunchError::MissingValue(_))));
+        assert!(matches!(
+            args("--bogus"),
+            Err(LaunchError::UnknownArgument(a)) if a == "--bogus"
+        ));
+        assert!(matches!(
+            args("--groups a"),
+            Err(LaunchError::UnknownArgument(a)) if a == "--groups"
+        ));
+        assert!(matches!(
+            args("--region mars-1"),
+            Err(LaunchError::InvalidRegion(_))
+        ));
+        assert!(matches!(
+            args("--group a --since tomorrow"),
+            Err(LaunchError::TimeRange { .. })
+        ));
+        assert!(matches!(
+            args("--filter ERROR"),
+            Err(LaunchError::NoGroup("filter"))
+        ));
+    }
+
+    #[test]
+    fn test_uri() {
+        let uri = target().to_uri();
+        assert_eq!(
+            "razy-cwlogs://open?profile=prd&region=ap-northeast-1\
+             &group=%2Faws%2Flambda%2Ffoo&since=1h&filter=ERROR",
+            uri
+        );
+        assert_eq!(target(), LaunchTarget::from_uri(&uri).unwrap());
+
+        let pattern = LaunchTarget {
+            filter: Some("{ $.level = \"error\" }".to_string()),
+            ..target()
+        };
+        assert_eq!(pattern, LaunchTarget::from_uri(&pattern.to_uri()).unwrap());
+
+        // options override the link.
+        assert_eq!(
+            LaunchCommand::Open(LaunchTarget {
+                profile: Some(ProfileName::from("stg")),
+                ..target()
+            }),
+            args(&format!("{} --profile stg", uri)).unwrap()
+        );
+
+        assert!(matches!(
+            LaunchTarget::from_uri("razy-cwlogs://open?group=%zz"),
+            Err(LaunchError::InvalidUri(_))
+        ));
+        assert!(matches!(
+            LaunchTarget::from_uri("razy-cwlogs://view?group=a"),
+            Err(LaunchError::InvalidUri(_))
+        ));
+    }
+}
@@ -1,10 +1,17 @@
 pub mod app;
 pub mod aws;
+pub mod bookmark;
+pub mod clipboard;
 pub mod collection;
+pub mod command;
 pub mod event;
+pub mod json;
+pub mod launch;
+pub mod matcher;
 pub mod preset;
 pub mod query;
 pub mod session;
 pub mod size;
 pub mod terminal;
+pub mod time;
 pub mod ui;
@@ -0,0 +1,379 @@
+use std::cmp::Reverse;
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_BOUNDARY: i64 = 10;
+const BONUS_CAMEL_CASE: i64 = 8;
+const BONUS_CONSECUTIVE: i64 = 12;
+const PENALTY_GAP: i64 = 1;
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '/' | '-' | '_' | '.' | ':' | ' ')
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Match {
+    pub score: i64,
+    /// char indices of the matched characters, in ascending order.
+    pub indices: Vec<usize>,
+}
+
+impl Match {
+    pub fn new(score: i64, indices: Vec<usize>) -> Match {
+        Match { score, indices }
+    }
+}
+
+pub trait Matcher<T: ?Sized> {
+    fn matches(&self, item: &T) -> Option<Match>;
+}
+
+/// Matches texts containing all characters of the pattern in the same order.
+///
+/// Uses smart case: the match is case sensitive only if the pattern has an uppercase character.
+#[derive(Debug, Clone)]
+pub struct FuzzyMatcher {
+    pattern: Vec<char>,
+    case_sensitive: bool,
+}
+
+impl FuzzyMatcher {
+    pub fn new(pattern: &str) -> FuzzyMatcher {
+        FuzzyMatcher {
+            pattern: pattern.chars().collect(),
+            case_sensitive: pattern.chars().any(char::is_uppercase),
+        }
+    }
+
+    fn char_eq(&self, a: char, b: char) -> bool {
+        if self.case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    }
+}
+
+fn bonus(text: &[char], i: usize) -> i64 {
+    if i == 0 {
+        return BONUS_BOUNDARY;
+    }
+
+    let (prev, cur) = (text[i - 1], text[i]);
+    if is_separator(prev) {
+        BONUS_BOUNDARY
+    } else if prev.is_lowercase() && cur.is_uppercase() {
+        BONUS_CAMEL_CASE
+    } else {
+        0
+    }
+}
+
+impl Matcher<str> for FuzzyMatcher {
+    fn matches(&self, text: &str) -> Option<Match> {
+        if self.pattern.is_empty() {
+            return Some(Match::new(0, Vec::new()));
+        }
+
+        let text = text.chars().collect::<Vec<_>>();
+        let (m, n) = (self.pattern.len(), text.len());
+        if m > n {
+            return None;
+        }
+
+        // scores[i][j]: best score of matching pattern[..=i] with pattern[i] placed at text[j].
+        let mut scores: Vec<Vec<Option<i64>>> = vec![vec![None; n]; m];
+        let mut prevs = vec![vec![0_usize; n]; m];
+
+        for j in 0..n {
+            if self.char_eq(self.pattern[0], text[j]) {
+                scores[0][j] = Some(SCORE_MATCH + bonus(&text, j));
+            }
+        }
+
+        for i in 1..m {
+            for j in i..n {
+                if !self.char_eq(self.pattern[i], text[j]) {
+                    continue;
+                }
+
+                let best = (i - 1..j)
+                    .filter_map(|k| scores[i - 1][k].map(|s| (k, s)))
+                    .map(|(k, s)| {
+                        let s = if k + 1 == j {
+                            s + BONUS_CONSECUTIVE
+                        } else {
+                            s - PENALTY_GAP * (j - k - 1) as i64
+                        };
+                        (k, s)
+                    })
+                    .max_by(|(k1, s1), (k2, s2)| s1.cmp(s2).then(k1.cmp(k2)));
+
+                if let Some((k, s)) = best {
+                    scores[i][j] = Some(s + SCORE_MATCH + bonus(&text, j));
+                    prevs[i][j] = k;
+                }
+            }
+        }
+
+        let (mut j, score) = scores[m - 1]
+            .iter()
+            .enumerate()
+            .filter_map(|(j, s)| s.map(|s| (j, s)))
+            .max_by(|(j1, s1), (j2, s2)| s1.cmp(s2).then(j2.cmp(j1)))?;
+
+        let mut indices = vec![0; m];
+        for i in (0..m).rev() {
+            indices[i] = j;
+            j = prevs[i][j];
+        }
+
+        Some(Match::new(score, indices))
+    }
+}
+
+/// Matches whole texts against a glob pattern, where `*` matches any characters and `?` matches
+/// a single character.
+///
+/// Uses smart case like `FuzzyMatcher`.
+#[derive(Debug, Clone)]
+pub struct PatternMatcher {
+    pattern: Vec<char>,
+    case_sensitive: bool,
+}
+
+impl PatternMatcher {
+    pub fn new(pattern: &str) -> PatternMatcher {
+        PatternMatcher {
+            pattern: pattern.chars().collect(),
+            case_sensitive: pattern.chars().any(char::is_uppercase),
+        }
+    }
+
+    pub fn is_pattern(s: &str) -> bool {
+        s.contains(&['*', '?'][..])
+    }
+
+    /// Returns the literal part before the first wildcard.
+    pub fn literal_prefix(&self) -> String {
+        self.pattern
+            .iter()
+            .take_while(|&&c| c != '*' && c != '?')
+            .collect()
+    }
+
+    fn char_eq(&self, a: char, b: char) -> bool {
+        if self.case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    }
+}
+
+impl Matcher<str> for PatternMatcher {
+    fn matches(&self, text: &str) -> Option<Match> {
+        let text = text.chars().collect::<Vec<_>>();
+        let pattern = &self.pattern;
+        let mut indices = Vec::new();
+
+        // (pattern position of the last `*`, text position it matches up to, matched indices)
+        let mut star: Option<(usize, usize, usize)> = None;
+        let (mut p, mut t) = (0, 0);
+        while t < text.len() {
+            // a `*` is a wildcard even where the text has a `*`.
+            if p < pattern.len() && pattern[p] == '*' {
+                star = Some((p, t, indices.len()));
+                p += 1;
+            } else if p < pattern.len() && (pattern[p] == '?' || self.char_eq(pattern[p], text[t]))
+            {
+                indices.push(t);
+                p += 1;
+                t += 1;
+            } else if let Some((star_p, star_t, len)) = star {
+                star = Some((star_p, star_t + 1, len));
+                indices.truncate(len);
+                p = star_p + 1;
+                t = star_t + 1;
+            } else {
+                return None;
+            }
+        }
+
+        if pattern[p..].iter().all(|&c| c == '*') {
+            Some(Match::new(0, indices))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Matched<'a, T> {
+    pub item: &'a T,
+    pub matched: Option<&'a Match>,
+}
+
+impl<'a, T> Matched<'a, T> {
+    pub fn indices(&self) -> &'a [usize] {
+        self.matched.map(|m| m.indices.as_slice()).unwrap_or(&[])
+    }
+}
+
+/// Position of a matched item, kept by stores so that items are matched once per query.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MatchedIndex {
+    pub index: usize,
+    pub matched: Option<Match>,
+}
+
+impl MatchedIndex {
+    pub fn of<'a, T>(&'a self, items: &'a [T]) -> Matched<'a, T> {
+        Matched {
+            item: &items[self.index],
+            matched: self.matched.as_ref(),
+        }
+    }
+
+    fn score(&self) -> i64 {
+        self.matched.as_ref().map(|m| m.score).unwrap_or(0)
+    }
+}
+
+/// Filters items by the matcher, keeping their order.
+pub fn filter<'a, T, K, M, F>(
+    items: impl Iterator<Item = &'a T>,
+    matcher: Option<&M>,
+    key: F,
+) -> Vec<MatchedIndex>
+where
+    T: 'a,
+    K: ?Sized,
+    M: Matcher<K>,
+    F: Fn(&T) -> &K,
+{
+    items
+        .enumerate()
+        .filter_map(|(index, item)| match matcher {
+            Some(matcher) => matcher.matches(key(item)).map(|m| MatchedIndex {
+                index,
+                matched: Some(m),
+            }),
+            None => Some(MatchedIndex {
+                index,
+                matched: None,
+            }),
+        })
+        .collect()
+}
+
+/// Filters items by the matcher and ranks them by score. Items with the same score keep their order.
+pub fn rank<'a, T, K, M, F>(
+    items: impl Iterator<Item = &'a T>,
+    matcher: Option<&M>,
+    key: F,
+) -> Vec<MatchedIndex>
+where
+    T: 'a,
+    K: ?Sized,
+    M: Matcher<K>,
+    F: Fn(&T) -> &K,
+{
+    let mut matched = filter(items, matcher, key);
+    if matcher.is_some() {
+        matched.sort_by_key(|m| Reverse(m.score()));
+    }
+    matched
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(pattern: &str, text: &str) -> Option<Vec<usize>> {
+        FuzzyMatcher::new(pattern).matches(text).map(|m| m.indices)
+    }
+
+    fn score(pattern: &str, text: &str) -> i64 {
+        FuzzyMatcher::new(pattern).matches(text).unwrap().score
+    }
+
+    #[test]
+    fn test_subsequence() {
+        assert_eq!(Some(vec![]), indices("", "project-prd-api"));
+        assert_eq!(Some(vec![0, 1, 2]), indices("pro", "project-prd-api"));
+        assert_eq!(
+            Some(vec![8, 9, 10, 12, 13, 14]),
+            indices("prdapi", "project-prd-api")
+        );
+        assert_eq!(None, indices("prdx", "project-prd-api"));
+        assert_eq!(None, indices("ipa", "api"));
+        assert_eq!(None, indices("long-pattern", "long"));
+    }
+
+    #[test]
+    fn test_smart_case() {
+        assert_eq!(Some(vec![5, 6, 7]), indices("api", "/aws/API"));
+        assert_eq!(Some(vec![5, 6, 7]), indices("API", "/aws/API"));
+        assert_eq!(None, indices("API", "/aws/api"));
+        assert_eq!(Some(vec![0, 1]), indices("Ab", "AbAB"));
+    }
+
+    #[test]
+    fn test_score() {
+        // consecutive matches are better than scattered ones.
+        assert!(score("api", "my-api") > score("api", "a-p-i"));
+
+        // matches at word boundaries are better than matches inside words.
+        assert!(score("api", "my-api") > score("api", "rapid"));
+
+        // shorter gaps are better.
+        assert!(score("ab", "a-b") > score("ab", "a---b"));
+    }
+
+    #[test]
+    fn test_pattern() {
+        let indices = |pattern: &str, text: &str| {
+            PatternMatcher::new(pattern)
+                .matches(text)
+                .map(|m| m.indices)
+        };
+
+        assert_eq!(Some(vec![0, 1]), indices("ab", "ab"));
+        assert_eq!(None, indices("ab", "abc"));
+        assert_eq!(Some(vec![0, 1]), indices("ab*", "abc"));
+        assert_eq!(Some(vec![0, 1, 2]), indices("a?c", "abc"));
+        assert_eq!(
+            Some(vec![0, 1, 2, 3, 4, 12, 13, 14]),
+            indices("/aws/*api", "/aws/lambda/api")
+        );
+        assert_eq!(Some(vec![0, 2]), indices("a*b*", "aabbb"));
+        assert_eq!(Some(vec![]), indices("*", ""));
+        assert_eq!(None, indices("/aws/*api", "/aws/lambda/apis"));
+        assert_eq!(Some(vec![0, 1]), indices("AB*", "ABc"));
+        assert_eq!(None, indices("AB*", "abc"));
+        assert_eq!(Some(vec![0, 3]), indices("a*b", "a*xb"));
+        assert_eq!(Some(vec![0]), indices("a*", "a*"));
+
+        assert!(PatternMatcher::is_pattern("/aws/*"));
+        assert!(!PatternMatcher::is_pattern("/aws/"));
+        assert_eq!("/aws/", PatternMatcher::new("/aws/*api").literal_prefix());
+        assert_eq!("", PatternMatcher::new("*api").literal_prefix());
+    }
+
+    #[test]
+    fn test_rank() {
+        let items = vec!["rapid", "a-p-i", "project-prd-api", "lambda"];
+        let matcher = FuzzyMatcher::new("api");
+        let ranked = rank(items.iter(), Some(&matcher), |s| *s)
+            .iter()
+            .map(|m| *m.of(&items).item)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["project-prd-api", "a-p-i", "rapid"], ranked);
+
+        let ranked = rank(items.iter(), None::<&FuzzyMatcher>, |s| *s)
+            .iter()
+            .map(|m| *m.of(&items).item)
+            .collect::<Vec<_>>();
+        assert_eq!(items, ranked);
+    }
+}
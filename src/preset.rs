@@ -1,10 +1,11 @@
 use std::cmp::Ordering;
 use std::fmt;
 
-use crate::collection::store::Store;
+use rusoto_logs::DescribeLogGroupsRequest;
+
 use crate::collection::{AsStr, Length};
+use crate::matcher::{rank, Matched, MatchedIndex};
 use crate::query::Query;
-use rusoto_logs::DescribeLogGroupsRequest;
 
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct PresetName(String);
@@ -74,20 +75,21 @@ impl Into<DescribeLogGroupsRequest> for Preset {
 
 #[derive(Debug)]
 pub struct PresetStore {
-    presets: Store<(usize, Preset)>,
+    presets: Vec<Preset>,
     query: Option<Query>,
+    matched: Vec<MatchedIndex>,
     label: String,
 }
 
 impl PresetStore {
     pub fn insert(&mut self, preset: Preset) {
-        self.presets.insert((self.presets.len() + 1, preset));
+        self.presets.push(preset);
+        self.update_matched();
     }
 
     pub fn extend(&mut self, presets: impl Iterator<Item = Preset>) {
-        let offset = self.presets.len() + 1;
-        self.presets
-            .extend(presets.enumerate().map(|(i, p)| (i + offset, p)));
+        self.presets.extend(presets);
+        self.update_matched();
     }
 
     pub fn set_query(&mut self, query: Option<Query>) {
@@ -97,6 +99,13 @@ impl PresetStore {
         } else {
             default_label().to_string()
         };
+        self.update_matched();
+    }
+
+    /// Ranks the presets once, when the query or the presets change.
+    fn update_matched(&mut self) {
+        let matcher = self.query.as_ref().map(Query::matcher);
+        self.matched = rank(self.presets.iter(), matcher.as_ref(), |p| p.name.as_str());
     }
 
     pub fn label(&self) -> &str {
@@ -104,19 +113,30 @@ impl PresetStore {
     }
 
     pub fn iter(&self) -> impl Iterator<Item = &Preset> {
-        let q = self.query.clone();
-        self.presets
-            .iter()
-            .filter(move |(_, p)| preset_matches(p, q.as_ref()))
-            .map(|(_, p)| p)
+        self.matched.iter().map(move |m| &self.presets[m.index])
+    }
+
+    /// The preset at the position in the ranked presets.
+    pub fn get(&self, i: usize) -> Option<&Preset> {
+        self.matched.get(i).map(|m| &self.presets[m.index])
+    }
+
+    /// Every preset, regardless of the query.
+    pub fn all(&self) -> impl Iterator<Item = &Preset> {
+        self.presets.iter()
+    }
+
+    pub fn matched(&self) -> Vec<Matched<'_, Preset>> {
+        self.matched.iter().map(|m| m.of(&self.presets)).collect()
     }
 }
 
 impl Default for PresetStore {
     fn default() -> Self {
         PresetStore {
-            presets: Store::default(),
+            presets: Vec::new(),
             query: None,
+            matched: Vec::new(),
             label: default_label().to_string(),
         }
     }
@@ -124,18 +144,10 @@ impl Default for PresetStore {
 
 impl Length for PresetStore {
     fn len(&self) -> usize {
-        self.iter().count()
+        self.matched.len()
     }
 }
 
 fn default_label() -> &'static str {
     "Presets"
 }
-
-fn preset_matches(preset: &Preset, query: Option<&Query>) -> bool {
-    if let Some(q) = query {
-        preset.name.as_str().contains(q.word())
-    } else {
-        true
-    }
-}
@@ -1,3 +1,7 @@
+pub mod group;
+
+use crate::matcher::FuzzyMatcher;
+
 #[derive(Debug, Clone, Default)]
 pub struct Query {
     word: String,
@@ -7,6 +11,10 @@ impl Query {
     pub fn word(&self) -> &str {
         self.word.as_str()
     }
+
+    pub fn matcher(&self) -> FuzzyMatcher {
+        FuzzyMatcher::new(self.word())
+    }
 }
 
 impl Into<String> for Query {
@@ -0,0 +1,446 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use thiserror::Error;
+
+use crate::aws::cwlogs::group::CwlGroup;
+use crate::matcher::{FuzzyMatcher, Match, Matcher, PatternMatcher};
+use crate::size::{HumanReadableSize, ParseSizeError, Size};
+use crate::time::{HumanDuration, ParseDurationError};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    /// Splits a leading comparison operator from the value. No operator means `Equal`.
+    fn split(s: &str) -> (Comparison, &str) {
+        use Comparison::*;
+
+        for (op, c) in &[
+            (">=", GreaterOrEqual),
+            ("<=", LessOrEqual),
+            (">", Greater),
+            ("<", Less),
+            ("=", Equal),
+        ] {
+            if let Some(rest) = s.strip_prefix(op) {
+                return (*c, rest);
+            }
+        }
+        (Equal, s)
+    }
+
+    pub fn compare<T: Ord>(&self, lhs: &T, rhs: &T) -> bool {
+        use Comparison::*;
+        match *self {
+            Less => lhs < rhs,
+            LessOrEqual => lhs <= rhs,
+            Equal => lhs == rhs,
+            GreaterOrEqual => lhs >= rhs,
+            Greater => lhs > rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum GroupFilter {
+    /// A bare word, fuzzy matched against the group name.
+    Fuzzy(String),
+    /// `name:<substring>`
+    Name(String),
+    /// `name:<pattern>` with `*` or `?`, matched against the whole group name.
+    NamePattern(String),
+    /// `size:<op><size>`, compared with the stored bytes.
+    Size(Comparison, Size),
+    /// `created:<op><duration>`, compared with the age of the group.
+    Created(Comparison, Duration),
+    /// `retention:none` or `retention:<op><duration>`. Groups without retention never expire,
+    /// so they are treated as longer than any duration.
+    Retention(Option<(Comparison, Duration)>),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseGroupQueryError {
+    #[error("unknown field `{0}`, expected one of name, size, created or retention")]
+    UnknownField(String),
+
+    #[error("`{0}:` needs a value")]
+    MissingValue(String),
+
+    #[error("invalid size in `{term}`: {source}")]
+    Size {
+        term: String,
+        source: ParseSizeError,
+    },
+
+    #[error("invalid duration in `{term}`: {source}")]
+    Duration {
+        term: String,
+        source: ParseDurationError,
+    },
+}
+
+fn parse_duration(term: &str, s: &str) -> Result<Duration, ParseGroupQueryError> {
+    HumanDuration::from_str(s)
+        .map(Duration::from)
+        .map_err(|source| ParseGroupQueryError::Duration {
+            term: term.to_string(),
+            source,
+        })
+}
+
+fn parse_filter(term: &str) -> Result<GroupFilter, ParseGroupQueryError> {
+    let (field, value) = match term.find(':') {
+        Some(pos) if pos > 0 => (&term[..pos], &term[pos + 1..]),
+        _ => return Ok(GroupFilter::Fuzzy(term.to_string())),
+    };
+
+    if value.is_empty() {
+        return match field {
+            "name" | "size" | "created" | "retention" => {
+                Err(ParseGroupQueryError::MissingValue(field.to_string()))
+            }
+            _ => Err(ParseGroupQueryError::UnknownField(field.to_string())),
+        };
+    }
+
+    match field {
+        "name" if PatternMatcher::is_pattern(value) => {
+            Ok(GroupFilter::NamePattern(value.to_string()))
+        }
+        "name" => Ok(GroupFilter::Name(value.to_string())),
+        "size" => {
+            let (c, value) = Comparison::split(value);
+            let size = HumanReadableSize::from_str(value).map_err(|source| {
+                ParseGroupQueryError::Size {
+                    term: term.to_string(),
+                    source,
+                }
+            })?;
+            Ok(GroupFilter::Size(c, Size::from(size)))
+        }
+        "created" => {
+            let (c, value) = Comparison::split(value);
+            Ok(GroupFilter::Created(c, parse_duration(term, value)?))
+        }
+        "retention" => {
+            if value.eq_ignore_ascii_case("none") {
+                Ok(GroupFilter::Retention(None))
+            } else {
+                let (c, value) = Comparison::split(value);
+                Ok(GroupFilter::Retention(Some((
+                    c,
+                    parse_duration(term, value)?,
+                ))))
+            }
+        }
+        _ => Err(ParseGroupQueryError::UnknownField(field.to_string())),
+    }
+}
+
+/// A query for log groups, like `name:/aws/lambda size:>1GiB created:<30d retention:none`.
+///
+/// Terms are separated by whitespace and all of them must match.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GroupQuery {
+    text: String,
+    filters: Vec<GroupFilter>,
+}
+
+impl GroupQuery {
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+
+    pub fn filters(&self) -> &[GroupFilter] {
+        &self.filters
+    }
+
+    /// Returns the literal prefix of the first name pattern, which can be used to narrow down
+    /// groups on the server side.
+    pub fn name_prefix(&self) -> Option<String> {
+        self.filters
+            .iter()
+            .find_map(|f| match f {
+                GroupFilter::NamePattern(p) => Some(PatternMatcher::new(p).literal_prefix()),
+                _ => None,
+            })
+            .filter(|prefix| !prefix.is_empty())
+    }
+
+    pub fn matcher(&self, now: DateTime<Utc>) -> GroupMatcher<'_> {
+        GroupMatcher { query: self, now }
+    }
+}
+
+impl FromStr for GroupQuery {
+    type Err = ParseGroupQueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let filters = s
+            .split_whitespace()
+            .map(parse_filter)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(GroupQuery {
+            text: s.trim().to_string(),
+            filters,
+        })
+    }
+}
+
+pub struct GroupMatcher<'a> {
+    query: &'a GroupQuery,
+    now: DateTime<Utc>,
+}
+
+/// Finds the pattern in the text with smart case, returning the char indices it covers.
+/// Chars are compared one by one, as lowercasing may change the byte length of a text.
+fn find_substring(text: &str, pattern: &str) -> Option<Vec<usize>> {
+    let case_sensitive = pattern.chars().any(char::is_uppercase);
+    let char_eq = |a: &char, b: &char| {
+        if case_sensitive {
+            a == b
+        } else {
+            a.to_lowercase().eq(b.to_lowercase())
+        }
+    };
+
+    let text = text.chars().collect::<Vec<_>>();
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    if pattern.len() > text.len() {
+        return None;
+    }
+    let start = (0..=text.len() - pattern.len()).find(|&i| {
+        text[i..]
+            .iter()
+            .zip(pattern.iter())
+            .all(|(a, b)| char_eq(a, b))
+    })?;
+    Some((start..start + pattern.len()).collect())
+}
+
+impl<'a> Matcher<CwlGroup> for GroupMatcher<'a> {
+    fn matches(&self, group: &CwlGroup) -> Option<Match> {
+        let mut score = 0;
+        let mut indices = Vec::new();
+
+        for filter in self.query.filters() {
+            let matches = match filter {
+                GroupFilter::Fuzzy(word) => {
+                    let m = FuzzyMatcher::new(word).matches(group.group_name.as_str())?;
+                    score += m.score;
+                    indices.extend(m.indices);
+                    true
+                }
+                GroupFilter::Name(s) => {
+                    indices.extend(find_substring(group.group_name.as_str(), s)?);
+                    true
+                }
+                GroupFilter::NamePattern(p) => {
+                    let m = PatternMatcher::new(p).matches(group.group_name.as_str())?;
+                    indices.extend(m.indices);
+                    true
+                }
+                GroupFilter::Size(c, size) => c.compare(&group.stored, size),
+                GroupFilter::Created(c, age) => {
+                    let group_age = (self.now - group.creation_time)
+                        .to_std()
+                        .unwrap_or_else(|_| Duration::from_secs(0));
+                    c.compare(&group_age, age)
+                }
+                GroupFilter::Retention(None) => group.retention.is_none(),
+                GroupFilter::Retention(Some((c, d))) => match group.retention {
+                    Some(retention) => c.compare(&retention, d),
+                    None => matches!(c, Comparison::Greater | Comparison::GreaterOrEqual),
+                },
+            };
+
+            if !matches {
+                return None;
+            }
+        }
+
+        indices.sort_unstable();
+        indices.dedup();
+        Some(Match::new(score, indices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    const DAY: u64 = 24 * 60 * 60;
+
+    fn group(
+        name: &str,
+        created_days_ago: i64,
+        retention_days: Option<u64>,
+        stored: u128,
+    ) -> CwlGroup {
+        let group = CwlGroup::for_test(name)
+            .created_at(now() - chrono::Duration::days(created_days_ago))
+            .stored(stored);
+        match retention_days {
+            Some(days) => group.retention_days(days),
+            None => group,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.ymd(2020, 11, 2).and_hms(11, 22, 33)
+    }
+
+    fn matches(query: &str, group: &CwlGroup) -> bool {
+        let query = GroupQuery::from_str(query).unwrap();
+        query.matcher(now()).matches(group).is_some()
+    }
+
+    #[test]
+    fn test_from_str() {
+        let query = GroupQuery::from_str(
+            " name:/aws/lambda size:>1GiB created:<30d retention:none retention:>=7d api ",
+        )
+        .unwrap();
+        assert_eq!(
+            "name:/aws/lambda size:>1GiB created:<30d retention:none retention:>=7d api",
+            query.text()
+        );
+        assert_eq!(
+            &[
+                GroupFilter::Name("/aws/lambda".to_string()),
+                GroupFilter::Size(Comparison::Greater, Size::new(1024 * 1024 * 1024)),
+                GroupFilter::Created(Comparison::Less, Duration::from_secs(30 * DAY)),
+                GroupFilter::Retention(None),
+                GroupFilter::Retention(Some((
+                    Comparison::GreaterOrEqual,
+                    Duration::from_secs(7 * DAY)
+                ))),
+                GroupFilter::Fuzzy("api".to_string()),
+            ],
+            query.filters()
+        );
+    }
+
+    #[test]
+    fn test_from_str_error() {
+        assert_eq!(
+            Err(ParseGroupQueryError::UnknownField("owner".to_string())),
+            GroupQuery::from_str("owner:me")
+        );
+        assert_eq!(
+            Err(ParseGroupQueryError::MissingValue("size".to_string())),
+            GroupQuery::from_str("size:")
+        );
+        assert_eq!(
+            Err(ParseGroupQueryError::Size {
+                term: "size:>1GB".to_string(),
+                source: ParseSizeError::UnknownUnit("GB".to_string()),
+            }),
+            GroupQuery::from_str("size:>1GB")
+        );
+        assert_eq!(
+            Err(ParseGroupQueryError::Size {
+                term: "size:>1.5MiB".to_string(),
+                source: ParseSizeError::Decimal("1.5MiB".to_string()),
+            }),
+            GroupQuery::from_str("size:>1.5MiB")
+        );
+        assert_eq!(
+            Err(ParseGroupQueryError::Duration {
+                term: "created:<30y".to_string(),
+                source: ParseDurationError::UnknownUnit("y".to_string()),
+            }),
+            GroupQuery::from_str("created:<30y")
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let lambda = group("/aws/lambda/api", 10, None, 2 * 1024 * 1024 * 1024);
+        let ecs = group("/ecs/worker", 60, Some(14), 1024);
+
+        assert!(matches("name:/aws/lambda", &lambda));
+        assert!(!matches("name:/aws/lambda", &ecs));
+        assert!(matches("name:WORKER", &group("/ecs/WORKER", 0, None, 0)));
+        assert!(!matches("name:WORKER", &ecs));
+
+        assert!(matches("size:>1GiB", &lambda));
+        assert!(!matches("size:>1GiB", &ecs));
+        assert!(matches("size:<=1KiB", &ecs));
+
+        assert!(matches("created:<30d", &lambda));
+        assert!(!matches("created:<30d", &ecs));
+        assert!(matches("created:>30d", &ecs));
+
+        assert!(matches("retention:none", &lambda));
+        assert!(!matches("retention:none", &ecs));
+        assert!(matches("retention:14d", &ecs));
+        assert!(matches("retention:>30d", &lambda));
+        assert!(!matches("retention:>30d", &ecs));
+        assert!(!matches("retention:<30d", &lambda));
+
+        assert!(matches("lmbapi size:>1GiB retention:none", &lambda));
+        assert!(!matches("lmbapi size:>1GiB retention:none", &ecs));
+        assert!(matches("", &ecs));
+    }
+
+    #[test]
+    fn test_name_pattern() {
+        let query = GroupQuery::from_str("name:/aws/lambda/* size:>1KiB").unwrap();
+        assert_eq!(
+            GroupFilter::NamePattern("/aws/lambda/*".to_string()),
+            query.filters()[0]
+        );
+        assert_eq!(Some("/aws/lambda/".to_string()), query.name_prefix());
+        assert_eq!(
+            None,
+            GroupQuery::from_str("name:*api").unwrap().name_prefix()
+        );
+        assert_eq!(
+            None,
+            GroupQuery::from_str("name:/aws").unwrap().name_prefix()
+        );
+
+        assert!(matches(
+            "name:/aws/*/api",
+            &group("/aws/lambda/api", 0, None, 0)
+        ));
+        assert!(!matches(
+            "name:/aws/*/api",
+            &group("/aws/lambda/api2", 0, None, 0)
+        ));
+    }
+
+    #[test]
+    fn test_match_indices() {
+        let query = GroupQuery::from_str("name:lambda api").unwrap();
+        let m = query
+            .matcher(now())
+            .matches(&group("/aws/lambda/api", 0, None, 0))
+            .unwrap();
+        assert_eq!(vec![5, 6, 7, 8, 9, 10, 12, 13, 14], m.indices);
+    }
+
+    #[test]
+    fn test_find_substring() {
+        assert_eq!(Some(vec![2, 3, 4]), find_substring("/aws/api", "ws/"));
+        assert_eq!(None, find_substring("/aws/api", "WS"));
+        assert_eq!(None, find_substring("ap", "api"));
+
+        // 'İ' lowercases to 3 bytes, which shifts byte offsets of a lowercased text.
+        assert_eq!(Some(vec![2, 3, 4]), find_substring("İİapi", "api"));
+        assert_eq!(Some(vec![1, 2]), find_substring("/ÄÖ-api", "äö"));
+        assert_eq!(Some(vec![0, 1]), find_substring("日本/api", "日本"));
+    }
+}
@@ -1,5 +1,12 @@
 use std::collections::BTreeMap;
 
+use crate::aws::cwlogs::event::{group_invocations, CwlEvent, CwlEventStore, Invocation};
+use crate::aws::cwlogs::group::{CwlGroup, CwlGroupSource};
+use crate::aws::cwlogs::stream::CwlStreamStore;
+use crate::aws::Arn;
+use crate::json::JsonPath;
+use crate::time::{TimeRangeSpec, TimeZoneChoice};
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct SessionId(u64);
 
@@ -15,22 +22,148 @@ impl From<u64> for SessionId {
     }
 }
 
+/// Log streams and events of a log group, opened from the shell.
+#[derive(Debug)]
 pub struct Session {
-    // client: CloudWatchLogsClient,
-// groups: CwlGroupStore,
-// streams: CwlStreamStore,
-// events: CwlEventStore,
+    pub source: CwlGroupSource,
+    pub group: CwlGroup,
+    pub streams: CwlStreamStore,
+    /// Listing of the streams, so pages of a listing started before a refresh are dropped.
+    pub streams_generation: u64,
+    pub events: CwlEventStore,
+    /// Listing of the events, so pages of a listing started before a refetch are dropped.
+    pub events_generation: u64,
+    /// Stream of the listed events, or `None` for events of every stream.
+    pub events_stream: Option<String>,
+    /// Time range of the events to look at.
+    pub time_range: TimeRangeSpec,
+    pub time_zone: TimeZoneChoice,
+    /// Conditions of the JSON filter pattern of the events, combined with `&&`.
+    pub event_filters: Vec<String>,
+    /// A filter pattern as typed, like `ERROR`, until a JSON condition replaces it.
+    pub filter_text: Option<String>,
+    /// Fields of JSON messages shown as columns of the events.
+    pub event_columns: Vec<JsonPath>,
+    /// Every group of a merged view of their events, empty for a session of one group.
+    pub merged_groups: Vec<CwlGroup>,
 }
 
+impl Session {
+    pub fn new(source: CwlGroupSource, group: CwlGroup) -> Self {
+        Session {
+            source,
+            group,
+            streams: CwlStreamStore::default(),
+            streams_generation: 0,
+            events: CwlEventStore::default(),
+            events_generation: 0,
+            events_stream: None,
+            time_range: TimeRangeSpec::default(),
+            time_zone: TimeZoneChoice::default(),
+            event_filters: Vec::new(),
+            filter_text: None,
+            event_columns: Vec::new(),
+            merged_groups: Vec::new(),
+        }
+    }
+
+    /// A session of the events of several groups, merged in time order.
+    pub fn merged(source: CwlGroupSource, groups: Vec<CwlGroup>) -> Self {
+        Session {
+            merged_groups: groups.clone(),
+            ..Session::new(source, groups[0].clone())
+        }
+    }
+
+    pub fn is_merged(&self) -> bool {
+        !self.merged_groups.is_empty()
+    }
+
+    /// Whether the group is written by a Lambda function.
+    pub fn is_lambda(&self) -> bool {
+        !self.is_merged() && self.group.group_name.starts_with("/aws/lambda/")
+    }
+
+    /// Group of the event, which is told by the event itself in a merged view.
+    pub fn group_name_of<'a>(&'a self, event: &'a CwlEvent) -> &'a str {
+        event
+            .group_name
+            .as_deref()
+            .unwrap_or(&self.group.group_name)
+    }
+
+    /// Invocations of the Lambda function in the loaded events.
+    pub fn invocations(&self) -> Vec<Invocation> {
+        group_invocations(self.events.order_by_asc())
+    }
+
+    /// The filter pattern of the events, like `{ ($.level = "error") && ($.status = 500) }`.
+    pub fn filter_pattern(&self) -> Option<String> {
+        match self.event_filters.as_slice() {
+            [] => self.filter_text.clone(),
+            [condition] => Some(format!("{{ {} }}", condition)),
+            conditions => {
+                let conditions = conditions
+                    .iter()
+                    .map(|c| format!("({})", c))
+                    .collect::<Vec<_>>();
+                Some(format!("{{ {} }}", conditions.join(" && ")))
+            }
+        }
+    }
+
+    /// Adds the condition to the filter pattern, returns `false` if it is already added.
+    pub fn add_event_filter(&mut self, condition: String) -> bool {
+        if self.event_filters.contains(&condition) {
+            return false;
+        }
+        self.event_filters.push(condition);
+        self.filter_text = None;
+        true
+    }
+
+    /// Adds the field to the columns, returns `false` if it is already shown.
+    pub fn add_event_column(&mut self, path: JsonPath) -> bool {
+        if self.event_columns.contains(&path) {
+            return false;
+        }
+        self.event_columns.push(path);
+        true
+    }
+}
+
+#[derive(Debug)]
 pub struct SessionSet {
     sessions: BTreeMap<SessionId, Session>,
     next_session_id: SessionId,
 }
 
 impl SessionSet {
-    pub fn insert(&mut self, session: Session) {
-        self.sessions.insert(self.next_session_id, session);
+    pub fn insert(&mut self, session: Session) -> SessionId {
+        let id = self.next_session_id;
+        self.sessions.insert(id, session);
         self.next_session_id = self.next_session_id.next();
+        id
+    }
+
+    pub fn get(&self, id: SessionId) -> Option<&Session> {
+        self.sessions.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: SessionId) -> Option<&mut Session> {
+        self.sessions.get_mut(&id)
+    }
+
+    pub fn remove(&mut self, id: SessionId) -> Option<Session> {
+        self.sessions.remove(&id)
+    }
+
+    /// Returns the id of the session opened for the group, except merged views.
+    pub fn find_by_group(&self, arn: &Arn) -> Option<SessionId> {
+        self.sessions
+            .iter()
+            .find(|(_, session)| !session.is_merged() && &session.group.arn == arn)
+            .map(|(&id, _)| id)
     }
 }
 
@@ -1,5 +1,7 @@
 use std::convert::TryFrom;
 use std::fmt;
+use std::iter::Sum;
+use std::str::FromStr;
 
 use thiserror::Error;
 
@@ -105,6 +107,23 @@ impl SizeUnit {
     }
 }
 
+impl FromStr for SizeUnit {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use SizeUnit::*;
+        match s.to_ascii_lowercase().as_str() {
+            "" | "b" => Ok(Byte),
+            "k" | "kib" => Ok(KibiByte),
+            "m" | "mib" => Ok(MebiByte),
+            "g" | "gib" => Ok(GibiByte),
+            "t" | "tib" => Ok(TebiByte),
+            "p" | "pib" => Ok(PebiByte),
+            _ => Err(ParseSizeError::UnknownUnit(s.to_string())),
+        }
+    }
+}
+
 fn preferred_size_unit(n: u128) -> Option<SizeUnit> {
     use SizeUnit::*;
 
@@ -140,6 +159,24 @@ pub enum SizeError {
     TooLarge,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseSizeError {
+    #[error("size is empty")]
+    Empty,
+
+    #[error("invalid number `{0}`")]
+    InvalidNumber(String),
+
+    #[error("unknown size unit `{0}`")]
+    UnknownUnit(String),
+
+    #[error("decimal size `{0}` is not supported, use a smaller unit like 1536KiB")]
+    Decimal(String),
+
+    #[error("size must be within max value of pebi byte")]
+    TooLarge,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Size {
     in_bytes: u128,
@@ -149,6 +186,16 @@ impl Size {
     pub fn new(in_bytes: u128) -> Size {
         Size { in_bytes }
     }
+
+    pub fn in_bytes(&self) -> u128 {
+        self.in_bytes
+    }
+}
+
+impl Sum for Size {
+    fn sum<I: Iterator<Item = Size>>(iter: I) -> Self {
+        Size::new(iter.map(|s| s.in_bytes).sum())
+    }
 }
 
 impl TryFrom<i64> for Size {
@@ -182,6 +229,39 @@ impl fmt::Display for HumanReadableSize {
     }
 }
 
+/// Parses sizes like `512B`, `10KiB`, `1 GiB` or `1g`. Unit names are case insensitive.
+impl FromStr for HumanReadableSize {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseSizeError::Empty);
+        }
+
+        let unit_pos = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+        let (number, unit) = s.split_at(unit_pos);
+        if unit.starts_with('.') {
+            return Err(ParseSizeError::Decimal(s.to_string()));
+        }
+        let size = number
+            .parse::<u128>()
+            .map_err(|_| ParseSizeError::InvalidNumber(number.to_string()))?;
+        let unit = SizeUnit::from_str(unit.trim())?;
+
+        if size > SizeUnit::PebiByte.max() / 1024_u128.pow(unit.multiplier()) {
+            return Err(ParseSizeError::TooLarge);
+        }
+        Ok(HumanReadableSize::new(size, unit))
+    }
+}
+
+impl From<HumanReadableSize> for Size {
+    fn from(size: HumanReadableSize) -> Self {
+        Size::new(size.size * 1024_u128.pow(size.unit.multiplier()))
+    }
+}
+
 impl TryFrom<Size> for HumanReadableSize {
     type Error = SizeError;
 
@@ -198,6 +278,12 @@ impl TryFrom<Size> for HumanReadableSize {
 mod tests {
     use super::*;
 
+    #[test]
+    fn size_sum() {
+        let sizes = vec![Size::new(1), Size::new(1_024), Size::new(0)];
+        assert_eq!(Size::new(1_025), sizes.into_iter().sum());
+    }
+
     #[test]
     fn size_unit_short_name() {
         use SizeUnit::*;
@@ -323,4 +409,66 @@ mod tests {
             HumanReadableSize::try_from(Size::new(1_152_921_504_606_846_976))
         );
     }
+
+    #[test]
+    fn human_readable_size_from_str() {
+        use SizeUnit::*;
+
+        assert_eq!(
+            Ok(HumanReadableSize::new(512, Byte)),
+            HumanReadableSize::from_str("512")
+        );
+        assert_eq!(
+            Ok(HumanReadableSize::new(512, Byte)),
+            HumanReadableSize::from_str("512B")
+        );
+        assert_eq!(
+            Ok(HumanReadableSize::new(10, KibiByte)),
+            HumanReadableSize::from_str("10KiB")
+        );
+        assert_eq!(
+            Ok(HumanReadableSize::new(1, GibiByte)),
+            HumanReadableSize::from_str("1 gib")
+        );
+        assert_eq!(
+            Ok(HumanReadableSize::new(3, TebiByte)),
+            HumanReadableSize::from_str("3T")
+        );
+        assert_eq!(
+            Ok(HumanReadableSize::new(1023, PebiByte)),
+            HumanReadableSize::from_str("1023PiB")
+        );
+
+        assert_eq!(Err(ParseSizeError::Empty), HumanReadableSize::from_str(""));
+        assert_eq!(
+            Err(ParseSizeError::InvalidNumber("".to_string())),
+            HumanReadableSize::from_str("GiB")
+        );
+        assert_eq!(
+            Err(ParseSizeError::UnknownUnit("GB".to_string())),
+            HumanReadableSize::from_str("1GB")
+        );
+        assert_eq!(
+            Err(ParseSizeError::TooLarge),
+            HumanReadableSize::from_str("1024PiB")
+        );
+        assert_eq!(
+            Err(ParseSizeError::Decimal("1.5MiB".to_string())),
+            HumanReadableSize::from_str("1.5MiB")
+        );
+    }
+
+    #[test]
+    fn size_from_human_readable_size() {
+        use SizeUnit::*;
+
+        assert_eq!(
+            Size::new(512),
+            Size::from(HumanReadableSize::new(512, Byte))
+        );
+        assert_eq!(
+            Size::new(1_073_741_824),
+            Size::from(HumanReadableSize::new(1, GibiByte))
+        );
+    }
 }
@@ -1,12 +1,17 @@
+use std::fmt;
 use std::io::{self, stdout, Stdout, Write};
 use std::ops::{Deref, DerefMut};
 
 use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
     execute,
+    style::{Attribute, SetAttribute},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    Command,
 };
 use thiserror::Error;
 use tui::backend::CrosstermBackend;
+use tui::layout::Rect;
 use tui::Terminal;
 
 #[derive(Debug, Error)]
@@ -18,6 +23,34 @@ pub enum CrossTermError {
     CrossTerm(#[from] crossterm::ErrorKind),
 }
 
+/// Prints the text as an OSC 8 hyperlink to the URL.
+struct Hyperlink<'a> {
+    text: &'a str,
+    url: &'a str,
+}
+
+impl fmt::Display for Hyperlink<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", self.url, self.text)
+    }
+}
+
+impl Command for Hyperlink<'_> {
+    type AnsiType = String;
+
+    fn ansi_code(&self) -> Self::AnsiType {
+        self.to_string()
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(
+        &self,
+        _writer: impl FnMut() -> crossterm::Result<()>,
+    ) -> crossterm::Result<()> {
+        Ok(())
+    }
+}
+
 pub struct CrossTermTerminal {
     terminal: Terminal<CrosstermBackend<Stdout>>,
 }
@@ -38,6 +71,26 @@ impl CrossTermTerminal {
         Ok(())
     }
 
+    /// Draws the text again over the area as a hyperlink, which the buffer of tui can not hold.
+    pub fn draw_hyperlink(
+        &mut self,
+        area: Rect,
+        text: &str,
+        url: &str,
+    ) -> Result<(), CrossTermError> {
+        let text = text.chars().take(area.width as usize).collect::<String>();
+        execute!(
+            self.terminal.backend_mut(),
+            SavePosition,
+            MoveTo(area.x, area.y),
+            SetAttribute(Attribute::Underlined),
+            Hyperlink { text: &text, url },
+            SetAttribute(Attribute::Reset),
+            RestorePosition
+        )?;
+        Ok(())
+    }
+
     fn terminate(&mut self) -> Result<(), CrossTermError> {
         disable_raw_mode()?;
         execute!(self.terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -0,0 +1,18 @@
+mod display;
+mod duration;
+mod range;
+mod zone;
+
+pub use display::format_delta;
+pub use display::TimeDisplay;
+
+pub use duration::HumanDuration;
+pub use duration::ParseDurationError;
+
+pub use range::ParseTimeRangeError;
+pub use range::TimeRange;
+pub use range::TimeRangeSpec;
+pub use range::TIME_RANGE_PRESETS;
+
+pub use zone::ParseTimeZoneError;
+pub use zone::TimeZoneChoice;
@@ -0,0 +1,180 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+
+use crate::time::zone::{ParseTimeZoneError, TimeZoneChoice};
+
+const SECONDS_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const MILLIS_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f";
+
+/// How times are shown, either in a time zone or relative to now like `3h ago`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum TimeDisplay {
+    Zone(TimeZoneChoice),
+    Relative,
+}
+
+impl TimeDisplay {
+    /// Formats the time with seconds precision.
+    pub fn format(&self, time: &DateTime<Utc>) -> String {
+        self.format_at(time, &Utc::now(), false)
+    }
+
+    /// Formats the time with milliseconds precision, for events.
+    pub fn format_millis(&self, time: &DateTime<Utc>) -> String {
+        self.format_at(time, &Utc::now(), true)
+    }
+
+    pub fn format_at(&self, time: &DateTime<Utc>, now: &DateTime<Utc>, millis: bool) -> String {
+        match self {
+            TimeDisplay::Zone(zone) if millis => zone.format(time, MILLIS_FORMAT),
+            TimeDisplay::Zone(zone) => zone.format(time, SECONDS_FORMAT),
+            TimeDisplay::Relative => format_relative(*now - *time, millis),
+        }
+    }
+
+    /// The next of local, UTC, relative and the configured display.
+    pub fn next(&self, configured: TimeDisplay) -> TimeDisplay {
+        let mut choices = vec![
+            TimeDisplay::Zone(TimeZoneChoice::Local),
+            TimeDisplay::Zone(TimeZoneChoice::Utc),
+            TimeDisplay::Relative,
+        ];
+        if !choices.contains(&configured) {
+            choices.insert(0, configured);
+        }
+        let i = choices.iter().position(|d| d == self).unwrap_or(0);
+        choices[(i + 1) % choices.len()]
+    }
+}
+
+impl Default for TimeDisplay {
+    fn default() -> Self {
+        TimeDisplay::Zone(TimeZoneChoice::Local)
+    }
+}
+
+impl FromStr for TimeDisplay {
+    type Err = ParseTimeZoneError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().eq_ignore_ascii_case("relative") {
+            Ok(TimeDisplay::Relative)
+        } else {
+            TimeZoneChoice::from_str(s).map(TimeDisplay::Zone)
+        }
+    }
+}
+
+impl TryFrom<String> for TimeDisplay {
+    type Error = ParseTimeZoneError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        TimeDisplay::from_str(&s)
+    }
+}
+
+impl fmt::Display for TimeDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeDisplay::Zone(zone) => write!(f, "{}", zone),
+            TimeDisplay::Relative => write!(f, "Relative"),
+        }
+    }
+}
+
+/// Formats the elapsed time in its largest unit, e.g. `3h ago` or `in 5m`.
+fn format_relative(elapsed: Duration, millis: bool) -> String {
+    let (abs, future) = if elapsed < Duration::zero() {
+        (-elapsed, true)
+    } else {
+        (elapsed, false)
+    };
+
+    let secs = abs.num_seconds();
+    let s = match secs {
+        0 if millis => format!("{}ms", abs.num_milliseconds()),
+        0 => return "now".to_string(),
+        1..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m", secs / 60),
+        3600..=86_399 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86_400),
+    };
+    if future {
+        format!("in {}", s)
+    } else {
+        format!("{} ago", s)
+    }
+}
+
+/// Formats the time since the previous event, e.g. `+120ms`, `+1.520s` or `+2m3s`.
+pub fn format_delta(delta: Duration) -> String {
+    let sign = if delta < Duration::zero() { "-" } else { "+" };
+    let ms = delta.num_milliseconds().abs();
+    match ms {
+        0..=999 => format!("{}{}ms", sign, ms),
+        1000..=59_999 => format!("{}{}.{:03}s", sign, ms / 1000, ms % 1000),
+        _ => {
+            let secs = ms / 1000;
+            let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+            if h > 0 {
+                format!("{}{}h{}m", sign, h, m)
+            } else {
+                format!("{}{}m{}s", sign, m, s)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let time = Utc.ymd(2021, 1, 2).and_hms_milli(3, 4, 5, 678);
+        let now = time + Duration::hours(3) + Duration::minutes(20);
+        let jst = TimeDisplay::from_str("+09:00").unwrap();
+
+        assert_eq!("2021-01-02 12:04:05", jst.format_at(&time, &now, false));
+        assert_eq!("2021-01-02 12:04:05.678", jst.format_at(&time, &now, true));
+        let utc = TimeDisplay::from_str("utc").unwrap();
+        assert_eq!("2021-01-02 03:04:05.678", utc.format_at(&time, &now, true));
+        let relative = TimeDisplay::Relative;
+        assert_eq!("3h ago", relative.format_at(&time, &now, false));
+        assert_eq!("in 3h", relative.format_at(&now, &time, false));
+        assert_eq!("now", relative.format_at(&time, &time, false));
+        assert_eq!(
+            "120ms ago",
+            relative.format_at(&time, &(time + Duration::milliseconds(120)), true)
+        );
+    }
+
+    #[test]
+    fn test_delta() {
+        assert_eq!("+120ms", format_delta(Duration::milliseconds(120)));
+        assert_eq!("+1.520s", format_delta(Duration::milliseconds(1520)));
+        assert_eq!("+2m3s", format_delta(Duration::seconds(123)));
+        assert_eq!("+1h1m", format_delta(Duration::seconds(3661)));
+        assert_eq!("-5ms", format_delta(Duration::milliseconds(-5)));
+    }
+
+    #[test]
+    fn test_next() {
+        let local = TimeDisplay::default();
+        let utc = TimeDisplay::Zone(TimeZoneChoice::Utc);
+        let jst = TimeDisplay::from_str("+09:00").unwrap();
+
+        assert_eq!(utc, local.next(local));
+        assert_eq!(TimeDisplay::Relative, utc.next(local));
+        assert_eq!(local, TimeDisplay::Relative.next(local));
+        assert_eq!(jst, TimeDisplay::Relative.next(jst));
+        assert_eq!(local, jst.next(jst));
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+use thiserror::Error;
+
+const SECOND: u64 = 1;
+const MINUTE: u64 = 60 * SECOND;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+fn unit_in_secs(unit: &str) -> Option<u64> {
+    match unit {
+        "s" => Some(SECOND),
+        "m" => Some(MINUTE),
+        "h" => Some(HOUR),
+        "d" => Some(DAY),
+        "w" => Some(WEEK),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseDurationError {
+    #[error("duration is empty")]
+    Empty,
+
+    #[error("invalid number in `{0}`")]
+    InvalidNumber(String),
+
+    #[error("unknown duration unit `{0}`, expected one of s, m, h, d or w")]
+    UnknownUnit(String),
+
+    #[error("duration is too long")]
+    TooLong,
+}
+
+/// A duration written like `45s`, `15m`, `2h`, `30d`, `1w` or combined like `1h30m`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub struct HumanDuration(Duration);
+
+impl HumanDuration {
+    pub fn as_duration(&self) -> Duration {
+        self.0
+    }
+
+    pub fn as_chrono(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.0.as_secs() as i64)
+    }
+}
+
+impl From<Duration> for HumanDuration {
+    fn from(d: Duration) -> Self {
+        HumanDuration(d)
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(d: HumanDuration) -> Self {
+        d.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseDurationError::Empty);
+        }
+
+        let mut secs: u64 = 0;
+        let mut rest = s;
+        while !rest.is_empty() {
+            let unit_pos = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .ok_or_else(|| ParseDurationError::UnknownUnit(String::new()))?;
+            let (number, tail) = rest.split_at(unit_pos);
+            let unit_len = tail
+                .find(|c: char| c.is_ascii_digit())
+                .unwrap_or(tail.len());
+            let (unit, tail) = tail.split_at(unit_len);
+
+            let n = number
+                .parse::<u64>()
+                .map_err(|_| ParseDurationError::InvalidNumber(s.to_string()))?;
+            let unit = unit_in_secs(unit)
+                .ok_or_else(|| ParseDurationError::UnknownUnit(unit.to_string()))?;
+            secs = n
+                .checked_mul(unit)
+                .and_then(|n| secs.checked_add(n))
+                .filter(|&n| n <= i64::MAX as u64 / 1000)
+                .ok_or(ParseDurationError::TooLong)?;
+
+            rest = tail;
+        }
+
+        Ok(HumanDuration(Duration::from_secs(secs)))
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut secs = self.0.as_secs();
+        if secs == 0 {
+            return write!(f, "0s");
+        }
+
+        for (unit, name) in &[
+            (WEEK, "w"),
+            (DAY, "d"),
+            (HOUR, "h"),
+            (MINUTE, "m"),
+            (SECOND, "s"),
+        ] {
+            if secs >= *unit {
+                write!(f, "{}{}", secs / unit, name)?;
+                secs %= unit;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(n: u64) -> Result<HumanDuration, ParseDurationError> {
+        Ok(HumanDuration::from(Duration::from_secs(n)))
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(secs(45), HumanDuration::from_str("45s"));
+        assert_eq!(secs(15 * 60), HumanDuration::from_str("15m"));
+        assert_eq!(secs(2 * 60 * 60), HumanDuration::from_str("2h"));
+        assert_eq!(secs(30 * 24 * 60 * 60), HumanDuration::from_str("30d"));
+        assert_eq!(secs(7 * 24 * 60 * 60), HumanDuration::from_str("1w"));
+        assert_eq!(secs(90 * 60), HumanDuration::from_str("1h30m"));
+        assert_eq!(secs(90 * 60), HumanDuration::from_str(" 1h30m "));
+
+        assert_eq!(Err(ParseDurationError::Empty), HumanDuration::from_str(""));
+        assert_eq!(
+            Err(ParseDurationError::UnknownUnit(String::new())),
+            HumanDuration::from_str("30")
+        );
+        assert_eq!(
+            Err(ParseDurationError::UnknownUnit("y".to_string())),
+            HumanDuration::from_str("1y")
+        );
+        assert_eq!(
+            Err(ParseDurationError::InvalidNumber("d".to_string())),
+            HumanDuration::from_str("d")
+        );
+        assert_eq!(
+            Err(ParseDurationError::TooLong),
+            HumanDuration::from_str("99999999999999999w")
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!("0s", format!("{}", HumanDuration::from_str("0s").unwrap()));
+        assert_eq!(
+            "1h30m",
+            format!("{}", HumanDuration::from_str("90m").unwrap())
+        );
+        assert_eq!(
+            "1w2d",
+            format!("{}", HumanDuration::from_str("9d").unwrap())
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, SecondsFormat, Utc};
+use thiserror::Error;
+
+use crate::time::duration::{HumanDuration, ParseDurationError};
+use crate::time::zone::TimeZoneChoice;
+
+const DATE_TIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+];
+const TIME_FORMATS: [&str; 2] = ["%H:%M:%S", "%H:%M"];
+
+/// Expressions offered as quick choices in the time range picker.
+pub const TIME_RANGE_PRESETS: [&str; 9] = [
+    "5m",
+    "15m",
+    "1h",
+    "3h",
+    "12h",
+    "1d",
+    "7d",
+    "today",
+    "yesterday",
+];
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseTimeRangeError {
+    #[error("time range is empty")]
+    Empty,
+
+    #[error(
+        "unknown time `{0}`, expected e.g. 15m, 2h ago, yesterday, today 09:00 or 2021-01-02T03:04"
+    )]
+    InvalidTime(String),
+
+    #[error("invalid duration: {0}")]
+    Duration(#[from] ParseDurationError),
+
+    #[error("`{0}` does not exist in the time zone")]
+    SkippedTime(String),
+
+    #[error("start of the time range must be before its end")]
+    StartAfterEnd,
+}
+
+/// Start and end of a time range, the end excluded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TimeRange {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl TimeRange {
+    /// `start_time` of requests, in milliseconds since the epoch.
+    pub fn start_time(&self) -> i64 {
+        self.start.timestamp_millis()
+    }
+
+    /// `end_time` of requests, in milliseconds since the epoch.
+    pub fn end_time(&self) -> i64 {
+        self.end.timestamp_millis()
+    }
+
+    pub fn contains(&self, time: &DateTime<Utc>) -> bool {
+        self.start <= *time && *time < self.end
+    }
+
+    /// Whether the range has a time in common with `[start, end]`.
+    pub fn overlaps(&self, start: &DateTime<Utc>, end: &DateTime<Utc>) -> bool {
+        *start < self.end && self.start <= *end
+    }
+}
+
+/// A time range as typed, relative ones following the current time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TimeRangeSpec {
+    /// The duration until now.
+    Last(HumanDuration),
+    /// From the time until now.
+    Since(DateTime<Utc>),
+    Between(DateTime<Utc>, DateTime<Utc>),
+}
+
+impl TimeRangeSpec {
+    /// Parses expressions like `15m`, `2h ago`, `yesterday`, `today 09:00..10:30` or
+    /// `2021-01-02T03:04..2021-01-02T05:00`, taking dates and times without offsets in the zone.
+    pub fn parse(
+        s: &str,
+        zone: &TimeZoneChoice,
+        now: &DateTime<Utc>,
+    ) -> Result<TimeRangeSpec, ParseTimeRangeError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseTimeRangeError::Empty);
+        }
+
+        if let Some(i) = s.find("..") {
+            let (start, end) = (s[..i].trim(), s[i + 2..].trim());
+            let start = parse_time(start, zone, now, None)?;
+            let end = parse_time(end, zone, now, Some(zone.date_of(&start)))?;
+            return if start < end {
+                Ok(TimeRangeSpec::Between(start, end))
+            } else {
+                Err(ParseTimeRangeError::StartAfterEnd)
+            };
+        }
+
+        // a whole day
+        if let Some(date) = parse_day(s, zone, now) {
+            let start = day_start(date, s, zone)?;
+            let end = date
+                .succ_opt()
+                .map(|next| day_start(next, s, zone))
+                .transpose()?
+                .ok_or_else(|| ParseTimeRangeError::InvalidTime(s.to_string()))?;
+            return Ok(TimeRangeSpec::Between(start, end));
+        }
+
+        let duration = s.strip_prefix("last ").unwrap_or(s).trim();
+        if duration.starts_with(|c: char| c.is_ascii_digit()) && !duration.contains(['-', ':']) {
+            let duration = duration.strip_suffix("ago").unwrap_or(duration).trim();
+            return Ok(TimeRangeSpec::Last(HumanDuration::from_str(duration)?));
+        }
+
+        let start = parse_time(s, zone, now, None)?;
+        if start < *now {
+            Ok(TimeRangeSpec::Since(start))
+        } else {
+            Err(ParseTimeRangeError::StartAfterEnd)
+        }
+    }
+
+    pub fn resolve(&self, now: &DateTime<Utc>) -> TimeRange {
+        match *self {
+            TimeRangeSpec::Last(duration) => TimeRange {
+                start: *now - duration.as_chrono(),
+                end: *now,
+            },
+            TimeRangeSpec::Since(start) => TimeRange { start, end: *now },
+            TimeRangeSpec::Between(start, end) => TimeRange { start, end },
+        }
+    }
+
+    /// Describes the range in the zone, e.g. `last 15m` or `2021-01-02 09:00 .. 10:30`.
+    pub fn describe(&self, zone: &TimeZoneChoice) -> String {
+        let format = |t: &DateTime<Utc>| zone.format(t, "%Y-%m-%d %H:%M:%S");
+        match self {
+            TimeRangeSpec::Last(duration) => format!("last {}", duration),
+            TimeRangeSpec::Since(start) => format!("{} .. now", format(start)),
+            TimeRangeSpec::Between(start, end) if zone.date_of(start) == zone.date_of(end) => {
+                format!("{} .. {}", format(start), zone.format(end, "%H:%M:%S"))
+            }
+            TimeRangeSpec::Between(start, end) => format!("{} .. {}", format(start), format(end)),
+        }
+    }
+
+    /// An expression `parse` reads back the same in every zone, e.g. `15m` or
+    /// `2021-01-02T00:00:00Z..2021-01-02T01:30:00Z`.
+    pub fn expression(&self) -> String {
+        let format = |t: &DateTime<Utc>| t.to_rfc3339_opts(SecondsFormat::AutoSi, true);
+        match self {
+            TimeRangeSpec::Last(duration) => duration.to_string(),
+            TimeRangeSpec::Since(start) => format(start),
+            TimeRangeSpec::Between(start, end) => format!("{}..{}", format(start), format(end)),
+        }
+    }
+}
+
+impl Default for TimeRangeSpec {
+    fn default() -> Self {
+        TimeRangeSpec::Last(HumanDuration::from(std::time::Duration::from_secs(60 * 60)))
+    }
+}
+
+fn parse_day(s: &str, zone: &TimeZoneChoice, now: &DateTime<Utc>) -> Option<NaiveDate> {
+    let today = zone.date_of(now);
+    match s {
+        "today" => Some(today),
+        "yesterday" => today.pred_opt(),
+        s => NaiveDate::parse_from_str(s, "%Y-%m-%d").ok(),
+    }
+}
+
+fn day_start(
+    date: NaiveDate,
+    s: &str,
+    zone: &TimeZoneChoice,
+) -> Result<DateTime<Utc>, ParseTimeRangeError> {
+    zone.to_utc(&date.and_hms(0, 0, 0))
+        .ok_or_else(|| ParseTimeRangeError::SkippedTime(s.to_string()))
+}
+
+/// Parses a point in time. A time without a date is on `day`, or today.
+fn parse_time(
+    s: &str,
+    zone: &TimeZoneChoice,
+    now: &DateTime<Utc>,
+    day: Option<NaiveDate>,
+) -> Result<DateTime<Utc>, ParseTimeRangeError> {
+    let invalid = || ParseTimeRangeError::InvalidTime(s.to_string());
+    let in_zone = |naive: NaiveDateTime| {
+        zone.to_utc(&naive)
+            .ok_or_else(|| ParseTimeRangeError::SkippedTime(s.to_string()))
+    };
+    if s.is_empty() {
+        return Err(invalid());
+    }
+    if s == "now" {
+        return Ok(*now);
+    }
+    if let Some(duration) = s.strip_suffix("ago") {
+        let duration = HumanDuration::from_str(duration.trim())?;
+        return Ok(*now - duration.as_chrono());
+    }
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    if let Some(naive) = DATE_TIME_FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(s, f).ok())
+    {
+        return in_zone(naive);
+    }
+
+    // today 09:00, yesterday, 2021-01-02, 10:30
+    let (date, time) = match s.find(' ') {
+        Some(i) => (Some(&s[..i]), s[i + 1..].trim()),
+        None if s.contains(':') => (None, s),
+        None => (Some(s), ""),
+    };
+    let date = match date {
+        Some(date) => parse_day(date, zone, now).ok_or_else(invalid)?,
+        None => day.unwrap_or_else(|| zone.date_of(now)),
+    };
+    let time = if time.is_empty() {
+        NaiveTime::from_hms(0, 0, 0)
+    } else {
+        TIME_FORMATS
+            .iter()
+            .find_map(|f| NaiveTime::parse_from_str(time, f).ok())
+            .ok_or_else(invalid)?
+    };
+    in_zone(date.and_time(time))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.ymd(2021, 1, 2).and_hms(12, 34, 56)
+    }
+
+    fn parse(s: &str) -> Result<TimeRange, ParseTimeRangeError> {
+        let jst = TimeZoneChoice::from_str("+09:00").unwrap();
+        TimeRangeSpec::parse(s, &jst, &now()).map(|spec| spec.resolve(&now()))
+    }
+
+    fn range(start: DateTime<Utc>, end: DateTime<Utc>) -> Result<TimeRange, ParseTimeRangeError> {
+        Ok(TimeRange { start, end })
+    }
+
+    #[test]
+    fn test_relative() {
+        let minutes = |n| now() - chrono::Duration::minutes(n);
+        assert_eq!(range(minutes(15), now()), parse("15m"));
+        assert_eq!(range(minutes(15), now()), parse("last 15m"));
+        assert_eq!(range(minutes(120), now()), parse("2h ago"));
+        assert_eq!(range(minutes(120), minutes(60)), parse("2h ago..1h ago"));
+        assert_eq!(range(minutes(90), now()), parse("1h30m ago..now"));
+    }
+
+    #[test]
+    fn test_days() {
+        // 2021-01-02 21:34:56 in +09:00
+        let jst = |d, h, m| Utc.ymd(2021, 1, d).and_hms(h, m, 0) - chrono::Duration::hours(9);
+        assert_eq!(range(jst(2, 0, 0), jst(3, 0, 0)), parse("today"));
+        assert_eq!(range(jst(1, 0, 0), jst(2, 0, 0)), parse("yesterday"));
+        assert_eq!(
+            range(jst(2, 9, 0), jst(2, 10, 30)),
+            parse("today 09:00..10:30")
+        );
+        assert_eq!(
+            range(jst(1, 22, 0), jst(2, 0, 0)),
+            parse("yesterday 22:00..today")
+        );
+        assert_eq!(range(jst(2, 9, 0), now()), parse("09:00"));
+    }
+
+    #[test]
+    fn test_iso8601() {
+        let utc = |d, h, m| Utc.ymd(2021, 1, d).and_hms(h, m, 0);
+        assert_eq!(
+            range(utc(1, 0, 0), utc(1, 1, 0)),
+            parse("2021-01-01T09:00..2021-01-01 10:00")
+        );
+        assert_eq!(
+            range(utc(1, 0, 0), utc(1, 1, 30)),
+            parse("2021-01-01T00:00:00Z..2021-01-01T10:30:00+09:00")
+        );
+        assert_eq!(
+            range(utc(1, 0, 0), utc(1, 1, 0)),
+            parse("2021-01-01T09:00..10:00")
+        );
+        assert_eq!(
+            range(Utc.ymd(2020, 12, 31).and_hms(15, 0, 0), utc(1, 15, 0)),
+            parse("2021-01-01")
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        use ParseTimeRangeError::*;
+
+        assert_eq!(Err(Empty), parse(" "));
+        assert_eq!(Err(StartAfterEnd), parse("1h ago..2h ago"));
+        assert_eq!(Err(StartAfterEnd), parse("today 10:00..09:00"));
+        assert_eq!(Err(StartAfterEnd), parse("2030-01-01T00:00"));
+        assert_eq!(Err(InvalidTime("tomorrow".to_string())), parse("tomorrow"));
+        assert_eq!(Err(InvalidTime("25:00".to_string())), parse("today..25:00"));
+        assert_eq!(
+            Err(Duration(ParseDurationError::UnknownUnit("y".to_string()))),
+            parse("1y ago")
+        );
+    }
+
+    #[test]
+    fn test_millis() {
+        let range = parse("2021-01-01T00:00:00Z..2021-01-01T00:00:01.5Z").unwrap();
+        assert_eq!(1_609_459_200_000, range.start_time());
+        assert_eq!(1_609_459_201_500, range.end_time());
+    }
+
+    #[test]
+    fn test_describe() {
+        let jst = TimeZoneChoice::from_str("+09:00").unwrap();
+        let describe = |s| {
+            TimeRangeSpec::parse(s, &jst, &now())
+                .unwrap()
+                .describe(&jst)
+        };
+        assert_eq!("last 15m", describe("15m"));
+        assert_eq!(
+            "2021-01-02 09:00:00 .. 10:30:00",
+            describe("today 09:00..10:30")
+        );
+        assert_eq!(
+            "2021-01-01 00:00:00 .. 2021-01-02 00:00:00",
+            describe("yesterday")
+        );
+    }
+
+    #[test]
+    fn test_expression() {
+        let jst = TimeZoneChoice::from_str("+09:00").unwrap();
+        let expression = |s| {
+            let spec = TimeRangeSpec::parse(s, &jst, &now()).unwrap();
+            let expression = spec.expression();
+            assert_eq!(Ok(spec), TimeRangeSpec::parse(&expression, &jst, &now()));
+            expression
+        };
+        assert_eq!("1h30m", expression("last 90m"));
+        assert_eq!("2021-01-02T00:00:00Z", expression("09:00"));
+        assert_eq!(
+            "2021-01-01T00:00:00Z..2021-01-01T00:00:01.500Z",
+            expression("2021-01-01T09:00:00+09:00..2021-01-01T00:00:01.5Z")
+        );
+    }
+}
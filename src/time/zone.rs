@@ -0,0 +1,137 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use thiserror::Error;
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[error("unknown time zone `{0}`, expected local, utc or an offset like +09:00")]
+pub struct ParseTimeZoneError(String);
+
+/// Time zone in which times are typed and shown.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TimeZoneChoice {
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+}
+
+impl Default for TimeZoneChoice {
+    fn default() -> Self {
+        TimeZoneChoice::Local
+    }
+}
+
+impl TimeZoneChoice {
+    /// The time of the local date and time in the zone, or `None` if it is skipped by a DST change.
+    pub fn to_utc(&self, naive: &NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self {
+            TimeZoneChoice::Local => Local.from_local_datetime(naive).earliest().map(utc),
+            TimeZoneChoice::Utc => Some(Utc.from_utc_datetime(naive)),
+            TimeZoneChoice::Fixed(offset) => offset.from_local_datetime(naive).earliest().map(utc),
+        }
+    }
+
+    /// The local date and time of the time in the zone.
+    pub fn to_naive(&self, time: &DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            TimeZoneChoice::Local => time.with_timezone(&Local).naive_local(),
+            TimeZoneChoice::Utc => time.naive_utc(),
+            TimeZoneChoice::Fixed(offset) => time.with_timezone(offset).naive_local(),
+        }
+    }
+
+    pub fn date_of(&self, time: &DateTime<Utc>) -> NaiveDate {
+        self.to_naive(time).date()
+    }
+
+    pub fn format(&self, time: &DateTime<Utc>, fmt: &str) -> String {
+        self.to_naive(time).format(fmt).to_string()
+    }
+}
+
+fn utc<Tz: TimeZone>(time: DateTime<Tz>) -> DateTime<Utc> {
+    time.with_timezone(&Utc)
+}
+
+impl FromStr for TimeZoneChoice {
+    type Err = ParseTimeZoneError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseTimeZoneError(s.to_string());
+        match s.trim().to_ascii_lowercase().as_str() {
+            "local" => return Ok(TimeZoneChoice::Local),
+            "utc" | "z" => return Ok(TimeZoneChoice::Utc),
+            _ => (),
+        }
+
+        // +09:00, -0530 or +9
+        let s = s.trim();
+        let sign = match s.chars().next() {
+            Some('+') => 1,
+            Some('-') => -1,
+            _ => return Err(error()),
+        };
+        let digits = s[1..].replace(':', "");
+        if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+        let (hours, minutes) = if digits.len() > 2 {
+            digits.split_at(digits.len() - 2)
+        } else {
+            (digits.as_str(), "0")
+        };
+        let (hours, minutes) = (
+            hours.parse::<i32>().map_err(|_| error())?,
+            minutes.parse::<i32>().map_err(|_| error())?,
+        );
+        if hours > 14 || minutes >= 60 {
+            return Err(error());
+        }
+
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            .map(TimeZoneChoice::Fixed)
+            .ok_or_else(error)
+    }
+}
+
+impl fmt::Display for TimeZoneChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeZoneChoice::Local => write!(f, "Local"),
+            TimeZoneChoice::Utc => write!(f, "UTC"),
+            TimeZoneChoice::Fixed(offset) => write!(f, "{}", offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let fixed = |secs| Ok(TimeZoneChoice::Fixed(FixedOffset::east(secs)));
+
+        assert_eq!(Ok(TimeZoneChoice::Local), TimeZoneChoice::from_str("local"));
+        assert_eq!(Ok(TimeZoneChoice::Utc), TimeZoneChoice::from_str("UTC"));
+        assert_eq!(fixed(9 * 3600), TimeZoneChoice::from_str("+09:00"));
+        assert_eq!(fixed(-(5 * 3600 + 1800)), TimeZoneChoice::from_str("-0530"));
+        assert_eq!(fixed(9 * 3600), TimeZoneChoice::from_str("+9"));
+        assert!(TimeZoneChoice::from_str("JST").is_err());
+        assert!(TimeZoneChoice::from_str("+25:00").is_err());
+        assert!(TimeZoneChoice::from_str("+09:75").is_err());
+    }
+
+    #[test]
+    fn test_conversion() {
+        let jst = TimeZoneChoice::from_str("+09:00").unwrap();
+        let naive = NaiveDate::from_ymd(2021, 1, 2).and_hms(9, 0, 0);
+        let time = jst.to_utc(&naive).unwrap();
+
+        assert_eq!(Utc.ymd(2021, 1, 2).and_hms(0, 0, 0), time);
+        assert_eq!(naive, jst.to_naive(&time));
+        assert_eq!("2021-01-02 09:00", jst.format(&time, "%Y-%m-%d %H:%M"));
+        assert_eq!("+09:00", jst.to_string());
+    }
+}
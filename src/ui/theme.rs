@@ -1,6 +1,8 @@
 use tui::style::{Color, Modifier, Style};
 use tui::widgets::{BorderType, Borders};
 
+use crate::aws::cwlogs::event::LogLevel;
+
 #[derive(Debug, Copy, Clone)]
 pub struct ColorStyle {
     fg: Option<Color>,
@@ -36,13 +38,19 @@ impl BlockStyle {
 pub struct ItemStyle {
     pub normal: Style,
     pub highlight: Style,
+    pub matched: Style,
 }
 
 impl ItemStyle {
-    pub fn with_colors(normal: ColorStyle, highlight: ColorStyle) -> ItemStyle {
+    pub fn with_colors(
+        normal: ColorStyle,
+        highlight: ColorStyle,
+        matched: ColorStyle,
+    ) -> ItemStyle {
         ItemStyle {
             normal: style_with_colors(Style::default(), normal),
             highlight: style_with_colors(Style::default(), highlight),
+            matched: style_with_colors(Style::default().add_modifier(Modifier::BOLD), matched),
         }
     }
 }
@@ -52,14 +60,21 @@ pub struct TableStyle {
     pub header: Style,
     pub normal: Style,
     pub highlight: Style,
+    pub matched: Style,
 }
 
 impl TableStyle {
-    pub fn with_colors(header: ColorStyle, normal: ColorStyle, highlight: ColorStyle) -> Self {
+    pub fn with_colors(
+        header: ColorStyle,
+        normal: ColorStyle,
+        highlight: ColorStyle,
+        matched: ColorStyle,
+    ) -> Self {
         TableStyle {
             header: style_with_colors(Style::default(), header),
             normal: style_with_colors(Style::default(), normal),
             highlight: style_with_colors(Style::default(), highlight),
+            matched: style_with_colors(Style::default().add_modifier(Modifier::BOLD), matched),
         }
     }
 }
@@ -77,11 +92,119 @@ impl WidgetStyle {
         header: ColorStyle,
         normal: ColorStyle,
         highlight: ColorStyle,
+        matched: ColorStyle,
     ) -> WidgetStyle {
         WidgetStyle {
             block: BlockStyle::with_color(block),
-            item: ItemStyle::with_colors(normal, highlight),
-            table: TableStyle::with_colors(header, normal, highlight),
+            item: ItemStyle::with_colors(normal, highlight, matched),
+            table: TableStyle::with_colors(header, normal, highlight, matched),
+        }
+    }
+}
+
+/// Syntax colours of pretty-printed JSON.
+#[derive(Debug, Copy, Clone)]
+pub struct JsonStyle {
+    pub key: Style,
+    pub string: Style,
+    pub number: Style,
+    pub literal: Style,
+    pub punct: Style,
+}
+
+impl Default for JsonStyle {
+    fn default() -> Self {
+        JsonStyle {
+            key: Style::default().fg(Color::Cyan),
+            string: Style::default().fg(Color::Green),
+            number: Style::default().fg(Color::Yellow),
+            literal: Style::default().fg(Color::Magenta),
+            punct: Style::default(),
+        }
+    }
+}
+
+/// Colours of event rows by level.
+#[derive(Debug, Copy, Clone)]
+pub struct LevelStyle {
+    pub trace: Style,
+    pub debug: Style,
+    pub info: Style,
+    pub warn: Style,
+    pub error: Style,
+    pub fatal: Style,
+}
+
+impl LevelStyle {
+    pub fn style(&self, level: Option<LogLevel>) -> Style {
+        match level {
+            Some(LogLevel::Trace) => self.trace,
+            Some(LogLevel::Debug) => self.debug,
+            Some(LogLevel::Info) | None => self.info,
+            Some(LogLevel::Warn) => self.warn,
+            Some(LogLevel::Error) => self.error,
+            Some(LogLevel::Fatal) => self.fatal,
+        }
+    }
+}
+
+impl Default for LevelStyle {
+    fn default() -> Self {
+        LevelStyle {
+            trace: Style::default().fg(Color::DarkGray),
+            debug: Style::default().fg(Color::Gray),
+            info: Style::default(),
+            warn: Style::default().fg(Color::Yellow),
+            error: Style::default().fg(Color::Red),
+            fatal: Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+/// Colours of groups in a merged view, taken in turn.
+#[derive(Debug, Copy, Clone)]
+pub struct GroupStyle {
+    palette: [Style; 6],
+}
+
+impl GroupStyle {
+    pub fn style(&self, i: usize) -> Style {
+        self.palette[i % self.palette.len()]
+    }
+}
+
+impl Default for GroupStyle {
+    fn default() -> Self {
+        let fg = |c| Style::default().fg(c);
+        GroupStyle {
+            palette: [
+                fg(Color::Cyan),
+                fg(Color::Magenta),
+                fg(Color::Green),
+                fg(Color::Blue),
+                fg(Color::LightYellow),
+                fg(Color::LightCyan),
+            ],
+        }
+    }
+}
+
+/// Themes switched by name at runtime.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ThemeName {
+    Dark,
+    Light,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 2] = [ThemeName::Dark, ThemeName::Light];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
         }
     }
 }
@@ -92,13 +215,19 @@ pub struct Theme {
     pub selecting_widget: WidgetStyle,
     pub normal_widget: WidgetStyle,
     pub debug_widget: WidgetStyle,
+    pub error: Style,
+    pub json: JsonStyle,
+    pub levels: LevelStyle,
+    pub groups: GroupStyle,
 }
 
 impl Default for Theme {
     fn default() -> Self {
         let header = ColorStyle::new(Some(Color::Yellow), None);
-        let with_header =
-            |block, normal, highlight| WidgetStyle::with_color(block, header, normal, highlight);
+        let matched = ColorStyle::new(Some(Color::Cyan), None);
+        let with_header = |block, normal, highlight| {
+            WidgetStyle::with_color(block, header, normal, highlight, matched)
+        };
 
         let active_widget = with_header(
             ColorStyle::new(Some(Color::LightRed), None),
@@ -129,6 +258,58 @@ impl Default for Theme {
             selecting_widget,
             normal_widget,
             debug_widget,
+            error: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            json: JsonStyle::default(),
+            levels: LevelStyle::default(),
+            groups: GroupStyle::default(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn named(name: ThemeName) -> Theme {
+        match name {
+            ThemeName::Dark => Theme::default(),
+            ThemeName::Light => Theme::light(),
+        }
+    }
+
+    /// Darker colours for terminals with a light background, where yellow and cyan fade out.
+    pub fn light() -> Theme {
+        let header = ColorStyle::new(Some(Color::Blue), None);
+        let matched = ColorStyle::new(Some(Color::Magenta), None);
+        let with_header = |block, normal, highlight| {
+            WidgetStyle::with_color(block, header, normal, highlight, matched)
+        };
+        let json = JsonStyle {
+            key: Style::default().fg(Color::Blue),
+            number: Style::default().fg(Color::Red),
+            ..JsonStyle::default()
+        };
+
+        Theme {
+            active_widget: with_header(
+                ColorStyle::new(Some(Color::Red), None),
+                ColorStyle::new(None, None),
+                ColorStyle::new(Some(Color::Red), None),
+            ),
+            selecting_widget: with_header(
+                ColorStyle::new(Some(Color::Blue), None),
+                ColorStyle::new(None, None),
+                ColorStyle::new(Some(Color::Blue), None),
+            ),
+            normal_widget: with_header(
+                ColorStyle::new(None, None),
+                ColorStyle::new(None, None),
+                ColorStyle::new(Some(Color::Green), None),
+            ),
+            debug_widget: with_header(
+                ColorStyle::new(Some(Color::Gray), None),
+                ColorStyle::new(Some(Color::Gray), None),
+                ColorStyle::new(None, None),
+            ),
+            json,
+            ..Theme::default()
         }
     }
 }
@@ -1,10 +1,22 @@
+pub mod bookmarks;
+pub mod command;
 pub mod debug;
+pub mod delete;
+pub mod event_context;
+pub mod event_detail;
+pub mod events;
 pub mod groups;
+pub mod help;
 mod helper;
+pub mod histogram;
+pub mod invocations;
 pub mod presets;
 pub mod profiles;
 pub mod search;
 pub mod stateful;
+pub mod status;
+pub mod streams;
+pub mod time_range;
 
 use tui::buffer::Buffer;
 use tui::layout::Rect;
@@ -0,0 +1,143 @@
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::widgets::{Cell, Clear, Row, Table, TableState, Widget};
+
+use crate::bookmark::{Bookmark, BookmarkStore};
+use crate::time::TimeDisplay;
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::events::short_group_name;
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, CustomWidget};
+
+static COLUMNS: [&str; 5] = ["Time", "Group", "Stream", "Note", "Message"];
+static WIDTHS: [Constraint; 5] = [
+    Constraint::Length(23),
+    Constraint::Percentage(15),
+    Constraint::Percentage(15),
+    Constraint::Percentage(20),
+    Constraint::Percentage(50),
+];
+
+/// Bookmarks of every session, with the note being typed for the selected one.
+#[derive(Debug, Clone, Default)]
+pub struct BookmarksState {
+    table: TableState,
+    note: Option<String>,
+}
+
+impl BookmarksState {
+    pub fn select_next(&mut self, bookmarks: &BookmarkStore) {
+        select_next(&mut self.table, bookmarks.len());
+    }
+
+    pub fn select_previous(&mut self, bookmarks: &BookmarkStore) {
+        select_previous(&mut self.table, bookmarks.len());
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.table.selected()
+    }
+
+    /// Keeps the selection in the list after a bookmark is removed.
+    pub fn clamp(&mut self, bookmarks: &BookmarkStore) {
+        let selected = match (self.table.selected(), bookmarks.len()) {
+            (_, 0) => None,
+            (Some(i), len) => Some(i.min(len - 1)),
+            (None, _) => None,
+        };
+        self.table.select(selected);
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.note.is_some()
+    }
+
+    /// Starts editing the note of the bookmark, from its current note.
+    pub fn edit_note(&mut self, bookmark: &Bookmark) {
+        self.note = Some(bookmark.note.clone().unwrap_or_default());
+    }
+
+    pub fn push(&mut self, c: char) {
+        if let Some(note) = self.note.as_mut() {
+            note.push(c);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(note) = self.note.as_mut() {
+            note.pop();
+        }
+    }
+
+    /// Finishes editing, returning the typed note.
+    pub fn take_note(&mut self) -> Option<String> {
+        self.note.take()
+    }
+}
+
+pub struct BookmarksWidget {
+    style: WidgetStyle,
+    time_display: TimeDisplay,
+}
+
+impl BookmarksWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        BookmarksWidget {
+            style,
+            time_display: TimeDisplay::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        BookmarksWidget {
+            time_display,
+            ..self
+        }
+    }
+}
+
+impl CustomWidget for BookmarksWidget {
+    type Data = BookmarkStore;
+    type State = BookmarksState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let area = centered_rect(90, 80, area);
+        Clear.render(area, buf);
+
+        let rows = data
+            .iter()
+            .map(|bookmark| {
+                Row::new(vec![
+                    Cell::from(self.time_display.format_millis(&bookmark.event_time())),
+                    Cell::from(short_group_name(&bookmark.group_name).to_string()),
+                    Cell::from(bookmark.stream_name.clone()),
+                    Cell::from(bookmark.note.clone().unwrap_or_default()),
+                    Cell::from(bookmark.message.trim_end().to_string()),
+                ])
+            })
+            .collect::<Vec<_>>();
+
+        let title = match state.note.as_ref() {
+            Some(note) => format!("Note: {}_ (<Enter>: save, <Esc>: cancel)", note),
+            None => format!(
+                "Bookmarks [{}] (<Enter>: jump, <n>: note, <d>: delete, <Esc>: close)",
+                data.len()
+            ),
+        };
+        let table = Table::new(rows)
+            .header(Row::new(COLUMNS.to_vec()).style(self.style.table.header))
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&WIDTHS)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
+        render_stateful_widget(table, area, buf, &mut state.table);
+    }
+}
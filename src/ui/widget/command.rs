@@ -0,0 +1,124 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Modifier, Style};
+use tui::text::{Span, Spans};
+use tui::widgets::Paragraph;
+
+use crate::command::{CommandHistory, Completion};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::{render_widget, CustomWidget};
+
+/// Candidates shown after the command line at most.
+const MAX_CANDIDATES: usize = 10;
+
+#[derive(Debug, Clone, Default)]
+pub struct CommandData {
+    input: String,
+    candidates: Vec<String>,
+    error: Option<String>,
+    pub history: CommandHistory,
+}
+
+impl CommandData {
+    pub fn input(&self) -> &str {
+        self.input.as_str()
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.candidates.clear();
+        self.error = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.candidates.clear();
+        self.error = None;
+    }
+
+    pub fn set_input(&mut self, input: &str) {
+        self.input = input.to_string();
+        self.candidates.clear();
+        self.error = None;
+    }
+
+    /// Shows the completion of the word being typed, listing what it could be.
+    pub fn set_completion(&mut self, completion: Option<Completion>) {
+        self.error = None;
+        match completion {
+            Some(completion) => {
+                self.input = completion.input;
+                self.candidates = if completion.candidates.len() > 1 {
+                    completion.candidates
+                } else {
+                    Vec::new()
+                };
+            }
+            None => {
+                self.candidates.clear();
+                self.error = Some("no completion".to_string());
+            }
+        }
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.candidates.clear();
+        self.error = Some(error);
+    }
+
+    /// Clears the line, keeping the history.
+    pub fn clear(&mut self) {
+        self.set_input("");
+        self.history.reset();
+    }
+}
+
+pub struct CommandWidget {
+    style: WidgetStyle,
+    error_style: Style,
+}
+
+impl CommandWidget {
+    pub fn with_style(style: WidgetStyle, error_style: Style) -> Self {
+        CommandWidget { style, error_style }
+    }
+
+    fn command_text_from(&self, data: &CommandData) -> Paragraph<'_> {
+        let mut spans = vec![Span::raw(format!(":{}", data.input()))];
+        if let Some(error) = data.error.as_ref() {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(error.clone(), self.error_style));
+        } else if !data.candidates.is_empty() {
+            let mut candidates =
+                data.candidates[..data.candidates.len().min(MAX_CANDIDATES)].join("  ");
+            if data.candidates.len() > MAX_CANDIDATES {
+                candidates.push_str("  ...");
+            }
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                candidates,
+                Style::default().add_modifier(Modifier::DIM),
+            ));
+        }
+
+        Paragraph::new(Spans::from(spans))
+            .block(default_block_with_style(&self.style.block, "Command"))
+    }
+}
+
+impl CustomWidget for CommandWidget {
+    type Data = CommandData;
+    type State = ();
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        _state: &mut Self::State,
+    ) {
+        let command_text = self.command_text_from(data);
+        render_widget(command_text, area, buf);
+    }
+}
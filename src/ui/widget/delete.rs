@@ -0,0 +1,192 @@
+use std::convert::TryFrom;
+
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Clear, Paragraph, Widget, Wrap};
+
+use crate::aws::cwlogs::group::{CwlGroup, CwlRetention};
+use crate::aws::cwlogs::stream::CwlStream;
+use crate::size::{HumanReadableSize, Size};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::{render_widget, CustomWidget};
+
+#[derive(Debug, Clone)]
+pub enum DeleteTarget {
+    Group(CwlGroup),
+    Groups(Vec<CwlGroup>),
+    Stream {
+        group_name: String,
+        stream: CwlStream,
+    },
+}
+
+impl DeleteTarget {
+    /// The name to type to confirm the deletion.
+    pub fn name(&self) -> String {
+        match self {
+            DeleteTarget::Group(group) => group.group_name.clone(),
+            DeleteTarget::Groups(groups) => format!("{} log groups", groups.len()),
+            DeleteTarget::Stream { stream, .. } => stream.stream_name.clone(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DeleteTarget::Group(_) => "log group",
+            DeleteTarget::Groups(_) => "bulk delete",
+            DeleteTarget::Stream { .. } => "log stream",
+        }
+    }
+
+    /// Lists what would be removed by the deletion.
+    pub fn listing(&self) -> Vec<String> {
+        match self {
+            DeleteTarget::Group(group) => {
+                let size = human_readable(group.stored);
+                vec![
+                    format!("log group      {}", group.group_name),
+                    format!("  arn          {}", group.arn),
+                    format!("  stored       {}", size),
+                    format!("  retention    {}", CwlRetention::from(group.retention)),
+                    format!(
+                        "  filters      {} metric filters",
+                        group.metric_filter_count
+                    ),
+                    "  every log stream and event in the group".to_string(),
+                ]
+            }
+            DeleteTarget::Groups(groups) => {
+                let mut lines = groups
+                    .iter()
+                    .map(|g| format!("log group      {}", g.group_name))
+                    .collect::<Vec<_>>();
+                lines.push("  every log stream and event in the groups".to_string());
+                lines
+            }
+            DeleteTarget::Stream { group_name, stream } => vec![
+                format!("log stream     {}", stream.stream_name),
+                format!("  group        {}", group_name),
+                format!("  events from  {}", stream.first_event_time),
+                format!("  events to    {}", stream.last_event_time),
+            ],
+        }
+    }
+}
+
+fn human_readable(size: Size) -> String {
+    HumanReadableSize::try_from(size)
+        .map(|s| format!("{} {}", s.size, s.unit.short_name()))
+        .unwrap_or_default()
+}
+
+/// Deletion waiting for the user to type the name of the target.
+#[derive(Debug, Clone)]
+pub struct DeleteConfirmState {
+    target: DeleteTarget,
+    input: String,
+    dry_run: bool,
+}
+
+impl DeleteConfirmState {
+    pub fn new(target: DeleteTarget) -> Self {
+        DeleteConfirmState {
+            target,
+            input: String::new(),
+            dry_run: false,
+        }
+    }
+
+    pub fn target(&self) -> &DeleteTarget {
+        &self.target
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn toggle_dry_run(&mut self) {
+        self.dry_run = !self.dry_run;
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.input == self.target.name()
+    }
+
+    /// Summary of a dry run, shown instead of deleting.
+    pub fn dry_run_message(&self) -> String {
+        match &self.target {
+            DeleteTarget::Groups(groups) => format!(
+                "dry run: would delete {} log groups ({})",
+                groups.len(),
+                human_readable(groups.iter().map(|g| g.stored).sum())
+            ),
+            target => format!("dry run: would delete {} {}", target.kind(), target.name()),
+        }
+    }
+}
+
+/// Popup asking to type the name of the log group or stream to delete.
+pub struct DeleteConfirmWidget {
+    style: WidgetStyle,
+}
+
+impl DeleteConfirmWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        DeleteConfirmWidget { style }
+    }
+}
+
+impl CustomWidget for DeleteConfirmWidget {
+    type Data = ();
+    type State = DeleteConfirmState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let header = self.style.table.header;
+        let mut lines = vec![Spans::from(Span::styled("Will be deleted:", header))];
+        lines.extend(state.target.listing().into_iter().map(Spans::from));
+        lines.push(Spans::default());
+        lines.push(Spans::from(format!(
+            "Type `{}` to confirm:",
+            state.target.name()
+        )));
+        let input_style = if state.is_confirmed() {
+            self.style.table.matched
+        } else {
+            self.style.table.normal
+        };
+        lines.push(Spans::from(vec![
+            Span::raw("> "),
+            Span::styled(state.input.as_str(), input_style),
+        ]));
+
+        let title = if state.dry_run {
+            "Delete [dry run] (<Enter>: list, <Tab>: dry run off, <Esc>: cancel)"
+        } else {
+            "Delete (<Enter>: delete, <Tab>: dry run, <Esc>: cancel)"
+        };
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(default_block_with_style(&self.style.block, title))
+            .style(self.style.table.normal)
+            .wrap(Wrap { trim: false });
+
+        let area = centered_rect(70, 50, area);
+        Clear.render(area, buf);
+        render_widget(paragraph, area, buf);
+    }
+}
@@ -0,0 +1,187 @@
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::style::Modifier;
+use tui::widgets::{Cell, Clear, Row, Table, TableState, Widget};
+
+use crate::aws::cwlogs::event::{ContextDirection, CwlEvent, CwlEventContext, CwlEventPage};
+use crate::time::TimeDisplay;
+use crate::ui::theme::{LevelStyle, WidgetStyle};
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, CustomWidget};
+
+/// Lines around an event in its stream, read a page at a time.
+#[derive(Debug, Clone)]
+pub struct EventContextState {
+    group_name: String,
+    context: CwlEventContext,
+    table: TableState,
+    /// Opening of the popup, so pages read for a popup opened before are dropped.
+    generation: u64,
+    /// Whether a page of each side is being read.
+    loading: (bool, bool),
+}
+
+impl EventContextState {
+    pub fn new(group_name: String, event: CwlEvent, generation: u64) -> Self {
+        EventContextState {
+            group_name,
+            context: CwlEventContext::new(event),
+            table: TableState::default(),
+            generation,
+            loading: (false, false),
+        }
+    }
+
+    pub fn group_name(&self) -> &str {
+        &self.group_name
+    }
+
+    pub fn context(&self) -> &CwlEventContext {
+        &self.context
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.loading.0 || self.loading.1
+    }
+
+    fn loading_mut(&mut self, direction: ContextDirection) -> &mut bool {
+        match direction {
+            ContextDirection::Before => &mut self.loading.0,
+            ContextDirection::After => &mut self.loading.1,
+        }
+    }
+
+    /// Whether a page of the side is being read.
+    pub fn is_loading_side(&self, direction: ContextDirection) -> bool {
+        match direction {
+            ContextDirection::Before => self.loading.0,
+            ContextDirection::After => self.loading.1,
+        }
+    }
+
+    pub fn start_loading(&mut self, direction: ContextDirection) {
+        *self.loading_mut(direction) = true;
+    }
+
+    pub fn fail_loading(&mut self, direction: ContextDirection) {
+        *self.loading_mut(direction) = false;
+    }
+
+    /// Adds the lines of a page, keeping the selected line or selecting the event.
+    pub fn extend(&mut self, direction: ContextDirection, page: CwlEventPage) {
+        *self.loading_mut(direction) = false;
+        let count = self.context.extend(direction, page);
+        match self.table.selected() {
+            Some(i) if direction == ContextDirection::Before => self.table.select(Some(i + count)),
+            Some(_) => (),
+            None => self.table.select(self.context.target_index()),
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        select_next(&mut self.table, self.context.events().len());
+    }
+
+    pub fn select_previous(&mut self) {
+        select_previous(&mut self.table, self.context.events().len());
+    }
+}
+
+pub struct EventContextWidget {
+    style: WidgetStyle,
+    level_style: LevelStyle,
+    time_display: TimeDisplay,
+}
+
+impl EventContextWidget {
+    pub fn with_style(style: WidgetStyle, level_style: LevelStyle) -> Self {
+        EventContextWidget {
+            style,
+            level_style,
+            time_display: TimeDisplay::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        EventContextWidget {
+            time_display,
+            ..self
+        }
+    }
+}
+
+impl CustomWidget for EventContextWidget {
+    type Data = ();
+    type State = EventContextState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let area = centered_rect(90, 90, area);
+        Clear.render(area, buf);
+
+        let context = &state.context;
+        let target = context.target_index();
+        let rows = context
+            .events()
+            .iter()
+            .enumerate()
+            .map(|(i, event)| {
+                let marker = if Some(i) == target { ">" } else { " " };
+                let style = self.level_style.style(event.level);
+                let style = if Some(i) == target {
+                    style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else {
+                    style
+                };
+                Row::new(vec![
+                    Cell::from(marker),
+                    Cell::from(self.time_display.format_millis(&event.event_time)),
+                    Cell::from(event.message.trim_end().to_string()),
+                ])
+                .style(style)
+            })
+            .collect::<Vec<_>>();
+
+        let more = |direction| {
+            if context.next_position(direction).is_some() {
+                "+"
+            } else {
+                ""
+            }
+        };
+        let mut title = format!(
+            "Context of {} in {} [{}{}{}] (<b>: earlier, <f>: later, <Esc>: close)",
+            context.target().stream_name,
+            state.group_name,
+            more(ContextDirection::Before),
+            context.events().len(),
+            more(ContextDirection::After),
+        );
+        if state.is_loading() {
+            title.push_str(" [loading]");
+        }
+
+        let widths = [
+            Constraint::Length(1),
+            Constraint::Length(23),
+            Constraint::Percentage(100),
+        ];
+        let table = Table::new(rows)
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&widths)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
+        render_stateful_widget(table, area, buf, &mut state.table);
+    }
+}
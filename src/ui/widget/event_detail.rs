@@ -0,0 +1,256 @@
+use std::collections::BTreeSet;
+
+use serde_json::Value;
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Clear, List, ListItem, ListState, Paragraph, Widget, Wrap};
+
+use crate::aws::cwlogs::event::CwlEvent;
+use crate::json::{flatten, format_value, parse_message, pretty_lines, JsonField, JsonLine};
+use crate::json::{JsonPath, JsonTokenKind};
+use crate::time::TimeDisplay;
+use crate::ui::theme::{JsonStyle, WidgetStyle};
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, render_widget, CustomWidget};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EventDetailTab {
+    Message,
+    Fields,
+}
+
+impl Default for EventDetailTab {
+    fn default() -> Self {
+        EventDetailTab::Message
+    }
+}
+
+/// A log event with its JSON message pretty-printed and flattened to fields.
+#[derive(Debug, Clone)]
+pub struct EventDetailState {
+    event: CwlEvent,
+    json: Option<Value>,
+    fields: Vec<JsonField>,
+    collapsed: BTreeSet<JsonPath>,
+    tab: EventDetailTab,
+    lines: ListState,
+    field_list: ListState,
+}
+
+impl EventDetailState {
+    pub fn new(event: CwlEvent) -> Self {
+        let json = parse_message(&event.message);
+        let fields = json.as_ref().map(flatten).unwrap_or_default();
+        let mut lines = ListState::default();
+        lines.select(Some(0));
+        let mut field_list = ListState::default();
+        field_list.select(Some(0));
+
+        EventDetailState {
+            event,
+            json,
+            fields,
+            collapsed: BTreeSet::new(),
+            tab: EventDetailTab::default(),
+            lines,
+            field_list,
+        }
+    }
+
+    pub fn event(&self) -> &CwlEvent {
+        &self.event
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.json.is_some()
+    }
+
+    pub fn tab(&self) -> EventDetailTab {
+        self.tab
+    }
+
+    /// Switches between the message and its fields, only JSON messages have fields.
+    pub fn toggle_tab(&mut self) {
+        self.tab = match self.tab {
+            EventDetailTab::Message if self.is_json() => EventDetailTab::Fields,
+            _ => EventDetailTab::Message,
+        };
+    }
+
+    fn pretty_lines(&self) -> Vec<JsonLine> {
+        self.json
+            .as_ref()
+            .map(|json| pretty_lines(json, &self.collapsed))
+            .unwrap_or_default()
+    }
+
+    pub fn select_next(&mut self) {
+        match self.tab {
+            EventDetailTab::Message => {
+                let len = self.pretty_lines().len();
+                select_next(&mut self.lines, len)
+            }
+            EventDetailTab::Fields => select_next(&mut self.field_list, self.fields.len()),
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        match self.tab {
+            EventDetailTab::Message => {
+                let len = self.pretty_lines().len();
+                select_previous(&mut self.lines, len)
+            }
+            EventDetailTab::Fields => select_previous(&mut self.field_list, self.fields.len()),
+        }
+    }
+
+    /// Collapses or expands the object or array opened on the selected line.
+    pub fn toggle_collapse(&mut self) {
+        let lines = self.pretty_lines();
+        let path = self
+            .lines
+            .selected()
+            .and_then(|i| lines.get(i))
+            .and_then(|line| line.container.clone());
+        if let Some(path) = path {
+            if !self.collapsed.remove(&path) {
+                self.collapsed.insert(path);
+            }
+        }
+    }
+
+    pub fn selected_field(&self) -> Option<&JsonField> {
+        match self.tab {
+            EventDetailTab::Message => None,
+            EventDetailTab::Fields => self.field_list.selected().and_then(|i| self.fields.get(i)),
+        }
+    }
+}
+
+pub struct EventDetailWidget {
+    style: WidgetStyle,
+    json_style: JsonStyle,
+    time_display: TimeDisplay,
+}
+
+impl EventDetailWidget {
+    pub fn with_style(style: WidgetStyle, json_style: JsonStyle) -> Self {
+        EventDetailWidget {
+            style,
+            json_style,
+            time_display: TimeDisplay::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        EventDetailWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    fn token_style(&self, kind: JsonTokenKind) -> Style {
+        match kind {
+            JsonTokenKind::Key => self.json_style.key,
+            JsonTokenKind::String => self.json_style.string,
+            JsonTokenKind::Number => self.json_style.number,
+            JsonTokenKind::Literal => self.json_style.literal,
+            JsonTokenKind::Punct => self.json_style.punct,
+        }
+    }
+
+    fn line_item(&self, line: JsonLine) -> ListItem<'static> {
+        let marker = match (&line.container, line.collapsed) {
+            (Some(_), true) => "+ ",
+            (Some(_), false) => "- ",
+            (None, _) => "  ",
+        };
+        let mut spans = vec![Span::raw(format!("{}{}", marker, "  ".repeat(line.depth)))];
+        spans.extend(
+            line.tokens
+                .into_iter()
+                .map(|t| Span::styled(t.text, self.token_style(t.kind))),
+        );
+        ListItem::new(Spans::from(spans))
+    }
+
+    fn field_item(&self, field: &JsonField, width: usize) -> ListItem<'static> {
+        ListItem::new(Spans::from(vec![
+            Span::styled(
+                format!("{:width$} ", field.path.to_string(), width = width),
+                self.json_style.key,
+            ),
+            Span::raw(format_value(&field.value)),
+        ]))
+    }
+}
+
+impl CustomWidget for EventDetailWidget {
+    type Data = ();
+    type State = EventDetailState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let area = centered_rect(90, 90, area);
+        Clear.render(area, buf);
+
+        let event = &state.event;
+        let help = match state.tab {
+            EventDetailTab::Message if state.is_json() => {
+                "<Tab>: fields, <Enter>: collapse, <Esc>: close"
+            }
+            EventDetailTab::Message => "<Esc>: close",
+            EventDetailTab::Fields => "<Tab>: message, <f>: filter, <c>: column, <Esc>: close",
+        };
+        let title = format!(
+            "{} {} ({})",
+            self.time_display.format_millis(&event.event_time),
+            event.stream_name,
+            help
+        );
+        let block = default_block_with_style(&self.style.block, &title);
+        let highlight = self.style.item.highlight;
+
+        match state.tab {
+            EventDetailTab::Message if state.is_json() => {
+                let items = state
+                    .pretty_lines()
+                    .into_iter()
+                    .map(|line| self.line_item(line))
+                    .collect::<Vec<_>>();
+                let list = List::new(items).block(block).highlight_style(highlight);
+                render_stateful_widget(list, area, buf, &mut state.lines);
+            }
+            EventDetailTab::Message => {
+                let paragraph = Paragraph::new(Text::raw(event.message.trim_end()))
+                    .block(block)
+                    .style(self.style.table.normal)
+                    .wrap(Wrap { trim: false });
+                render_widget(paragraph, area, buf);
+            }
+            EventDetailTab::Fields => {
+                let width = state
+                    .fields
+                    .iter()
+                    .map(|f| f.path.to_string().chars().count())
+                    .max()
+                    .unwrap_or(0);
+                let items = state
+                    .fields
+                    .iter()
+                    .map(|field| self.field_item(field, width))
+                    .collect::<Vec<_>>();
+                let list = List::new(items).block(block).highlight_style(highlight);
+                render_stateful_widget(list, area, buf, &mut state.field_list);
+            }
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::BTreeSet;
+
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::widgets::{Cell, Row, Table, TableState};
+
+use crate::aws::cwlogs::event::{CwlEvent, CwlEventStore, LogLevel};
+use crate::collection::Length;
+use crate::json::{format_value, JsonPath};
+use crate::time::{format_delta, TimeDisplay};
+use crate::ui::theme::{GroupStyle, LevelStyle, WidgetStyle};
+use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::stateful::table::TableStateMut;
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, CustomWidget};
+
+#[derive(Debug, Clone, Default)]
+pub struct EventsState {
+    table: TableState,
+    show_delta: bool,
+    min_level: Option<LogLevel>,
+    /// Groups of a merged view whose events are hidden.
+    muted: BTreeSet<String>,
+    /// Where the marked range of events starts, it ends at the selected event.
+    anchor: Option<usize>,
+}
+
+impl EventsState {
+    /// Events are shown from the oldest one, hiding ones below the minimum level
+    /// and ones of muted groups.
+    pub fn visible_events<'a>(
+        &'a self,
+        data: &'a CwlEventStore,
+    ) -> impl Iterator<Item = &'a CwlEvent> {
+        let min_level = self.min_level;
+        data.order_by_asc().filter(move |event| {
+            event.level >= min_level
+                && !event
+                    .group_name
+                    .as_ref()
+                    .map(|g| self.muted.contains(g))
+                    .unwrap_or(false)
+        })
+    }
+
+    pub fn selected_event<'a>(&'a self, data: &'a CwlEventStore) -> Option<&'a CwlEvent> {
+        self.table
+            .selected()
+            .and_then(|i| self.visible_events(data).nth(i))
+    }
+
+    /// Events from the anchor to the selected one, or only the selected one without an anchor.
+    pub fn selected_range<'a>(&'a self, data: &'a CwlEventStore) -> Vec<&'a CwlEvent> {
+        let selected = match self.table.selected() {
+            Some(i) => i,
+            None => return Vec::new(),
+        };
+        let anchor = self.anchor.unwrap_or(selected);
+        let (start, end) = (anchor.min(selected), anchor.max(selected));
+        self.visible_events(data)
+            .skip(start)
+            .take(end - start + 1)
+            .collect()
+    }
+
+    pub fn has_anchor(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// Starts a marked range at the selected event, or clears the range.
+    pub fn toggle_anchor(&mut self) {
+        self.anchor = match self.anchor {
+            Some(_) => None,
+            None => self.table.selected(),
+        };
+    }
+
+    pub fn clear_anchor(&mut self) {
+        self.anchor = None;
+    }
+
+    pub fn select_next(&mut self, data: &CwlEventStore) {
+        let len = self.visible_events(data).count();
+        select_next(&mut self.table, len);
+    }
+
+    pub fn select_previous(&mut self, data: &CwlEventStore) {
+        let len = self.visible_events(data).count();
+        select_previous(&mut self.table, len);
+    }
+
+    pub fn min_level(&self) -> Option<LogLevel> {
+        self.min_level
+    }
+
+    /// Raises the minimum level of shown events, or shows every event after FATAL.
+    /// Events of unknown levels are hidden as well while a minimum level is set.
+    pub fn cycle_min_level(&mut self) {
+        self.min_level = match self.min_level {
+            None => Some(LogLevel::Debug),
+            Some(LogLevel::Fatal) => None,
+            Some(level) => LogLevel::ALL.iter().copied().find(|l| *l > level),
+        };
+        self.table.select(None);
+        self.anchor = None;
+    }
+
+    pub fn is_muted(&self, group_name: &str) -> bool {
+        self.muted.contains(group_name)
+    }
+
+    /// Hides or shows events of the group, returns whether they are hidden.
+    pub fn toggle_mute(&mut self, group_name: &str) -> bool {
+        if !self.muted.remove(group_name) {
+            self.muted.insert(group_name.to_string());
+        }
+        self.table.select(None);
+        self.anchor = None;
+        self.is_muted(group_name)
+    }
+
+    /// Shows or hides the time since the previous event.
+    pub fn toggle_delta(&mut self) {
+        self.show_delta = !self.show_delta;
+    }
+
+    pub fn reset(&mut self) {
+        self.table.select(None);
+        self.anchor = None;
+    }
+}
+
+impl TableStateMut for EventsState {
+    fn table_state_mut(&mut self) -> &mut TableState {
+        &mut self.table
+    }
+}
+
+pub struct EventsWidget {
+    style: WidgetStyle,
+    time_display: TimeDisplay,
+    title: String,
+    show_stream: bool,
+    columns: Vec<JsonPath>,
+    level_style: LevelStyle,
+    groups: Vec<String>,
+    group_style: GroupStyle,
+}
+
+/// The last part of a group name, like `api` of `/aws/lambda/api`.
+pub fn short_group_name(group_name: &str) -> &str {
+    group_name
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(group_name)
+}
+
+impl EventsWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        EventsWidget {
+            style,
+            time_display: TimeDisplay::default(),
+            title: "Events".to_string(),
+            show_stream: true,
+            columns: Vec::new(),
+            level_style: LevelStyle::default(),
+            groups: Vec::new(),
+            group_style: GroupStyle::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        EventsWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    pub fn title(self, title: String) -> Self {
+        EventsWidget { title, ..self }
+    }
+
+    /// Shows the stream of each event, when events of several streams are listed.
+    pub fn show_stream(self, show_stream: bool) -> Self {
+        EventsWidget {
+            show_stream,
+            ..self
+        }
+    }
+
+    pub fn level_style(self, level_style: LevelStyle) -> Self {
+        EventsWidget {
+            level_style,
+            ..self
+        }
+    }
+
+    pub fn group_style(self, group_style: GroupStyle) -> Self {
+        EventsWidget {
+            group_style,
+            ..self
+        }
+    }
+
+    /// Shows the group of each event in its colour, when events of the groups are merged.
+    pub fn groups(self, groups: Vec<String>) -> Self {
+        EventsWidget { groups, ..self }
+    }
+
+    /// Shows the fields of JSON messages as columns.
+    pub fn columns(self, columns: Vec<JsonPath>) -> Self {
+        EventsWidget { columns, ..self }
+    }
+}
+
+impl CustomWidget for EventsWidget {
+    type Data = CwlEventStore;
+    type State = EventsState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let mut header = vec!["Time".to_string()];
+        let mut widths = vec![Constraint::Length(23)];
+        if state.show_delta {
+            header.push("Delta".to_string());
+            widths.push(Constraint::Length(9));
+        }
+        header.push("Level".to_string());
+        widths.push(Constraint::Length(5));
+        if !self.groups.is_empty() {
+            header.push("Group".to_string());
+            widths.push(Constraint::Percentage(12));
+        }
+        if self.show_stream {
+            header.push("Stream".to_string());
+            widths.push(Constraint::Percentage(20));
+        }
+        for path in self.columns.iter() {
+            header.push(path.to_string());
+            widths.push(Constraint::Percentage(15));
+        }
+        header.push("Message".to_string());
+        widths.push(Constraint::Percentage(100));
+
+        let mut previous: Option<&CwlEvent> = None;
+        let mut count = 0;
+        let rows = state
+            .visible_events(data)
+            .map(|event| {
+                count += 1;
+                let mut cells = vec![Cell::from(
+                    self.time_display.format_millis(&event.event_time),
+                )];
+                if state.show_delta {
+                    let delta = previous
+                        .map(|p| format_delta(event.event_time - p.event_time))
+                        .unwrap_or_default();
+                    cells.push(Cell::from(format!("{:>9}", delta)));
+                }
+                let level = event.level.map(|l| l.to_string()).unwrap_or_default();
+                cells.push(Cell::from(level));
+                if !self.groups.is_empty() {
+                    let group_name = event.group_name.as_deref().unwrap_or_default();
+                    let i = self.groups.iter().position(|g| g == group_name);
+                    let cell = Cell::from(short_group_name(group_name).to_string());
+                    cells.push(match i {
+                        Some(i) => cell.style(self.group_style.style(i)),
+                        None => cell,
+                    });
+                }
+                if self.show_stream {
+                    cells.push(Cell::from(event.stream_name.clone()));
+                }
+                if !self.columns.is_empty() {
+                    let json = data.json(event);
+                    cells.extend(self.columns.iter().map(|path| {
+                        let value = json.and_then(|json| path.lookup(json));
+                        Cell::from(value.map(format_value).unwrap_or_default())
+                    }));
+                }
+                cells.push(Cell::from(event.message.trim_end().to_string()));
+                previous = Some(event);
+                Row::new(cells).style(self.level_style.style(event.level))
+            })
+            .collect::<Vec<_>>();
+
+        let mut title = match state.min_level {
+            Some(level) => format!("{} [{}/{}, {}+]", self.title, count, data.len(), level),
+            None if !state.muted.is_empty() => {
+                format!("{} [{}/{}]", self.title, count, data.len())
+            }
+            None => format!("{} [{}]", self.title, data.len()),
+        };
+        if let (Some(anchor), Some(selected)) = (state.anchor, state.table.selected()) {
+            let lines = anchor.max(selected) - anchor.min(selected) + 1;
+            title.push_str(&format!(" [{} marked]", lines));
+        }
+        if data.is_loading() {
+            title.push_str(" [loading]");
+        }
+
+        let table = Table::new(rows)
+            .header(Row::new(header).style(self.style.table.header))
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&widths)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
+
+        render_stateful_widget(table, area, buf, &mut state.table);
+    }
+}
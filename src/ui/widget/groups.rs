@@ -1,45 +1,153 @@
+mod analytics;
+mod bulk;
+mod chooser;
+mod column;
+mod detail;
+mod export;
+mod retention;
+mod tree;
+
+pub use analytics::{AnalyticsState, AnalyticsWidget};
+pub use bulk::{
+    BulkAction, BulkMenuState, BulkMenuWidget, BulkSummaryState, BulkSummaryWidget, TagInputState,
+    TagInputWidget,
+};
+pub use chooser::{ColumnChooserState, ColumnChooserWidget};
+pub use column::{ColumnEntry, ColumnWidth, GroupColumn, GroupColumns, ParseColumnWidthError};
+pub use detail::{GroupDetailData, GroupDetailState, GroupDetailStatus, GroupDetailWidget};
+pub use export::to_csv;
+pub use retention::{RetentionEditorState, RetentionEditorWidget};
+pub use tree::{GroupTreeState, GroupTreeWidget, GroupsView};
+
+use std::collections::BTreeSet;
 use std::convert::TryFrom;
 
-use once_cell::sync::Lazy;
 use tui::buffer::Buffer;
 use tui::layout::{Constraint, Rect};
-use tui::widgets::{Row, Table, TableState};
+use tui::widgets::{Cell, Row, Table, TableState};
 
-use crate::aws::cwlogs::group::{CwlGroup, CwlGroupStore};
+use crate::aws::cwlogs::group::{CwlGroup, CwlGroupStore, GroupSort};
+use crate::aws::Arn;
+use crate::matcher::Matched;
 use crate::size::HumanReadableSize;
+use crate::time::TimeDisplay;
 use crate::ui::theme::WidgetStyle;
-use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::helper::{default_block_with_style, highlight_matches};
 use crate::ui::widget::stateful::table::TableStateMut;
 use crate::ui::widget::{render_stateful_widget, CustomWidget};
 
-struct Column {
-    name: &'static str,
-    width: Constraint,
-}
-
-static COLUMNS: Lazy<Vec<Column>> = Lazy::new(|| {
-    vec![
-        Column {
-            name: "Name",
-            width: Constraint::Percentage(60),
-        },
-        Column {
-            name: "Created at( Local)",
-            width: Constraint::Length(20),
-        },
-        Column {
-            name: "Stored Size",
-            width: Constraint::Length(10),
-        },
-    ]
-});
-
-static COLUMN_WIDTH: Lazy<Vec<Constraint>> =
-    Lazy::new(|| COLUMNS.iter().map(|c| c.width).collect());
-
 #[derive(Debug, Clone, Default)]
 pub struct GroupsStates {
     table: TableState,
+    columns: GroupColumns,
+    marked: BTreeSet<Arn>,
+    view: GroupsView,
+    tree: GroupTreeState,
+}
+
+impl GroupsStates {
+    pub fn with_columns(columns: GroupColumns) -> Self {
+        GroupsStates {
+            columns,
+            ..Default::default()
+        }
+    }
+
+    pub fn columns(&self) -> &GroupColumns {
+        &self.columns
+    }
+
+    pub fn columns_mut(&mut self) -> &mut GroupColumns {
+        &mut self.columns
+    }
+
+    pub fn view(&self) -> GroupsView {
+        self.view
+    }
+
+    /// Switches between the table and the tree of the groups.
+    pub fn toggle_view(&mut self) {
+        self.view = match self.view {
+            GroupsView::Table => GroupsView::Tree,
+            GroupsView::Tree => GroupsView::Table,
+        };
+    }
+
+    pub fn tree(&self) -> &GroupTreeState {
+        &self.tree
+    }
+
+    pub fn tree_mut(&mut self) -> &mut GroupTreeState {
+        &mut self.tree
+    }
+
+    /// Changes the order of the groups, keeping the same group selected.
+    pub fn set_sort(&mut self, sort: GroupSort, data: &mut CwlGroupStore) {
+        let selected = self.selected_group(data).map(|group| group.arn.clone());
+        data.set_sort(sort);
+
+        if let Some(arn) = selected {
+            let i = data.sorted().iter().position(|m| m.item.arn == arn);
+            self.table.select(i);
+        }
+    }
+
+    pub fn cycle_sort_column(&mut self, data: &mut CwlGroupStore) {
+        let sort = data.sort();
+        self.set_sort(GroupSort::new(sort.column.next(), sort.order), data);
+    }
+
+    pub fn reverse_sort_order(&mut self, data: &mut CwlGroupStore) {
+        let sort = data.sort();
+        self.set_sort(GroupSort::new(sort.column, sort.order.reversed()), data);
+    }
+
+    /// Marks the selected group, or unmarks it if it is already marked.
+    pub fn toggle_mark(&mut self, data: &CwlGroupStore) {
+        if let Some(arn) = self.selected_group(data).map(|g| g.arn.clone()) {
+            if !self.marked.remove(&arn) {
+                self.marked.insert(arn);
+            }
+        }
+    }
+
+    /// Marks every group matching the query, or unmarks them if all of them are marked.
+    pub fn toggle_mark_all(&mut self, data: &CwlGroupStore) {
+        let matched = data.sorted();
+        let arns = matched.iter().map(|m| &m.item.arn);
+        if matched.iter().all(|m| self.marked.contains(&m.item.arn)) {
+            arns.for_each(|arn| {
+                self.marked.remove(arn);
+            });
+        } else {
+            self.marked.extend(arns.cloned());
+        }
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+
+    pub fn is_marked(&self, group: &CwlGroup) -> bool {
+        self.marked.contains(&group.arn)
+    }
+
+    /// Returns the marked groups in the order of the table, including ones hidden by the query.
+    pub fn marked_groups<'a>(&self, data: &'a CwlGroupStore) -> Vec<&'a CwlGroup> {
+        data.order_by_name_asc()
+            .filter(|g| self.is_marked(g))
+            .collect()
+    }
+
+    pub fn selected_group<'a>(&self, data: &'a CwlGroupStore) -> Option<&'a CwlGroup> {
+        if self.view == GroupsView::Tree {
+            self.tree.selected_group(data)
+        } else if let Some(i) = self.table.selected() {
+            data.get(i)
+        } else {
+            None
+        }
+    }
 }
 
 impl TableStateMut for GroupsStates {
@@ -48,13 +156,42 @@ impl TableStateMut for GroupsStates {
     }
 }
 
+const SECONDS_IN_DAY: u64 = 24 * 60 * 60;
+
 pub struct GroupsWidget {
     style: WidgetStyle,
+    time_display: TimeDisplay,
 }
 
 impl GroupsWidget {
     pub fn with_style(style: WidgetStyle) -> Self {
-        GroupsWidget { style }
+        GroupsWidget {
+            style,
+            time_display: TimeDisplay::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        GroupsWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    fn row_from<'a>(&self, m: &Matched<'a, CwlGroup>, state: &GroupsStates) -> Row<'a> {
+        let formatter = CwlGroupFormatter::new(m.item, self.time_display);
+        let mark = if state.is_marked(m.item) { "*" } else { " " };
+        let cells = state.columns.visible().map(|e| match e.column {
+            GroupColumn::Name => Cell::from(highlight_matches(
+                m.item.group_name.as_str(),
+                m.indices(),
+                self.style.table.normal,
+                self.style.table.matched,
+            )),
+            column => Cell::from(formatter.format(column)),
+        });
+
+        Row::new(Some(Cell::from(mark)).into_iter().chain(cells))
     }
 }
 
@@ -69,17 +206,42 @@ impl CustomWidget for GroupsWidget {
         data: &Self::Data,
         state: &mut Self::State,
     ) {
-        let table = Table::new(
-            COLUMNS.iter().map(|c| c.name),
-            data.order_by_name_asc()
-                .map(|g| Row::Data(CwlGroupFormatter::new(g).format().into_iter())),
+        if state.view == GroupsView::Tree {
+            return GroupTreeWidget::with_style(self.style)
+                .render_app_widget(area, buf, data, state);
+        }
+
+        let columns = &state.columns;
+        let header = Row::new(
+            Some(Cell::from(" ")).into_iter().chain(
+                columns
+                    .visible()
+                    .map(|e| Cell::from(e.column.header(data.sort()))),
+            ),
         )
-        .block(default_block_with_style(&self.style.block, "Groups"))
-        .header_style(self.style.table.header)
-        .widths(&COLUMN_WIDTH)
-        .style(self.style.table.normal)
-        .highlight_style(self.style.table.highlight)
-        .column_spacing(1);
+        .style(self.style.table.header)
+        .bottom_margin(1);
+        let widths = Some(Constraint::Length(1))
+            .into_iter()
+            .chain(columns.visible().map(|e| Constraint::from(e.width)))
+            .collect::<Vec<_>>();
+        let rows = data
+            .sorted()
+            .iter()
+            .map(|m| self.row_from(m, state))
+            .collect::<Vec<_>>();
+        let title = match state.marked_groups(data).len() {
+            0 => data.label().to_string(),
+            n => format!("{} [{} marked]", data.label(), n),
+        };
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&widths)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
 
         render_stateful_widget(table, area, buf, &mut state.table);
     }
@@ -87,29 +249,41 @@ impl CustomWidget for GroupsWidget {
 
 struct CwlGroupFormatter<'a> {
     group: &'a CwlGroup,
+    time_display: TimeDisplay,
 }
 
 impl<'a> CwlGroupFormatter<'a> {
-    fn new(group: &'a CwlGroup) -> Self {
-        CwlGroupFormatter { group }
+    fn new(group: &'a CwlGroup, time_display: TimeDisplay) -> Self {
+        CwlGroupFormatter {
+            group,
+            time_display,
+        }
     }
 
-    fn format(&self) -> Vec<String> {
-        let group_name = self.group_name();
-        let creation_time = self.creation_time();
-        let size = self.stored();
-        vec![group_name, creation_time, size]
+    fn format(&self, column: GroupColumn) -> String {
+        use GroupColumn::*;
+        match column {
+            Name => self.group.group_name.clone(),
+            Retention => self.retention(),
+            Created => self.creation_time(),
+            Stored => self.stored(),
+            Arn => self.group.arn.to_string(),
+            Account => self.group.arn.account_id().unwrap_or("-").to_string(),
+            Region => self.group.arn.region().unwrap_or("-").to_string(),
+            MetricFilters => format!("{:>7}", self.group.metric_filter_count),
+            KmsKey => self.group.kms_key_id.as_deref().unwrap_or("-").to_string(),
+        }
     }
 
-    fn group_name(&self) -> String {
-        self.group.group_name.clone()
+    fn retention(&self) -> String {
+        self.group
+            .retention
+            .map(|r| format!("{} days", r.as_secs() / SECONDS_IN_DAY))
+            .unwrap_or_else(|| "Never".to_string())
     }
 
     fn creation_time(&self) -> String {
-        self.group
-            .creation_time_local()
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string()
+        self.time_display.format(&self.group.creation_time)
     }
 
     fn stored(&self) -> String {
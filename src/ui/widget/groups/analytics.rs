@@ -0,0 +1,198 @@
+use std::convert::TryFrom;
+
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::Style;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, Widget};
+
+use crate::aws::cwlogs::group::{
+    monthly_cost, CwlGroupAnalytics, CwlGroupStore, CwlRetention, StorageCostConfig,
+};
+use crate::size::{HumanReadableSize, Size};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::{render_widget, CustomWidget};
+
+const MAX_PREFIX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct AnalyticsState {
+    config: StorageCostConfig,
+}
+
+impl AnalyticsState {
+    pub fn new(config: StorageCostConfig) -> Self {
+        AnalyticsState { config }
+    }
+
+    /// Totals by longer name prefixes.
+    pub fn deepen(&mut self) {
+        self.config.prefix_depth = (self.config.prefix_depth + 1).min(MAX_PREFIX_DEPTH);
+    }
+
+    /// Totals by shorter name prefixes.
+    pub fn shallow(&mut self) {
+        self.config.prefix_depth = self.config.prefix_depth.saturating_sub(1).max(1);
+    }
+}
+
+/// Popup showing where the stored bytes of the shown groups are, and what they cost.
+pub struct AnalyticsWidget {
+    style: WidgetStyle,
+    warning_style: Style,
+}
+
+impl AnalyticsWidget {
+    pub fn with_style(style: WidgetStyle, warning_style: Style) -> Self {
+        AnalyticsWidget {
+            style,
+            warning_style,
+        }
+    }
+
+    fn size(size: Size) -> String {
+        HumanReadableSize::try_from(size)
+            .map(|s| format!("{:>4} {}", s.size, s.unit.short_name()))
+            .unwrap_or_default()
+    }
+
+    fn cost(&self, size: Size, config: &StorageCostConfig) -> String {
+        format!("${:>10.2}", monthly_cost(size, config.price_per_gib))
+    }
+
+    fn summary<'a>(&self, analytics: &CwlGroupAnalytics, config: &StorageCostConfig) -> Text<'a> {
+        let mut lines = vec![Spans::from(format!(
+            "{} groups, {} stored, ${:.2} / month (at ${} per GiB)",
+            analytics.group_count,
+            AnalyticsWidget::size(analytics.total).trim(),
+            monthly_cost(analytics.total, config.price_per_gib),
+            config.price_per_gib
+        ))];
+        if analytics.no_retention_count > 0 {
+            lines.push(Spans::from(Span::styled(
+                format!(
+                    "{} groups keep events forever (no retention)",
+                    analytics.no_retention_count
+                ),
+                self.warning_style,
+            )));
+        }
+        Text::from(lines)
+    }
+}
+
+impl CustomWidget for AnalyticsWidget {
+    type Data = CwlGroupStore;
+    type State = AnalyticsState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let config = &state.config;
+        let matched = data.matched();
+        let analytics = CwlGroupAnalytics::new(
+            matched.iter().map(|m| m.item),
+            config.prefix_depth,
+            config.top_groups,
+        );
+
+        let area = centered_rect(90, 90, area);
+        Clear.render(area, buf);
+        let block = default_block_with_style(
+            &self.style.block,
+            "Storage (+/-: prefix depth, <Esc>: close)",
+        );
+        let inner = block.inner(area);
+        render_widget(block, area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Percentage(45),
+                Constraint::Min(0),
+            ])
+            .split(inner);
+
+        render_widget(
+            Paragraph::new(self.summary(&analytics, config)),
+            chunks[0],
+            buf,
+        );
+
+        // totals per name prefix
+        let header = Row::new(vec![
+            "Prefix",
+            "Groups",
+            "Stored",
+            "Cost/month",
+            "No retention",
+        ])
+        .style(self.style.table.header);
+        let rows = analytics.prefixes.iter().map(|u| {
+            let row = Row::new(vec![
+                u.prefix.clone(),
+                format!("{:>6}", u.group_count),
+                AnalyticsWidget::size(u.stored),
+                self.cost(u.stored, config),
+                format!("{:>12}", u.no_retention_count),
+            ]);
+            if u.no_retention_count > 0 {
+                row.style(self.warning_style)
+            } else {
+                row
+            }
+        });
+        let title = format!("By prefix (depth {})", config.prefix_depth);
+        let widths = [
+            Constraint::Percentage(50),
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Length(11),
+            Constraint::Length(12),
+        ];
+        let table = Table::new(rows.collect::<Vec<_>>())
+            .header(header)
+            .block(Block::default().borders(Borders::TOP).title(title))
+            .widths(&widths)
+            .style(self.style.table.normal)
+            .column_spacing(1);
+        render_widget(table, chunks[1], buf);
+
+        // largest groups
+        let header = Row::new(vec!["Group", "Retention", "Stored", "Cost/month"])
+            .style(self.style.table.header);
+        let rows = analytics.top.iter().map(|g| {
+            let row = Row::new(vec![
+                g.group_name.clone(),
+                CwlRetention::from(g.retention).to_string(),
+                AnalyticsWidget::size(g.stored),
+                self.cost(g.stored, config),
+            ]);
+            if g.retention.is_none() {
+                row.style(self.warning_style)
+            } else {
+                row
+            }
+        });
+        let title = format!("Top {} groups", config.top_groups);
+        let widths = [
+            Constraint::Percentage(60),
+            Constraint::Length(9),
+            Constraint::Length(7),
+            Constraint::Length(11),
+        ];
+        let table = Table::new(rows.collect::<Vec<_>>())
+            .header(header)
+            .block(Block::default().borders(Borders::TOP).title(title))
+            .widths(&widths)
+            .style(self.style.table.normal)
+            .column_spacing(1);
+        render_widget(table, chunks[2], buf);
+    }
+}
@@ -0,0 +1,295 @@
+use std::cmp::min;
+
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Clear, List, ListItem, ListState, Paragraph, Widget};
+
+use crate::aws::cwlogs::group::{
+    parse_tag_keys, parse_tags, CwlGroupBulkOp, CwlGroupBulkResult, ParseTagsError,
+};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::{apply_item_style, centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, render_widget, CustomWidget};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum BulkAction {
+    SetRetention,
+    AddTags,
+    RemoveTags,
+    Delete,
+    Export,
+    MergeEvents,
+}
+
+impl BulkAction {
+    pub const ALL: [BulkAction; 6] = [
+        BulkAction::SetRetention,
+        BulkAction::AddTags,
+        BulkAction::RemoveTags,
+        BulkAction::Delete,
+        BulkAction::Export,
+        BulkAction::MergeEvents,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            BulkAction::SetRetention => "Set retention",
+            BulkAction::AddTags => "Add tags",
+            BulkAction::RemoveTags => "Remove tags",
+            BulkAction::Delete => "Delete",
+            BulkAction::Export => "Export as CSV",
+            BulkAction::MergeEvents => "Merge events",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkMenuState {
+    list: ListState,
+}
+
+impl BulkMenuState {
+    pub fn select_next(&mut self) {
+        select_next(&mut self.list, BulkAction::ALL.len());
+    }
+
+    pub fn select_previous(&mut self) {
+        select_previous(&mut self.list, BulkAction::ALL.len());
+    }
+
+    pub fn selected(&self) -> Option<BulkAction> {
+        self.list
+            .selected()
+            .and_then(|i| BulkAction::ALL.get(i))
+            .copied()
+    }
+}
+
+impl Default for BulkMenuState {
+    fn default() -> Self {
+        let mut list = ListState::default();
+        list.select(Some(0));
+        BulkMenuState { list }
+    }
+}
+
+/// Popup listing the operations applicable to the marked groups.
+pub struct BulkMenuWidget {
+    style: WidgetStyle,
+}
+
+impl BulkMenuWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        BulkMenuWidget { style }
+    }
+}
+
+impl CustomWidget for BulkMenuWidget {
+    type Data = usize;
+    type State = BulkMenuState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let style = &self.style.item;
+        let items = BulkAction::ALL
+            .iter()
+            .map(|a| ListItem::new(a.title()))
+            .map(|i| apply_item_style(i, style))
+            .collect::<Vec<_>>();
+
+        let title = format!("{} marked groups (<Enter>: choose, <Esc>: close)", data);
+        let list = List::new(items)
+            .block(default_block_with_style(&self.style.block, &title))
+            .highlight_style(style.highlight);
+
+        let area = centered_rect(50, 30, area);
+        Clear.render(area, buf);
+        render_stateful_widget(list, area, buf, &mut state.list);
+    }
+}
+
+/// Text input of tags to add to or remove from the marked groups.
+#[derive(Debug, Clone)]
+pub struct TagInputState {
+    remove: bool,
+    input: String,
+    error: Option<ParseTagsError>,
+}
+
+impl TagInputState {
+    pub fn add() -> Self {
+        TagInputState::new(false)
+    }
+
+    pub fn remove() -> Self {
+        TagInputState::new(true)
+    }
+
+    fn new(remove: bool) -> Self {
+        TagInputState {
+            remove,
+            input: String::new(),
+            error: None,
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.error = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.error = None;
+    }
+
+    /// Parses the input, keeping the error to show it.
+    pub fn parse(&mut self) -> Option<CwlGroupBulkOp> {
+        let op = if self.remove {
+            parse_tag_keys(&self.input).map(CwlGroupBulkOp::RemoveTags)
+        } else {
+            parse_tags(&self.input).map(CwlGroupBulkOp::AddTags)
+        };
+
+        match op {
+            Ok(op) => Some(op),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+pub struct TagInputWidget {
+    style: WidgetStyle,
+    error_style: Style,
+}
+
+impl TagInputWidget {
+    pub fn with_style(style: WidgetStyle, error_style: Style) -> Self {
+        TagInputWidget { style, error_style }
+    }
+}
+
+impl CustomWidget for TagInputWidget {
+    type Data = ();
+    type State = TagInputState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let (title, hint) = if state.remove {
+            ("Remove tags (<Enter>: apply, <Esc>: cancel)", "key1,key2")
+        } else {
+            (
+                "Add tags (<Enter>: apply, <Esc>: cancel)",
+                "key1=value1,key2=value2",
+            )
+        };
+
+        let mut lines = vec![
+            Spans::from(Span::styled(hint, self.style.table.header)),
+            Spans::from(format!("> {}", state.input)),
+        ];
+        if let Some(e) = state.error.as_ref() {
+            lines.push(Spans::from(Span::styled(e.to_string(), self.error_style)));
+        }
+
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(default_block_with_style(&self.style.block, title))
+            .style(self.style.table.normal);
+
+        let area = centered_rect(60, 20, area);
+        Clear.render(area, buf);
+        render_widget(paragraph, area, buf);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BulkSummaryState {
+    result: CwlGroupBulkResult,
+    scroll: u16,
+}
+
+impl BulkSummaryState {
+    pub fn new(result: CwlGroupBulkResult) -> Self {
+        BulkSummaryState { result, scroll: 0 }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+/// Popup showing the outcome of a bulk operation for each group.
+pub struct BulkSummaryWidget {
+    style: WidgetStyle,
+    error_style: Style,
+}
+
+impl BulkSummaryWidget {
+    pub fn with_style(style: WidgetStyle, error_style: Style) -> Self {
+        BulkSummaryWidget { style, error_style }
+    }
+}
+
+impl CustomWidget for BulkSummaryWidget {
+    type Data = ();
+    type State = BulkSummaryState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let mut lines = vec![
+            Spans::from(Span::styled(
+                state.result.summary(),
+                self.style.table.header,
+            )),
+            Spans::default(),
+        ];
+        for outcome in state.result.outcomes.iter() {
+            let name = outcome.group.group_name.as_str();
+            lines.push(match outcome.error.as_ref() {
+                None => Spans::from(format!("ok    {}", name)),
+                Some(e) => Spans::from(Span::styled(
+                    format!("error {}: {}", name, e),
+                    self.error_style,
+                )),
+            });
+        }
+        state.scroll = min(state.scroll, lines.len().saturating_sub(1) as u16);
+
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(default_block_with_style(
+                &self.style.block,
+                "Bulk result (j/k: scroll, <Esc>: close)",
+            ))
+            .style(self.style.table.normal)
+            .scroll((state.scroll, 0));
+
+        let area = centered_rect(80, 70, area);
+        Clear.render(area, buf);
+        render_widget(paragraph, area, buf);
+    }
+}
@@ -0,0 +1,77 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::widgets::{Clear, List, ListItem, ListState, Widget};
+
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::groups::column::{ColumnWidth, GroupColumns};
+use crate::ui::widget::helper::{apply_item_style, centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::list::ListStateMut;
+use crate::ui::widget::{render_stateful_widget, CustomWidget};
+
+#[derive(Debug, Clone, Default)]
+pub struct ColumnChooserState {
+    list: ListState,
+}
+
+impl ColumnChooserState {
+    pub fn selected(&self) -> Option<usize> {
+        self.list.selected()
+    }
+}
+
+impl ListStateMut for ColumnChooserState {
+    fn list_state_mut(&mut self) -> &mut ListState {
+        &mut self.list
+    }
+}
+
+/// Popup to show or hide columns of the Groups table.
+pub struct ColumnChooserWidget {
+    style: WidgetStyle,
+}
+
+impl ColumnChooserWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        ColumnChooserWidget { style }
+    }
+}
+
+impl CustomWidget for ColumnChooserWidget {
+    type Data = GroupColumns;
+    type State = ColumnChooserState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let style = &self.style.item;
+        let items = data
+            .entries()
+            .iter()
+            .map(|e| {
+                let mark = if e.visible { 'x' } else { ' ' };
+                let width = match e.width {
+                    ColumnWidth::Length(n) => n.to_string(),
+                    ColumnWidth::Percentage(p) => format!("{}%", p),
+                };
+                format!("[{}] {:<20} {:>4}", mark, e.column.title(), width)
+            })
+            .map(ListItem::new)
+            .map(|i| apply_item_style(i, style))
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(default_block_with_style(
+                &self.style.block,
+                "Columns (<Space>: toggle, <Esc>: close)",
+            ))
+            .highlight_style(style.highlight);
+
+        let area = centered_rect(50, 60, area);
+        Clear.render(area, buf);
+        render_stateful_widget(list, area, buf, &mut state.list);
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+use serde::Deserialize;
+use thiserror::Error;
+use tui::layout::Constraint;
+
+use crate::aws::cwlogs::group::{GroupSort, GroupSortColumn};
+use crate::collection::{Length, SortOrder};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize)]
+#[serde(try_from = "String")]
+pub enum GroupColumn {
+    Name,
+    Retention,
+    Created,
+    Stored,
+    Arn,
+    Account,
+    Region,
+    MetricFilters,
+    KmsKey,
+}
+
+impl GroupColumn {
+    pub const ALL: [GroupColumn; 9] = [
+        GroupColumn::Name,
+        GroupColumn::Retention,
+        GroupColumn::Created,
+        GroupColumn::Stored,
+        GroupColumn::Arn,
+        GroupColumn::Account,
+        GroupColumn::Region,
+        GroupColumn::MetricFilters,
+        GroupColumn::KmsKey,
+    ];
+
+    /// Returns the name of the column in the config file.
+    pub fn key(&self) -> &'static str {
+        use GroupColumn::*;
+        match *self {
+            Name => "name",
+            Retention => "retention",
+            Created => "created",
+            Stored => "stored",
+            Arn => "arn",
+            Account => "account",
+            Region => "region",
+            MetricFilters => "metric_filters",
+            KmsKey => "kms_key",
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        use GroupColumn::*;
+        match *self {
+            Name => "Name",
+            Retention => "Retention",
+            Created => "Created at",
+            Stored => "Stored Size",
+            Arn => "ARN",
+            Account => "Account",
+            Region => "Region",
+            MetricFilters => "Filters",
+            KmsKey => "KMS Key",
+        }
+    }
+
+    pub fn default_width(&self) -> ColumnWidth {
+        use GroupColumn::*;
+        match *self {
+            Name => ColumnWidth::Percentage(60),
+            Retention => ColumnWidth::Length(10),
+            Created => ColumnWidth::Length(20),
+            Stored => ColumnWidth::Length(12),
+            Arn => ColumnWidth::Percentage(40),
+            Account => ColumnWidth::Length(13),
+            Region => ColumnWidth::Length(15),
+            MetricFilters => ColumnWidth::Length(8),
+            KmsKey => ColumnWidth::Percentage(20),
+        }
+    }
+
+    /// Returns the sort column ordering groups by this column, if the column is sortable.
+    pub fn sort_column(&self) -> Option<GroupSortColumn> {
+        match *self {
+            GroupColumn::Name => Some(GroupSortColumn::Name),
+            GroupColumn::Created => Some(GroupSortColumn::CreationTime),
+            GroupColumn::Stored => Some(GroupSortColumn::Stored),
+            _ => None,
+        }
+    }
+
+    /// Returns the title with an arrow if groups are sorted by this column.
+    pub fn header(&self, sort: GroupSort) -> String {
+        if self.sort_column() != Some(sort.column) {
+            return self.title().to_string();
+        }
+
+        let arrow = match sort.order {
+            SortOrder::Asc => '▲',
+            SortOrder::Desc => '▼',
+        };
+        format!("{} {}", self.title(), arrow)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+#[error("unknown column: {0}")]
+pub struct ParseGroupColumnError(String);
+
+impl FromStr for GroupColumn {
+    type Err = ParseGroupColumnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GroupColumn::ALL
+            .iter()
+            .find(|c| c.key() == s)
+            .copied()
+            .ok_or_else(|| ParseGroupColumnError(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for GroupColumn {
+    type Error = ParseGroupColumnError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        GroupColumn::from_str(&s)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum ParseColumnWidthError {
+    #[error("invalid width: {0}")]
+    InvalidNumber(#[from] ParseIntError),
+    #[error("percentage must be 100 or less: {0}")]
+    TooLarge(u16),
+}
+
+/// Width of a column, either in cells (`20`) or in percentage of the table (`"60%"`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "WidthValue")]
+pub enum ColumnWidth {
+    Length(u16),
+    Percentage(u16),
+}
+
+impl FromStr for ColumnWidth {
+    type Err = ParseColumnWidthError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.strip_suffix('%') {
+            Some(p) => {
+                let p = p.trim().parse::<u16>()?;
+                if p > 100 {
+                    Err(ParseColumnWidthError::TooLarge(p))
+                } else {
+                    Ok(ColumnWidth::Percentage(p))
+                }
+            }
+            None => Ok(ColumnWidth::Length(s.parse()?)),
+        }
+    }
+}
+
+impl From<ColumnWidth> for Constraint {
+    fn from(width: ColumnWidth) -> Self {
+        match width {
+            ColumnWidth::Length(n) => Constraint::Length(n),
+            ColumnWidth::Percentage(p) => Constraint::Percentage(p),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WidthValue {
+    Number(u16),
+    Text(String),
+}
+
+impl TryFrom<WidthValue> for ColumnWidth {
+    type Error = ParseColumnWidthError;
+
+    fn try_from(value: WidthValue) -> Result<Self, Self::Error> {
+        match value {
+            WidthValue::Number(n) => Ok(ColumnWidth::Length(n)),
+            WidthValue::Text(s) => ColumnWidth::from_str(&s),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct ColumnEntry {
+    pub column: GroupColumn,
+    pub width: ColumnWidth,
+    pub visible: bool,
+}
+
+/// Every column of the Groups table, with the visible ones first in the configured order.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct GroupColumns {
+    entries: Vec<ColumnEntry>,
+}
+
+impl GroupColumns {
+    pub fn new(visible: &[GroupColumn], widths: &HashMap<GroupColumn, ColumnWidth>) -> Self {
+        let entry = |column: GroupColumn, visible: bool| ColumnEntry {
+            column,
+            width: widths
+                .get(&column)
+                .copied()
+                .unwrap_or_else(|| column.default_width()),
+            visible,
+        };
+
+        let mut entries = Vec::new();
+        for &column in visible {
+            if !entries.iter().any(|e: &ColumnEntry| e.column == column) {
+                entries.push(entry(column, true));
+            }
+        }
+        for &column in GroupColumn::ALL.iter() {
+            if !visible.contains(&column) {
+                entries.push(entry(column, false));
+            }
+        }
+
+        GroupColumns { entries }
+    }
+
+    pub fn entries(&self) -> &[ColumnEntry] {
+        &self.entries
+    }
+
+    pub fn visible(&self) -> impl Iterator<Item = &ColumnEntry> {
+        self.entries.iter().filter(|e| e.visible)
+    }
+
+    /// Shows or hides the column at the index. The last visible column cannot be hidden.
+    pub fn toggle(&mut self, i: usize) {
+        let visible = self.visible().count();
+        if let Some(entry) = self.entries.get_mut(i) {
+            if !entry.visible || visible > 1 {
+                entry.visible = !entry.visible;
+            }
+        }
+    }
+}
+
+impl Default for GroupColumns {
+    fn default() -> Self {
+        use GroupColumn::*;
+        GroupColumns::new(&[Name, Created, Stored], &HashMap::new())
+    }
+}
+
+impl Length for GroupColumns {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        for column in GroupColumn::ALL.iter() {
+            assert_eq!(Ok(*column), GroupColumn::from_str(column.key()));
+        }
+        assert_eq!(
+            Err(ParseGroupColumnError("size".to_string())),
+            GroupColumn::from_str("size")
+        );
+    }
+
+    #[test]
+    fn test_column_width() {
+        assert_eq!(Ok(ColumnWidth::Length(20)), ColumnWidth::from_str("20"));
+        assert_eq!(
+            Ok(ColumnWidth::Percentage(60)),
+            ColumnWidth::from_str("60%")
+        );
+        assert_eq!(
+            Err(ParseColumnWidthError::TooLarge(120)),
+            ColumnWidth::from_str("120%")
+        );
+        assert!(ColumnWidth::from_str("wide").is_err());
+    }
+
+    #[test]
+    fn test_columns() {
+        use GroupColumn::*;
+
+        let mut widths = HashMap::new();
+        widths.insert(Arn, ColumnWidth::Length(80));
+        let mut columns = GroupColumns::new(&[Arn, Name, Arn], &widths);
+
+        let visible = columns.visible().map(|e| e.column).collect::<Vec<_>>();
+        assert_eq!(vec![Arn, Name], visible);
+        assert_eq!(GroupColumn::ALL.len(), columns.len());
+        assert_eq!(ColumnWidth::Length(80), columns.entries()[0].width);
+        assert_eq!(Name.default_width(), columns.entries()[1].width);
+
+        columns.toggle(0);
+        columns.toggle(1);
+        let visible = columns.visible().map(|e| e.column).collect::<Vec<_>>();
+        assert_eq!(vec![Name], visible);
+
+        columns.toggle(2);
+        let visible = columns.visible().map(|e| e.column).collect::<Vec<_>>();
+        assert_eq!(vec![Name, Retention], visible);
+    }
+}
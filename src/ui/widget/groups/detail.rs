@@ -0,0 +1,199 @@
+use std::cmp::min;
+
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::Style;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Clear, Paragraph, Widget};
+
+use crate::aws::cwlogs::group::{CwlGroup, CwlGroupDetail};
+use crate::aws::Arn;
+use crate::time::TimeDisplay;
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::groups::column::GroupColumn;
+use crate::ui::widget::groups::CwlGroupFormatter;
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::{render_widget, CustomWidget};
+
+#[derive(Debug, Clone)]
+pub enum GroupDetailStatus {
+    Loading,
+    Loaded(CwlGroupDetail),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupDetailData {
+    group: CwlGroup,
+    status: GroupDetailStatus,
+}
+
+impl GroupDetailData {
+    pub fn new(group: CwlGroup) -> Self {
+        GroupDetailData {
+            group,
+            status: GroupDetailStatus::Loading,
+        }
+    }
+
+    pub fn group(&self) -> &CwlGroup {
+        &self.group
+    }
+
+    pub fn status(&self) -> &GroupDetailStatus {
+        &self.status
+    }
+
+    /// Ignores the detail if it is not for the shown group.
+    pub fn set_detail(&mut self, arn: &Arn, detail: CwlGroupDetail) {
+        if &self.group.arn == arn {
+            self.status = GroupDetailStatus::Loaded(detail);
+        }
+    }
+
+    pub fn set_error(&mut self, arn: &Arn, msg: String) {
+        if &self.group.arn == arn {
+            self.status = GroupDetailStatus::Failed(msg);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GroupDetailState {
+    scroll: u16,
+}
+
+impl GroupDetailState {
+    pub fn scroll_down(&mut self) {
+        self.scroll = self.scroll.saturating_add(1);
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+}
+
+/// Popup showing settings, tags and filters of a log group.
+pub struct GroupDetailWidget {
+    style: WidgetStyle,
+    error_style: Style,
+    time_display: TimeDisplay,
+}
+
+impl GroupDetailWidget {
+    pub fn with_style(style: WidgetStyle, error_style: Style) -> Self {
+        GroupDetailWidget {
+            style,
+            error_style,
+            time_display: TimeDisplay::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        GroupDetailWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    fn lines_from<'a>(&self, data: &'a GroupDetailData) -> Vec<Spans<'a>> {
+        let formatter = CwlGroupFormatter::new(&data.group, self.time_display);
+        let field = |name: &'static str, value: String| {
+            Spans::from(vec![
+                Span::styled(format!("{:<12}", name), self.style.table.header),
+                Span::raw(value),
+            ])
+        };
+
+        let mut lines = vec![
+            field("Name", data.group.group_name.clone()),
+            field("ARN", data.group.arn.to_string()),
+            field("Created", formatter.format(GroupColumn::Created)),
+            field("Retention", formatter.format(GroupColumn::Retention)),
+            field("KMS key", formatter.format(GroupColumn::KmsKey)),
+            field("Stored", formatter.format(GroupColumn::Stored)),
+            Spans::default(),
+        ];
+
+        let detail = match &data.status {
+            GroupDetailStatus::Loading => {
+                lines.push(Spans::from("loading..."));
+                return lines;
+            }
+            GroupDetailStatus::Failed(msg) => {
+                lines.push(Spans::from(Span::styled(msg.as_str(), self.error_style)));
+                return lines;
+            }
+            GroupDetailStatus::Loaded(detail) => detail,
+        };
+
+        lines.push(self.section("Tags", detail.tags.len()));
+        lines.extend(
+            detail
+                .tags
+                .iter()
+                .map(|(k, v)| Spans::from(format!("  {} = {}", k, v))),
+        );
+
+        lines.push(Spans::default());
+        lines.push(self.section("Metric filters", detail.metric_filters.len()));
+        for filter in detail.metric_filters.iter() {
+            lines.push(Spans::from(format!("  {}", filter.name)));
+            lines.push(Spans::from(format!("    pattern: {}", filter.pattern)));
+            for t in filter.transformations.iter() {
+                lines.push(Spans::from(format!(
+                    "    metric:  {}/{} = {}",
+                    t.namespace, t.name, t.value
+                )));
+            }
+        }
+
+        lines.push(Spans::default());
+        lines.push(self.section("Subscription filters", detail.subscription_filters.len()));
+        for filter in detail.subscription_filters.iter() {
+            lines.push(Spans::from(format!("  {}", filter.name)));
+            lines.push(Spans::from(format!("    pattern:     {}", filter.pattern)));
+            lines.push(Spans::from(format!(
+                "    destination: {}",
+                filter.destination_arn
+            )));
+        }
+
+        lines
+    }
+
+    fn section(&self, name: &'static str, count: usize) -> Spans<'static> {
+        Spans::from(Span::styled(
+            format!("{} ({})", name, count),
+            self.style.block.title_style,
+        ))
+    }
+}
+
+impl CustomWidget for GroupDetailWidget {
+    type Data = GroupDetailData;
+    type State = GroupDetailState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let lines = self.lines_from(data);
+        state.scroll = min(state.scroll, lines.len().saturating_sub(1) as u16);
+
+        let paragraph = Paragraph::new(Text::from(lines))
+            .block(default_block_with_style(
+                &self.style.block,
+                "Detail (j/k: scroll, <Esc>: close)",
+            ))
+            .style(self.style.table.normal)
+            .scroll((state.scroll, 0));
+
+        let area = centered_rect(80, 80, area);
+        Clear.render(area, buf);
+        render_widget(paragraph, area, buf);
+    }
+}
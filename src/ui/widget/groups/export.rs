@@ -0,0 +1,74 @@
+use crate::aws::cwlogs::group::CwlGroup;
+use crate::time::TimeDisplay;
+use crate::ui::widget::groups::column::GroupColumns;
+use crate::ui::widget::groups::CwlGroupFormatter;
+
+/// Formats the groups as CSV, one row per group with the visible columns.
+pub fn to_csv(groups: &[&CwlGroup], columns: &GroupColumns) -> String {
+    let mut csv = String::new();
+    let header = columns.visible().map(|e| e.column.title().to_string());
+    push_row(&mut csv, header);
+
+    for group in groups {
+        let formatter = CwlGroupFormatter::new(group, TimeDisplay::default());
+        let row = columns
+            .visible()
+            .map(|e| formatter.format(e.column).trim().to_string());
+        push_row(&mut csv, row);
+    }
+
+    csv
+}
+
+fn push_row(csv: &mut String, cells: impl Iterator<Item = String>) {
+    let cells = cells.map(|c| escape(&c)).collect::<Vec<_>>();
+    csv.push_str(&cells.join(","));
+    csv.push('\n');
+}
+
+fn escape(cell: &str) -> String {
+    if cell.contains([',', '"', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::Duration;
+
+    use chrono::prelude::*;
+
+    use super::*;
+    use crate::aws::Arn;
+    use crate::size::Size;
+    use crate::ui::widget::groups::column::GroupColumn;
+
+    #[test]
+    fn test_to_csv() {
+        let group = CwlGroup {
+            arn: Arn::from("arn:aws:logs:ap-northeast-1:000000000000:log-group:a,\"b\":*"),
+            creation_time: Utc.ymd(2020, 11, 2).and_hms(11, 22, 33),
+            group_name: "a,\"b\"".to_string(),
+            retention: Some(Duration::from_secs(86_400 * 7)),
+            stored: Size::new(0),
+            metric_filter_count: 3,
+            kms_key_id: None,
+        };
+        let columns = GroupColumns::new(
+            &[
+                GroupColumn::Name,
+                GroupColumn::Retention,
+                GroupColumn::MetricFilters,
+            ],
+            &HashMap::new(),
+        );
+
+        assert_eq!(
+            "Name,Retention,Filters\n\"a,\"\"b\"\"\",7 days,3\n",
+            to_csv(&[&group], &columns)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Clear, List, ListItem, ListState, Paragraph, Widget};
+
+use crate::aws::cwlogs::group::{CwlGroup, CwlRetention};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::{apply_item_style, centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, render_widget, CustomWidget};
+
+#[derive(Debug, Clone)]
+pub struct RetentionEditorState {
+    groups: Vec<CwlGroup>,
+    choices: Vec<CwlRetention>,
+    list: ListState,
+    /// The chosen retention waiting for confirmation.
+    confirm: Option<CwlRetention>,
+}
+
+impl RetentionEditorState {
+    pub fn new(group: CwlGroup) -> Self {
+        RetentionEditorState::for_groups(vec![group])
+    }
+
+    pub fn for_groups(groups: Vec<CwlGroup>) -> Self {
+        let mut state = RetentionEditorState {
+            groups,
+            choices: CwlRetention::all(),
+            list: ListState::default(),
+            confirm: None,
+        };
+        let current = state.current();
+        let selected = state.choices.iter().position(|&r| Some(r) == current);
+        state.list.select(selected.or(Some(0)));
+        state
+    }
+
+    pub fn groups(&self) -> &[CwlGroup] {
+        &self.groups
+    }
+
+    pub fn select_next(&mut self) {
+        select_next(&mut self.list, self.choices.len());
+    }
+
+    pub fn select_previous(&mut self) {
+        select_previous(&mut self.list, self.choices.len());
+    }
+
+    /// The retention shared by all of the groups.
+    pub fn current(&self) -> Option<CwlRetention> {
+        let mut retentions = self.groups.iter().map(|g| CwlRetention::from(g.retention));
+        let first = retentions.next()?;
+        retentions.all(|r| r == first).then_some(first)
+    }
+
+    pub fn confirming(&self) -> Option<CwlRetention> {
+        self.confirm
+    }
+
+    /// Asks for confirmation of the selected retention, unless it is the current one.
+    pub fn choose(&mut self) {
+        let selected = self.list.selected().and_then(|i| self.choices.get(i));
+        self.confirm = selected.copied().filter(|&r| Some(r) != self.current());
+    }
+
+    pub fn cancel(&mut self) {
+        self.confirm = None;
+    }
+}
+
+/// Popup to change the retention of a log group.
+pub struct RetentionEditorWidget {
+    style: WidgetStyle,
+}
+
+impl RetentionEditorWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        RetentionEditorWidget { style }
+    }
+
+    fn preview<'a>(&self, state: &'a RetentionEditorState, to: CwlRetention) -> Paragraph<'a> {
+        let field = |name: &'static str, value: String| {
+            Spans::from(vec![
+                Span::styled(format!("{:<8}", name), self.style.table.header),
+                Span::raw(value),
+            ])
+        };
+
+        let group = match state.groups.as_slice() {
+            [group] => group.group_name.clone(),
+            groups => format!("{} groups", groups.len()),
+        };
+        let from = state
+            .current()
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| "(mixed)".to_string());
+        let mut lines = vec![
+            field("Group", group),
+            field("From", from),
+            field("To", to.to_string()),
+            Spans::default(),
+        ];
+        if let CwlRetention::Days(days) = to {
+            lines.push(Spans::from(format!(
+                "Events older than {} days will be deleted.",
+                days
+            )));
+        }
+        lines.push(Spans::from("Apply? (y/n)"));
+
+        Paragraph::new(Text::from(lines)).block(default_block_with_style(
+            &self.style.block,
+            "Change retention",
+        ))
+    }
+}
+
+impl CustomWidget for RetentionEditorWidget {
+    type Data = ();
+    type State = RetentionEditorState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        if let Some(to) = state.confirm {
+            let area = centered_rect(60, 30, area);
+            Clear.render(area, buf);
+            render_widget(self.preview(state, to), area, buf);
+            return;
+        }
+
+        let style = &self.style.item;
+        let current = state.current();
+        let items = state
+            .choices
+            .iter()
+            .map(|&r| {
+                let mark = if Some(r) == current { '*' } else { ' ' };
+                format!("{} {}", mark, r)
+            })
+            .map(ListItem::new)
+            .map(|i| apply_item_style(i, style))
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(default_block_with_style(
+                &self.style.block,
+                "Retention (<Enter>: choose, <Esc>: close)",
+            ))
+            .highlight_style(style.highlight);
+
+        let area = centered_rect(40, 70, area);
+        Clear.render(area, buf);
+        render_stateful_widget(list, area, buf, &mut state.list);
+    }
+}
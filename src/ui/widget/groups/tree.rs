@@ -0,0 +1,183 @@
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::widgets::{Cell, Row, Table, TableState};
+
+use crate::aws::cwlogs::group::{CwlGroup, CwlGroupStore, CwlGroupTree};
+use crate::size::HumanReadableSize;
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::groups::GroupsStates;
+use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, CustomWidget};
+
+/// Groups shown in the Groups widget.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GroupsView {
+    Table,
+    Tree,
+}
+
+impl Default for GroupsView {
+    fn default() -> Self {
+        GroupsView::Table
+    }
+}
+
+fn tree_of(data: &CwlGroupStore) -> CwlGroupTree<'_> {
+    CwlGroupTree::new(data.matched().into_iter().map(|m| m.item))
+}
+
+/// Selection and expanded name prefixes of the groups tree.
+#[derive(Debug, Clone, Default)]
+pub struct GroupTreeState {
+    table: TableState,
+    expanded: BTreeSet<String>,
+}
+
+impl GroupTreeState {
+    pub fn select_next(&mut self, data: &CwlGroupStore) {
+        select_next(&mut self.table, tree_of(data).rows(&self.expanded).len());
+    }
+
+    pub fn select_previous(&mut self, data: &CwlGroupStore) {
+        select_previous(&mut self.table, tree_of(data).rows(&self.expanded).len());
+    }
+
+    /// Name prefix of the selected node.
+    pub fn selected_path(&self, data: &CwlGroupStore) -> Option<String> {
+        let i = self.table.selected()?;
+        let tree = tree_of(data);
+        let rows = tree.rows(&self.expanded);
+        rows.get(i).map(|r| r.node.path.clone())
+    }
+
+    /// The group named exactly the selected node, if any.
+    pub fn selected_group<'a>(&self, data: &'a CwlGroupStore) -> Option<&'a CwlGroup> {
+        let i = self.table.selected()?;
+        let tree = tree_of(data);
+        let rows = tree.rows(&self.expanded);
+        rows.get(i).and_then(|r| r.node.group)
+    }
+
+    pub fn expand(&mut self, data: &CwlGroupStore) {
+        if let Some(path) = self.selected_path(data) {
+            self.expanded.insert(path);
+        }
+    }
+
+    /// Collapses the selected node, or moves to its parent if it is already collapsed.
+    pub fn collapse(&mut self, data: &CwlGroupStore) {
+        let i = match self.table.selected() {
+            Some(i) => i,
+            None => return,
+        };
+        let tree = tree_of(data);
+        let rows = tree.rows(&self.expanded);
+        let row = match rows.get(i) {
+            Some(row) => row,
+            None => return,
+        };
+
+        if row.expanded {
+            self.expanded.remove(&row.node.path);
+        } else if let Some(parent) = rows[..i].iter().rposition(|r| r.depth < row.depth) {
+            self.table.select(Some(parent));
+        }
+    }
+
+    /// Expands the selected node, or collapses it if it is expanded.
+    pub fn toggle(&mut self, data: &CwlGroupStore) {
+        if let Some(path) = self.selected_path(data) {
+            if !self.expanded.remove(&path) {
+                self.expanded.insert(path);
+            }
+        }
+    }
+}
+
+/// Groups split into name prefixes, with the group count and stored bytes of each prefix.
+pub struct GroupTreeWidget {
+    style: WidgetStyle,
+}
+
+impl GroupTreeWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        GroupTreeWidget { style }
+    }
+}
+
+impl CustomWidget for GroupTreeWidget {
+    type Data = CwlGroupStore;
+    type State = GroupsStates;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let tree = tree_of(data);
+        let rows = tree.rows(&state.tree.expanded);
+        if let Some(i) = state.tree.table.selected() {
+            if i >= rows.len() {
+                state.tree.table.select(rows.len().checked_sub(1));
+            }
+        }
+
+        let header = Row::new(vec![" ", "Prefix", "Groups", "Stored"])
+            .style(self.style.table.header)
+            .bottom_margin(1);
+        let rows = rows
+            .iter()
+            .map(|r| {
+                let mark = match r.node.group {
+                    Some(group) if state.is_marked(group) => "*",
+                    _ => " ",
+                };
+                let folder = match (r.node.has_children(), r.expanded) {
+                    (false, _) => "  ",
+                    (true, false) => "+ ",
+                    (true, true) => "- ",
+                };
+                let stored = HumanReadableSize::try_from(r.node.stored)
+                    .map(|s| format!("{:>4} {}", s.size, s.unit.short_name()))
+                    .unwrap_or_default();
+                Row::new(vec![
+                    Cell::from(mark),
+                    Cell::from(format!(
+                        "{}{}{}",
+                        "  ".repeat(r.depth),
+                        folder,
+                        r.node.segment
+                    )),
+                    Cell::from(format!("{:>6}", r.node.group_count)),
+                    Cell::from(stored),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let title = match state.marked_groups(data).len() {
+            0 => format!("{} (tree)", data.label()),
+            n => format!("{} (tree) [{} marked]", data.label(), n),
+        };
+        let widths = [
+            Constraint::Length(1),
+            Constraint::Percentage(70),
+            Constraint::Length(6),
+            Constraint::Length(7),
+        ];
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&widths)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
+
+        render_stateful_widget(table, area, buf, &mut state.tree.table);
+    }
+}
@@ -0,0 +1,172 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::text::{Span, Spans};
+use tui::widgets::{Clear, Paragraph, Widget};
+
+use crate::app::keymap::HelpSection;
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::{centered_rect, default_block_with_style};
+use crate::ui::widget::{render_widget, CustomWidget};
+
+/// Keys of the view it was opened on, narrowed down by a query.
+#[derive(Debug, Clone)]
+pub struct HelpState {
+    context: String,
+    sections: Vec<HelpSection>,
+    query: String,
+    searching: bool,
+    offset: usize,
+}
+
+impl HelpState {
+    pub fn new(context: impl Into<String>, sections: Vec<HelpSection>) -> Self {
+        HelpState {
+            context: context.into(),
+            sections,
+            query: String::new(),
+            searching: false,
+            offset: 0,
+        }
+    }
+
+    /// Sections with the entries matching the query.
+    pub fn sections(&self) -> Vec<HelpSection> {
+        self.sections
+            .iter()
+            .filter_map(|s| s.filter(&self.query))
+            .collect()
+    }
+
+    /// Lines of the sections, each with its title and a blank line after it.
+    fn line_count(&self) -> usize {
+        self.sections().iter().map(|s| s.entries.len() + 2).sum()
+    }
+
+    pub fn is_filtered(&self) -> bool {
+        !self.query.is_empty()
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.searching
+    }
+
+    pub fn start_search(&mut self) {
+        self.searching = true;
+    }
+
+    pub fn stop_search(&mut self) {
+        self.searching = false;
+    }
+
+    pub fn clear_search(&mut self) {
+        self.searching = false;
+        self.query.clear();
+        self.offset = 0;
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.offset = 0;
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.offset = 0;
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.offset = self.offset.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.offset = (self.offset + 1).min(self.line_count().saturating_sub(1));
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.offset = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.offset = self.line_count().saturating_sub(1);
+    }
+}
+
+pub struct HelpWidget {
+    style: WidgetStyle,
+}
+
+impl HelpWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        HelpWidget { style }
+    }
+
+    fn lines_from(&self, sections: &[HelpSection]) -> Vec<Spans<'static>> {
+        let width = sections
+            .iter()
+            .flat_map(|s| s.entries.iter())
+            .map(|e| e.keys.chars().count())
+            .max()
+            .unwrap_or_default();
+
+        let mut lines = Vec::new();
+        for section in sections {
+            lines.push(Spans::from(Span::styled(
+                section.title.clone(),
+                self.style.table.header,
+            )));
+            lines.extend(section.entries.iter().map(|entry| {
+                Spans::from(vec![
+                    Span::styled(
+                        format!("  {:<width$}  ", entry.keys, width = width),
+                        self.style.item.matched,
+                    ),
+                    Span::raw(entry.description.clone()),
+                ])
+            }));
+            lines.push(Spans::default());
+        }
+        lines
+    }
+}
+
+impl CustomWidget for HelpWidget {
+    type Data = ();
+    type State = HelpState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let area = centered_rect(70, 80, area);
+        Clear.render(area, buf);
+
+        let title = match (state.is_searching(), state.is_filtered()) {
+            (true, _) => format!(
+                "Help: {} /{}_ (<Enter>: done, <Esc>: clear)",
+                state.context, state.query
+            ),
+            (false, false) => format!(
+                "Help: {} (</>: search, <j>/<k>: scroll, <Esc>: close)",
+                state.context
+            ),
+            (false, true) => format!(
+                "Help: {} \"{}\" (</>: search, <Esc>: clear)",
+                state.context, state.query
+            ),
+        };
+        let sections = state.sections();
+        let mut lines = self.lines_from(&sections);
+        if lines.is_empty() {
+            lines.push(Spans::from("no key matches the query"));
+        }
+
+        let paragraph = Paragraph::new(lines)
+            .block(default_block_with_style(&self.style.block, &title))
+            .style(self.style.table.normal)
+            .scroll((state.offset as u16, 0));
+        render_widget(paragraph, area, buf);
+    }
+}
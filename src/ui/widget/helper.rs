@@ -1,4 +1,6 @@
-use tui::text::Span;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::Style;
+use tui::text::{Span, Spans};
 use tui::widgets::{Block, ListItem};
 
 use crate::ui::theme::{BlockStyle, ItemStyle};
@@ -18,3 +20,63 @@ pub fn default_block_with_style<'a>(styles: &BlockStyle, title: &'a str) -> Bloc
 pub fn apply_item_style<'a>(item: ListItem<'a>, styles: &ItemStyle) -> ListItem<'a> {
     item.style(styles.normal)
 }
+
+/// Splits the text into spans, applying `matched` style to the chars at the given indices.
+pub fn highlight_matches<'a>(
+    text: &'a str,
+    indices: &[usize],
+    normal: Style,
+    matched: Style,
+) -> Spans<'a> {
+    if indices.is_empty() {
+        return Spans::from(Span::styled(text, normal));
+    }
+
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut is_matched = false;
+    let mut indices = indices.iter().peekable();
+    for (i, (pos, _)) in text.char_indices().enumerate() {
+        let matches = indices.peek().map(|&&m| m == i).unwrap_or(false);
+        if matches {
+            indices.next();
+        }
+
+        if matches != is_matched {
+            if pos > start {
+                let style = if is_matched { matched } else { normal };
+                spans.push(Span::styled(&text[start..pos], style));
+            }
+            start = pos;
+            is_matched = matches;
+        }
+    }
+
+    if start < text.len() {
+        let style = if is_matched { matched } else { normal };
+        spans.push(Span::styled(&text[start..], style));
+    }
+
+    Spans::from(spans)
+}
+
+/// Returns a rect of the given percentage of the area, placed at the center of it.
+pub fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
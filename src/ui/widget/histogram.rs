@@ -0,0 +1,242 @@
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::Style;
+use tui::widgets::{Paragraph, Sparkline};
+
+use crate::aws::cwlogs::event::{CwlEvent, CwlEventStore, Histogram, LogLevel};
+use crate::time::{HumanDuration, TimeDisplay, TimeRange};
+use crate::ui::theme::{LevelStyle, WidgetStyle};
+use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::{render_widget, CustomWidget};
+
+/// How the counts of a bucket are split into series, each drawn on its own row.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HistogramSplit {
+    Total,
+    /// Errors and above, warnings, and the rest.
+    Level,
+    /// Events shown and hidden by the minimum level.
+    Filter,
+}
+
+impl Default for HistogramSplit {
+    fn default() -> Self {
+        HistogramSplit::Total
+    }
+}
+
+impl HistogramSplit {
+    pub fn next(self) -> Self {
+        match self {
+            HistogramSplit::Total => HistogramSplit::Level,
+            HistogramSplit::Level => HistogramSplit::Filter,
+            HistogramSplit::Filter => HistogramSplit::Total,
+        }
+    }
+
+    fn series_count(self) -> usize {
+        match self {
+            HistogramSplit::Total => 1,
+            HistogramSplit::Level => 3,
+            HistogramSplit::Filter => 2,
+        }
+    }
+
+    fn series_of(self, event: &CwlEvent, min_level: Option<LogLevel>) -> usize {
+        match self {
+            HistogramSplit::Total => 0,
+            HistogramSplit::Level if event.level >= Some(LogLevel::Error) => 0,
+            HistogramSplit::Level if event.level == Some(LogLevel::Warn) => 1,
+            HistogramSplit::Level => 2,
+            HistogramSplit::Filter if event.level >= min_level => 0,
+            HistogramSplit::Filter => 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HistogramState {
+    split: HistogramSplit,
+    selected: Option<usize>,
+    /// Buckets of the last rendered chart, one per column.
+    buckets: usize,
+}
+
+impl HistogramState {
+    pub fn split(&self) -> HistogramSplit {
+        self.split
+    }
+
+    pub fn cycle_split(&mut self) {
+        self.split = self.split.next();
+    }
+
+    pub fn select_next(&mut self) {
+        let last = self.buckets.saturating_sub(1);
+        self.selected = Some(self.selected.map(|i| (i + 1).min(last)).unwrap_or(0));
+    }
+
+    pub fn select_previous(&mut self) {
+        let last = self.buckets.saturating_sub(1);
+        self.selected = Some(self.selected.map(|i| i.saturating_sub(1)).unwrap_or(last));
+    }
+
+    /// The time range of the selected bucket, of the chart drawn for the range.
+    pub fn selected_range(&self, range: TimeRange) -> Option<TimeRange> {
+        let histogram = Histogram::new(range, self.buckets, 0, std::iter::empty(), |_| None);
+        self.selected
+            .filter(|i| *i < self.buckets)
+            .map(|i| histogram.bucket_range(i))
+    }
+
+    pub fn reset(&mut self) {
+        self.selected = None;
+    }
+}
+
+pub struct HistogramWidget {
+    style: WidgetStyle,
+    level_style: LevelStyle,
+    time_display: TimeDisplay,
+    range: Option<TimeRange>,
+    min_level: Option<LogLevel>,
+}
+
+impl HistogramWidget {
+    pub fn with_style(style: WidgetStyle, level_style: LevelStyle) -> Self {
+        HistogramWidget {
+            style,
+            level_style,
+            time_display: TimeDisplay::default(),
+            range: None,
+            min_level: None,
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        HistogramWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    pub fn range(self, range: TimeRange) -> Self {
+        HistogramWidget {
+            range: Some(range),
+            ..self
+        }
+    }
+
+    pub fn min_level(self, min_level: Option<LogLevel>) -> Self {
+        HistogramWidget { min_level, ..self }
+    }
+
+    fn series_style(&self, split: HistogramSplit, series: usize) -> Style {
+        match (split, series) {
+            (HistogramSplit::Level, 0) => self.level_style.error,
+            (HistogramSplit::Level, 1) => self.level_style.warn,
+            (HistogramSplit::Filter, 1) => self.level_style.trace,
+            _ => self.style.table.highlight,
+        }
+    }
+
+    fn title(&self, histogram: &Histogram, state: &HistogramState) -> String {
+        let width = match histogram.bucket_width() {
+            w if w.num_seconds() == 0 => format!("{}ms", w.num_milliseconds()),
+            w => w
+                .to_std()
+                .map(|d| HumanDuration::from(d).to_string())
+                .unwrap_or_default(),
+        };
+        let split = match state.split {
+            HistogramSplit::Total => "",
+            HistogramSplit::Level => " by level",
+            HistogramSplit::Filter => " shown / hidden",
+        };
+        let selected = state
+            .selected
+            .filter(|i| *i < histogram.buckets())
+            .map(|i| {
+                let range = histogram.bucket_range(i);
+                format!(
+                    ", {}: {} events",
+                    self.time_display.format(&range.start),
+                    histogram.total(i)
+                )
+            });
+        format!(
+            "Events per {}{}{} (<[>/<]>: select, <z>: zoom, <H>: split)",
+            width,
+            split,
+            selected.unwrap_or_default()
+        )
+    }
+}
+
+impl CustomWidget for HistogramWidget {
+    type Data = CwlEventStore;
+    type State = HistogramState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let range = match self.range {
+            Some(range) => range,
+            None => return,
+        };
+
+        let block = default_block_with_style(&self.style.block, "");
+        let inner = block.inner(area);
+        state.buckets = inner.width as usize;
+        let (split, min_level) = (state.split, self.min_level);
+        let histogram = Histogram::new(
+            range,
+            state.buckets,
+            split.series_count(),
+            data.order_by_asc(),
+            |event| Some(split.series_of(event, min_level)),
+        );
+
+        let title = self.title(&histogram, state);
+        render_widget(
+            default_block_with_style(&self.style.block, &title),
+            area,
+            buf,
+        );
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+        let count = histogram.series_count() as u32;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(vec![Constraint::Ratio(1, count); count as usize])
+            .split(chunks[0]);
+        let max = (0..histogram.series_count())
+            .flat_map(|s| histogram.series(s).iter().copied())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (i, row) in rows.into_iter().enumerate() {
+            let sparkline = Sparkline::default()
+                .data(histogram.series(i))
+                .max(max)
+                .style(self.series_style(split, i));
+            render_widget(sparkline, row, buf);
+        }
+
+        if let Some(i) = state.selected.filter(|i| *i < histogram.buckets()) {
+            let marker = format!("{}▲", " ".repeat(i));
+            render_widget(
+                Paragraph::new(marker).style(self.style.table.highlight),
+                chunks[1],
+                buf,
+            );
+        }
+    }
+}
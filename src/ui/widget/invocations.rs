@@ -0,0 +1,221 @@
+use std::collections::BTreeSet;
+
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::widgets::{Cell, Paragraph, Row, Table, TableState};
+
+use crate::aws::cwlogs::event::{Invocation, InvocationStats, Percentiles};
+use crate::time::TimeDisplay;
+use crate::ui::theme::{LevelStyle, WidgetStyle};
+use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, render_widget, CustomWidget};
+
+static COLUMNS: [&str; 7] = [
+    "Start",
+    "RequestId",
+    "Duration",
+    "Billed",
+    "Max memory",
+    "Init",
+    "Error / lines",
+];
+static WIDTHS: [Constraint; 7] = [
+    Constraint::Length(23),
+    Constraint::Length(36),
+    Constraint::Length(11),
+    Constraint::Length(9),
+    Constraint::Length(10),
+    Constraint::Length(11),
+    Constraint::Percentage(100),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct InvocationsState {
+    table: TableState,
+    expanded: BTreeSet<String>,
+}
+
+impl InvocationsState {
+    /// Rows of invocations, followed by the indices of their lines if they are expanded.
+    fn rows<'a>(&self, invocations: &'a [Invocation]) -> Vec<(&'a Invocation, Option<usize>)> {
+        let mut rows = Vec::new();
+        for invocation in invocations.iter() {
+            rows.push((invocation, None));
+            if self.expanded.contains(&invocation.request_id) {
+                rows.extend((0..invocation.events.len()).map(|i| (invocation, Some(i))));
+            }
+        }
+        rows
+    }
+
+    pub fn select_next(&mut self, invocations: &[Invocation]) {
+        let len = self.rows(invocations).len();
+        select_next(&mut self.table, len);
+    }
+
+    pub fn select_previous(&mut self, invocations: &[Invocation]) {
+        let len = self.rows(invocations).len();
+        select_previous(&mut self.table, len);
+    }
+
+    /// Shows or hides the lines of the selected invocation, selecting the invocation itself.
+    pub fn toggle(&mut self, invocations: &[Invocation]) {
+        let selected = self
+            .table
+            .selected()
+            .and_then(|i| self.rows(invocations).get(i).map(|(inv, _)| *inv));
+        let invocation = match selected {
+            Some(invocation) => invocation,
+            None => return,
+        };
+        if !self.expanded.remove(&invocation.request_id) {
+            self.expanded.insert(invocation.request_id.clone());
+        }
+        let i = self
+            .rows(invocations)
+            .iter()
+            .position(|(inv, line)| inv.request_id == invocation.request_id && line.is_none());
+        self.table.select(i);
+    }
+
+    pub fn reset(&mut self) {
+        self.table.select(None);
+        self.expanded.clear();
+    }
+}
+
+pub struct InvocationsWidget {
+    style: WidgetStyle,
+    level_style: LevelStyle,
+    time_display: TimeDisplay,
+    title: String,
+    loading: bool,
+}
+
+impl InvocationsWidget {
+    pub fn with_style(style: WidgetStyle, level_style: LevelStyle) -> Self {
+        InvocationsWidget {
+            style,
+            level_style,
+            time_display: TimeDisplay::default(),
+            title: "Invocations".to_string(),
+            loading: false,
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        InvocationsWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    pub fn title(self, title: String) -> Self {
+        InvocationsWidget { title, ..self }
+    }
+
+    pub fn loading(self, loading: bool) -> Self {
+        InvocationsWidget { loading, ..self }
+    }
+
+    fn stats_text(stats: &InvocationStats) -> String {
+        let format = |name: &str, p: Option<Percentiles>, unit: &str| match p {
+            Some(p) => format!(
+                "{} p50 {:.1}{} p95 {:.1}{} max {:.1}{}",
+                name, p.p50, unit, p.p95, unit, p.max, unit
+            ),
+            None => format!("{} -", name),
+        };
+        [
+            format!(
+                "{} invocations, {} errors, {} cold starts",
+                stats.count, stats.errors, stats.cold_starts
+            ),
+            format("duration", stats.duration_ms, "ms"),
+            format("billed", stats.billed_duration_ms, "ms"),
+            format("memory", stats.max_memory_used_mb, "MB"),
+            format("init", stats.init_duration_ms, "ms"),
+        ]
+        .join(" | ")
+    }
+
+    fn invocation_row(&self, invocation: &Invocation) -> Row<'static> {
+        let ms = |v: Option<f64>| v.map(|v| format!("{:.2} ms", v)).unwrap_or_default();
+        let error = if invocation.has_error { "error" } else { "" };
+        Row::new(vec![
+            Cell::from(self.time_display.format_millis(&invocation.start)),
+            Cell::from(invocation.request_id.clone()),
+            Cell::from(format!("{:>11}", ms(invocation.duration_ms))),
+            Cell::from(format!("{:>9}", ms(invocation.billed_duration_ms))),
+            Cell::from(
+                invocation
+                    .max_memory_used_mb
+                    .map(|m| format!("{:>7} MB", m))
+                    .unwrap_or_default(),
+            ),
+            Cell::from(format!("{:>11}", ms(invocation.init_duration_ms))),
+            Cell::from(format!("{:5} {} lines", error, invocation.events.len())),
+        ])
+        .style(if invocation.has_error {
+            self.level_style.error
+        } else {
+            self.level_style.info
+        })
+    }
+}
+
+impl CustomWidget for InvocationsWidget {
+    type Data = Vec<Invocation>;
+    type State = InvocationsState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(3), Constraint::Min(0)])
+            .split(area);
+
+        let stats = InvocationStats::new(data);
+        let paragraph = Paragraph::new(InvocationsWidget::stats_text(&stats))
+            .block(default_block_with_style(&self.style.block, "Stats"))
+            .style(self.style.table.normal);
+        render_widget(paragraph, chunks[0], buf);
+
+        let rows = state
+            .rows(data)
+            .into_iter()
+            .map(|(invocation, line)| match line {
+                None => self.invocation_row(invocation),
+                Some(i) => {
+                    let event = &invocation.events[i];
+                    let mut cells = vec![Cell::from(
+                        self.time_display.format_millis(&event.event_time),
+                    )];
+                    cells.extend((0..5).map(|_| Cell::from("")));
+                    cells.push(Cell::from(format!("  {}", event.message.trim_end())));
+                    Row::new(cells).style(self.level_style.style(event.level))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut title = format!("{} [{}]", self.title, data.len());
+        if self.loading {
+            title.push_str(" [loading]");
+        }
+        let table = Table::new(rows)
+            .header(Row::new(COLUMNS.to_vec()).style(self.style.table.header))
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&WIDTHS)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
+
+        render_stateful_widget(table, chunks[1], buf, &mut state.table);
+    }
+}
@@ -5,7 +5,7 @@ use tui::widgets::{List, ListItem, ListState};
 use crate::collection::AsStr;
 use crate::preset::{Preset, PresetStore};
 use crate::ui::theme::WidgetStyle;
-use crate::ui::widget::helper::{apply_item_style, default_block_with_style};
+use crate::ui::widget::helper::{apply_item_style, default_block_with_style, highlight_matches};
 use crate::ui::widget::stateful::list::ListStateMut;
 use crate::ui::widget::{render_stateful_widget, CustomWidget};
 
@@ -17,7 +17,7 @@ pub struct PresetsState {
 impl PresetsState {
     pub fn selected_preset<'a>(&self, data: &'a PresetStore) -> Option<&'a Preset> {
         if let Some(i) = self.list.selected() {
-            data.iter().skip(i).next()
+            data.get(i)
         } else {
             None
         }
@@ -40,11 +40,20 @@ impl PresetsWidget {
     }
 
     fn presets_from<'a>(&self, data: &'a PresetStore) -> List<'a> {
+        let style = &self.style.item;
         let items = data
-            .iter()
-            .map(|p| p.name.as_str())
+            .matched()
+            .into_iter()
+            .map(|m| {
+                highlight_matches(
+                    m.item.name.as_str(),
+                    m.indices(),
+                    style.normal,
+                    style.matched,
+                )
+            })
             .map(ListItem::new)
-            .map(|i| apply_item_style(i, &self.style.item))
+            .map(|i| apply_item_style(i, style))
             .collect::<Vec<_>>();
 
         List::new(items)
@@ -5,7 +5,7 @@ use tui::widgets::{List, ListItem, ListState};
 use crate::aws::profile::{ProfileName, ProfileStore};
 use crate::collection::AsStr;
 use crate::ui::theme::WidgetStyle;
-use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::helper::{default_block_with_style, highlight_matches};
 use crate::ui::widget::stateful::list::ListStateMut;
 use crate::ui::widget::{render_stateful_widget, CustomWidget};
 
@@ -17,7 +17,7 @@ pub struct ProfilesState {
 impl ProfilesState {
     pub fn selected_profile<'a>(&self, data: &'a ProfileStore) -> Option<&'a ProfileName> {
         if let Some(i) = self.list.selected() {
-            data.iter().skip(i).next()
+            data.get(i)
         } else {
             None
         }
@@ -40,10 +40,12 @@ impl ProfilesWidget {
     }
 
     fn profiles_from<'a>(&self, data: &'a ProfileStore) -> List<'a> {
+        let style = &self.style.item;
         let items = data
-            .iter()
-            .map(|p| p.as_str())
-            .map(|s| ListItem::new(s).style(self.style.item.normal))
+            .matched()
+            .into_iter()
+            .map(|m| highlight_matches(m.item.as_str(), m.indices(), style.normal, style.matched))
+            .map(|s| ListItem::new(s).style(style.normal))
             .collect::<Vec<_>>();
 
         List::new(items)
@@ -1,5 +1,7 @@
 use tui::buffer::Buffer;
 use tui::layout::Rect;
+use tui::style::Style;
+use tui::text::{Span, Spans};
 use tui::widgets::Paragraph;
 
 use crate::ui::theme::WidgetStyle;
@@ -20,15 +22,18 @@ fn clamp<T: Ord>(x: T, min: T, max: T) -> T {
 pub struct SearchData {
     chars: Vec<char>,
     input_pos: usize,
+    error: Option<String>,
 }
 
 impl SearchData {
     pub fn append_char(&mut self, c: char) {
+        self.error = None;
         self.chars.insert(self.input_pos, c);
         self.input_pos += 1;
     }
 
     pub fn delete_char(&mut self) {
+        self.error = None;
         if !self.chars.is_empty() && self.input_pos >= 1 {
             self.chars.remove(self.input_pos - 1);
             self.input_pos -= 1;
@@ -45,6 +50,14 @@ impl SearchData {
     pub fn query(&self) -> String {
         self.chars.iter().collect()
     }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
 }
 
 #[derive(Debug, Copy, Clone, Default)]
@@ -54,16 +67,23 @@ pub struct SearchState {
 
 pub struct SearchWidget {
     style: WidgetStyle,
+    error_style: Style,
 }
 
 impl SearchWidget {
-    pub fn with_style(style: WidgetStyle) -> Self {
-        SearchWidget { style }
+    pub fn with_style(style: WidgetStyle, error_style: Style) -> Self {
+        SearchWidget { style, error_style }
     }
 
     fn search_text_from(&self, data: &SearchData) -> Paragraph {
-        let text = data.query();
-        Paragraph::new(text).block(default_block_with_style(&self.style.block, "Search"))
+        let mut spans = vec![Span::raw(data.query())];
+        if let Some(error) = data.error() {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(error.to_string(), self.error_style));
+        }
+
+        Paragraph::new(Spans::from(spans))
+            .block(default_block_with_style(&self.style.block, "Search"))
     }
 }
 
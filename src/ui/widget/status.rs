@@ -0,0 +1,95 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Modifier, Style};
+use tui::text::Span;
+use tui::widgets::Paragraph;
+
+use crate::ui::widget::{render_widget, CustomWidget};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StatusMessage {
+    Info(String),
+    Error(String),
+    /// Text opening the URL when clicked, in terminals supporting OSC 8 hyperlinks.
+    Link {
+        text: String,
+        url: String,
+    },
+}
+
+/// The latest result of an operation, shown at the bottom of the screen.
+#[derive(Debug, Clone, Default)]
+pub struct StatusData {
+    message: Option<StatusMessage>,
+}
+
+impl StatusData {
+    pub fn message(&self) -> Option<&StatusMessage> {
+        self.message.as_ref()
+    }
+
+    pub fn set_info(&mut self, msg: String) {
+        self.message = Some(StatusMessage::Info(msg));
+    }
+
+    pub fn set_error(&mut self, msg: String) {
+        self.message = Some(StatusMessage::Error(msg));
+    }
+
+    pub fn set_link(&mut self, text: String, url: String) {
+        self.message = Some(StatusMessage::Link { text, url });
+    }
+
+    pub fn clear(&mut self) {
+        self.message = None;
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct StatusState {
+    /// Where the text of a link is drawn, to be turned into a hyperlink after drawing.
+    link_area: Option<Rect>,
+}
+
+impl StatusState {
+    pub fn link_area(&self) -> Option<Rect> {
+        self.link_area
+    }
+}
+
+pub struct StatusWidget {
+    style: Style,
+    error_style: Style,
+}
+
+impl StatusWidget {
+    pub fn with_style(style: Style, error_style: Style) -> Self {
+        StatusWidget { style, error_style }
+    }
+}
+
+impl CustomWidget for StatusWidget {
+    type Data = StatusData;
+    type State = StatusState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        state.link_area = None;
+        let span = match data.message() {
+            Some(StatusMessage::Info(msg)) => Span::styled(msg.as_str(), self.style),
+            Some(StatusMessage::Error(msg)) => Span::styled(msg.as_str(), self.error_style),
+            Some(StatusMessage::Link { text, .. }) => {
+                let width = (text.chars().count() as u16).min(area.width);
+                state.link_area = Some(Rect { width, ..area });
+                Span::styled(text.as_str(), self.style.add_modifier(Modifier::UNDERLINED))
+            }
+            None => Span::raw(""),
+        };
+        render_widget(Paragraph::new(span), area, buf);
+    }
+}
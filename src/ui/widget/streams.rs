@@ -0,0 +1,126 @@
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Rect};
+use tui::style::Modifier;
+use tui::widgets::{Row, Table, TableState};
+
+use crate::aws::cwlogs::stream::{CwlStream, CwlStreamStore};
+use crate::time::{TimeDisplay, TimeRange};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::default_block_with_style;
+use crate::ui::widget::stateful::table::TableStateMut;
+use crate::ui::widget::{render_stateful_widget, CustomWidget};
+
+static COLUMNS: [&str; 4] = ["Name", "Last event", "First event", "Created"];
+static WIDTHS: [Constraint; 4] = [
+    Constraint::Percentage(49),
+    Constraint::Length(19),
+    Constraint::Length(19),
+    Constraint::Length(19),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct StreamsState {
+    table: TableState,
+}
+
+impl StreamsState {
+    /// Streams are shown from the most recently written one.
+    pub fn selected_stream<'a>(&self, data: &'a CwlStreamStore) -> Option<&'a CwlStream> {
+        self.table
+            .selected()
+            .and_then(|i| data.order_by_last_event_time_desc().nth(i))
+    }
+}
+
+impl TableStateMut for StreamsState {
+    fn table_state_mut(&mut self) -> &mut TableState {
+        &mut self.table
+    }
+}
+
+pub struct StreamsWidget {
+    style: WidgetStyle,
+    time_range: Option<(TimeRange, String)>,
+    time_display: TimeDisplay,
+}
+
+impl StreamsWidget {
+    pub fn with_style(style: WidgetStyle) -> Self {
+        StreamsWidget {
+            style,
+            time_range: None,
+            time_display: TimeDisplay::default(),
+        }
+    }
+
+    pub fn time_display(self, time_display: TimeDisplay) -> Self {
+        StreamsWidget {
+            time_display,
+            ..self
+        }
+    }
+
+    /// Dims streams without events in the time range.
+    pub fn with_time_range(self, range: TimeRange, label: String) -> Self {
+        StreamsWidget {
+            time_range: Some((range, label)),
+            ..self
+        }
+    }
+
+    fn row_from<'a>(&self, stream: &'a CwlStream) -> Row<'a> {
+        let local = |t| self.time_display.format(t);
+        let row = Row::new(vec![
+            stream.stream_name.clone(),
+            local(&stream.last_event_time),
+            local(&stream.first_event_time),
+            local(&stream.creation_time),
+        ]);
+        match self.time_range.as_ref() {
+            Some((range, _))
+                if !range.overlaps(&stream.first_event_time, &stream.last_event_time) =>
+            {
+                row.style(self.style.table.normal.add_modifier(Modifier::DIM))
+            }
+            _ => row,
+        }
+    }
+}
+
+impl CustomWidget for StreamsWidget {
+    type Data = CwlStreamStore;
+    type State = StreamsState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let header = Row::new(COLUMNS.to_vec())
+            .style(self.style.table.header)
+            .bottom_margin(1);
+        let rows = data
+            .order_by_last_event_time_desc()
+            .map(|s| self.row_from(s))
+            .collect::<Vec<_>>();
+        let mut title = "Streams".to_string();
+        if let Some((_, label)) = self.time_range.as_ref() {
+            title = format!("{} ({})", title, label);
+        }
+        if data.is_loading() {
+            title.push_str(" [loading]");
+        }
+
+        let table = Table::new(rows)
+            .header(header)
+            .block(default_block_with_style(&self.style.block, &title))
+            .widths(&WIDTHS)
+            .style(self.style.table.normal)
+            .highlight_style(self.style.table.highlight)
+            .column_spacing(1);
+
+        render_stateful_widget(table, area, buf, &mut state.table);
+    }
+}
@@ -0,0 +1,191 @@
+use chrono::{DateTime, Utc};
+use tui::buffer::Buffer;
+use tui::layout::{Constraint, Direction, Layout, Rect};
+use tui::style::Style;
+use tui::text::{Span, Spans, Text};
+use tui::widgets::{Clear, List, ListItem, ListState, Paragraph, Widget};
+
+use crate::time::{
+    ParseTimeRangeError, TimeRange, TimeRangeSpec, TimeZoneChoice, TIME_RANGE_PRESETS,
+};
+use crate::ui::theme::WidgetStyle;
+use crate::ui::widget::helper::{apply_item_style, centered_rect, default_block_with_style};
+use crate::ui::widget::stateful::{select_next, select_previous};
+use crate::ui::widget::{render_stateful_widget, render_widget, CustomWidget};
+
+/// Text input of a time range, with quick presets and a choice of time zone.
+#[derive(Debug, Clone)]
+pub struct TimeRangePickerState {
+    current: TimeRangeSpec,
+    input: String,
+    zone: TimeZoneChoice,
+    zones: Vec<TimeZoneChoice>,
+    presets: ListState,
+    error: Option<ParseTimeRangeError>,
+}
+
+impl TimeRangePickerState {
+    pub fn new(current: TimeRangeSpec, zone: TimeZoneChoice) -> Self {
+        let mut zones = vec![TimeZoneChoice::Local, TimeZoneChoice::Utc];
+        if !zones.contains(&zone) {
+            zones.push(zone);
+        }
+
+        TimeRangePickerState {
+            current,
+            input: String::new(),
+            zone,
+            zones,
+            presets: ListState::default(),
+            error: None,
+        }
+    }
+
+    pub fn zone(&self) -> TimeZoneChoice {
+        self.zone
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.input.push(c);
+        self.error = None;
+    }
+
+    pub fn pop(&mut self) {
+        self.input.pop();
+        self.error = None;
+    }
+
+    /// Types the next preset into the input.
+    pub fn select_next(&mut self) {
+        select_next(&mut self.presets, TIME_RANGE_PRESETS.len());
+        self.fill_preset();
+    }
+
+    pub fn select_previous(&mut self) {
+        select_previous(&mut self.presets, TIME_RANGE_PRESETS.len());
+        self.fill_preset();
+    }
+
+    fn fill_preset(&mut self) {
+        if let Some(preset) = self
+            .presets
+            .selected()
+            .and_then(|i| TIME_RANGE_PRESETS.get(i))
+        {
+            self.input = preset.to_string();
+            self.error = None;
+        }
+    }
+
+    /// Switches the time zone of dates and times without offsets.
+    pub fn cycle_zone(&mut self) {
+        let i = self.zones.iter().position(|z| *z == self.zone).unwrap_or(0);
+        self.zone = self.zones[(i + 1) % self.zones.len()];
+    }
+
+    fn preview(&self, now: &DateTime<Utc>) -> Result<TimeRangeSpec, ParseTimeRangeError> {
+        TimeRangeSpec::parse(&self.input, &self.zone, now)
+    }
+
+    /// Parses the input, keeping the error to show it.
+    pub fn parse(&mut self, now: &DateTime<Utc>) -> Option<TimeRangeSpec> {
+        match self.preview(now) {
+            Ok(spec) => Some(spec),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+pub struct TimeRangePickerWidget {
+    style: WidgetStyle,
+    error_style: Style,
+}
+
+impl TimeRangePickerWidget {
+    pub fn with_style(style: WidgetStyle, error_style: Style) -> Self {
+        TimeRangePickerWidget { style, error_style }
+    }
+
+    fn describe(range: &TimeRange, zone: &TimeZoneChoice) -> String {
+        let format = |t: &DateTime<Utc>| zone.format(t, "%Y-%m-%d %H:%M:%S");
+        format!(
+            "{} .. {} ({} ms .. {} ms)",
+            format(&range.start),
+            format(&range.end),
+            range.start_time(),
+            range.end_time()
+        )
+    }
+}
+
+impl CustomWidget for TimeRangePickerWidget {
+    type Data = ();
+    type State = TimeRangePickerState;
+
+    fn render_app_widget(
+        self,
+        area: Rect,
+        buf: &mut Buffer,
+        _data: &Self::Data,
+        state: &mut Self::State,
+    ) {
+        let now = Utc::now();
+        let header = self.style.table.header;
+        let mut lines = vec![
+            Spans::from(vec![
+                Span::styled("Current: ", header),
+                Span::raw(state.current.describe(&state.zone)),
+            ]),
+            Spans::from(vec![
+                Span::styled("Zone:    ", header),
+                Span::raw(state.zone.to_string()),
+            ]),
+            Spans::from(format!("> {}", state.input)),
+        ];
+        let preview = match state.error.as_ref() {
+            Some(e) => Err(e.clone()),
+            None if state.input.trim().is_empty() => Ok(None),
+            None => state.preview(&now).map(Some),
+        };
+        lines.push(match preview {
+            Ok(Some(spec)) => Spans::from(TimeRangePickerWidget::describe(
+                &spec.resolve(&now),
+                &state.zone,
+            )),
+            Ok(None) => Spans::from(Span::styled(
+                "e.g. 15m, 2h ago, yesterday, today 09:00..10:30, 2021-01-02T03:04..05:00",
+                header,
+            )),
+            Err(e) => Spans::from(Span::styled(e.to_string(), self.error_style)),
+        });
+
+        let area = centered_rect(70, 60, area);
+        Clear.render(area, buf);
+        let block = default_block_with_style(
+            &self.style.block,
+            "Time range (<Up>/<Down>: presets, <Tab>: zone, <Enter>: apply, <Esc>: cancel)",
+        );
+        let inner = block.inner(area);
+        render_widget(block, area, buf);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(5), Constraint::Min(0)])
+            .split(inner);
+
+        let paragraph = Paragraph::new(Text::from(lines)).style(self.style.table.normal);
+        render_widget(paragraph, chunks[0], buf);
+
+        let style = &self.style.item;
+        let items = TIME_RANGE_PRESETS
+            .iter()
+            .map(|p| ListItem::new(*p))
+            .map(|i| apply_item_style(i, style))
+            .collect::<Vec<_>>();
+        let list = List::new(items).highlight_style(style.highlight);
+        render_stateful_widget(list, chunks[1], buf, &mut state.presets);
+    }
+}